@@ -4,6 +4,8 @@
 #![allow(clippy::needless_late_init)]
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::unnecessary_cast)]
+// The crate docs show how to write a #[test] for schema compatibility
+#![allow(clippy::test_attr_in_doctest)]
 
 /*!
 This is the documentation for `savefile-abi`
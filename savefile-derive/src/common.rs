@@ -420,6 +420,33 @@ pub(crate) fn parse_attr_tag(attrs: &[syn::Attribute]) -> AttrsResult {
     }
 }
 
+/// Parse a container attribute naming a hook function, like `#[savefile_post_load="rebuild"]`.
+/// A single identifier is taken to be a method of the type itself. Returns the path to call.
+pub(crate) fn parse_hook_attr(attrs: &[syn::Attribute], attr_name: &str) -> Option<TokenStream> {
+    for attr in attrs.iter() {
+        if attr.path().is_ident(attr_name) {
+            if let Meta::NameValue(nv) = &attr.meta {
+                if let Expr::Lit(lit) = &nv.value {
+                    if let Lit::Str(litstr) = &lit.lit {
+                        return match syn::parse_str::<syn::Path>(&litstr.value()) {
+                            Ok(path) if path.get_ident().is_some() => Some(quote! { Self::#path }),
+                            Ok(path) => Some(path.to_token_stream()),
+                            Err(_) => abort!(litstr.span(), "Invalid {} value, expected a function name", attr_name),
+                        };
+                    }
+                }
+            }
+            abort!(
+                attr.span(),
+                "Invalid {} attribute. Use for example #[{}=\"fn_name\"]",
+                attr_name,
+                attr_name
+            );
+        }
+    }
+    None
+}
+
 fn parse_integer(p0: &Expr) -> u32 {
     match p0 {
         Expr::Lit(lit) => match &lit.lit {
@@ -1,10 +1,12 @@
-use crate::common::{check_is_remove, get_extra_where_clauses, parse_attr_tag, FieldInfo, RemovedType};
+use crate::common::{
+    check_is_remove, get_extra_where_clauses, parse_attr_tag, parse_hook_attr, FieldInfo, RemovedType,
+};
 use crate::{doc_hidden, get_enum_size};
 use proc_macro2::{Literal, TokenStream};
 use syn::spanned::Spanned;
 use syn::DeriveInput;
 
-fn implement_deserialize(field_infos: Vec<FieldInfo>) -> Vec<TokenStream> {
+pub(crate) fn implement_deserialize(field_infos: Vec<FieldInfo>) -> Vec<TokenStream> {
     let span = proc_macro2::Span::call_site();
     let defspan = proc_macro2::Span::call_site();
     let removeddef = quote_spanned! { defspan => _savefile::prelude::Removed };
@@ -149,6 +151,17 @@ pub fn savefile_derive_crate_deserialize(input: DeriveInput) -> TokenStream {
 
     let dummy_const = syn::Ident::new("_", proc_macro2::Span::call_site());
 
+    let post_load = parse_hook_attr(&input.attrs, "savefile_post_load");
+    let finish = |output: TokenStream| match &post_load {
+        Some(hook) => quote! {
+            let result: Result<Self, #saveerr> = { #output };
+            let mut value = result?;
+            _savefile::HookResult::into_savefile_result(#hook(&mut value))?;
+            Ok(value)
+        },
+        None => output,
+    };
+
     let expanded = match &input.data {
         &syn::Data::Enum(ref enum1) => {
             let mut output = Vec::new();
@@ -209,6 +222,12 @@ pub fn savefile_derive_crate_deserialize(input: DeriveInput) -> TokenStream {
                 4 => quote! { deserializer.read_u32()?  },
                 _ => unreachable!(),
             };
+            let body = finish(quote! {
+                Ok(match #variant_deserializer {
+                    #(#output,)*
+                    _ => return Err(_savefile::prelude::SavefileError::GeneralError{msg:format!("Corrupt file - unknown enum variant detected.")})
+                })
+            });
 
             quote! {
                 #[allow(non_upper_case_globals)]
@@ -221,11 +240,7 @@ pub fn savefile_derive_crate_deserialize(input: DeriveInput) -> TokenStream {
                     impl #impl_generics #deserialize for #name #ty_generics #where_clause #extra_where {
                         #[allow(unused_comparisons, unused_variables)]
                         fn deserialize(deserializer: &mut #deserializer) -> Result<Self,#saveerr> {
-
-                            Ok(match #variant_deserializer {
-                                #(#output,)*
-                                _ => return Err(_savefile::prelude::SavefileError::GeneralError{msg:format!("Corrupt file - unknown enum variant detected.")})
-                            })
+                            #body
                         }
                     }
                 };
@@ -275,6 +290,7 @@ pub fn savefile_derive_crate_deserialize(input: DeriveInput) -> TokenStream {
                     quote! {Ok(#name )}
                 } //_ => panic!("Only regular structs supported, not tuple structs."),
             };
+            let output = finish(output);
             quote! {
                 #[allow(non_upper_case_globals)]
                 #[allow(clippy::double_comparisons)]
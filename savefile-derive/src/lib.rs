@@ -25,7 +25,7 @@ extern crate proc_macro_error2;
 use crate::savefile_abi::is_well_known;
 use common::{
     check_is_remove, compile_time_check_reprc, compile_time_size, get_extra_where_clauses, parse_attr_tag,
-    path_to_string, FieldInfo,
+    parse_hook_attr, path_to_string, FieldInfo,
 };
 use proc_macro2::TokenStream;
 use proc_macro2::{Span, TokenTree};
@@ -224,6 +224,8 @@ mod deserialize;
 
 mod savefile_abi;
 
+mod remote;
+
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn savefile_abi_exportable(
@@ -880,12 +882,19 @@ pub fn savefile_abi_export(item: proc_macro::TokenStream) -> proc_macro::TokenSt
         savefile_ignore,
         savefile_default_val,
         savefile_default_fn,
-        savefile_doc_hidden
+        savefile_doc_hidden,
+        savefile_remote,
+        savefile_post_load,
+        savefile_pre_save
     )
 )]
 pub fn savefile(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).expect("Expected valid rust code [Savefile]");
 
+    if let Some(remote) = remote::get_remote_path(&input.attrs) {
+        return remote::savefile_derive_crate_remote(input, remote, true).into();
+    }
+
     let s = serialize::savefile_derive_crate_serialize(input.clone());
 
     let d = deserialize::savefile_derive_crate_deserialize(input.clone());
@@ -934,12 +943,19 @@ pub fn savefile(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         savefile_ignore,
         savefile_introspect_ignore,
         savefile_default_val,
-        savefile_default_fn
+        savefile_default_fn,
+        savefile_remote,
+        savefile_post_load,
+        savefile_pre_save
     )
 )]
 pub fn savefile_no_introspect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: DeriveInput = syn::parse(input).expect("Expected valid rust code [SavefileNoIntrospect]");
 
+    if let Some(remote) = remote::get_remote_path(&input.attrs) {
+        return remote::savefile_derive_crate_remote(input, remote, false).into();
+    }
+
     let s = serialize::savefile_derive_crate_serialize(input.clone());
 
     let d = deserialize::savefile_derive_crate_deserialize(input.clone());
@@ -1238,6 +1254,27 @@ fn get_enum_size(attrs: &[syn::Attribute], actual_variants: usize) -> EnumSize {
     }
 }
 
+/// Returns true if the in-memory discriminant of each variant is known to be equal to
+/// the index of the variant.
+fn discriminants_are_indices(enum1: &syn::DataEnum) -> bool {
+    let mut next = Some(0u64);
+    for (index, variant) in enum1.variants.iter().enumerate() {
+        let value = match &variant.discriminant {
+            Some((_, syn::Expr::Lit(lit))) => match &lit.lit {
+                syn::Lit::Int(i) => i.base10_parse::<u64>().ok(),
+                _ => None,
+            },
+            Some(_) => None,
+            None => next,
+        };
+        if value != Some(index as u64) {
+            return false;
+        }
+        next = value.map(|x| x + 1);
+    }
+    true
+}
+
 #[proc_macro_error]
 #[proc_macro_derive(
     Packed,
@@ -1280,6 +1317,16 @@ fn derive_reprc_new(input: DeriveInput) -> TokenStream {
         return implement_reprc_hardcoded_false(name, input.generics);
     }*/
 
+    if parse_hook_attr(&input.attrs, "savefile_post_load").is_some()
+        || parse_hook_attr(&input.attrs, "savefile_pre_save").is_some()
+    {
+        // The hooks would be bypassed if values were copied directly from memory
+        if opt_in_fast {
+            abort_call_site!("The #[savefile_require_fast] attribute cannot be combined with savefile_post_load or savefile_pre_save.");
+        }
+        return implement_reprc_hardcoded_false(name.clone(), &input);
+    }
+
     let expanded = match &input.data {
         &syn::Data::Enum(ref enum1) => {
             let enum_size = get_enum_size(&input.attrs, enum1.variants.len());
@@ -1294,6 +1341,14 @@ fn derive_reprc_new(input: DeriveInput) -> TokenStream {
                 }
                 return implement_reprc_hardcoded_false(name.clone(), &input);
             }
+            if !discriminants_are_indices(enum1) {
+                // Savefile serializes the index of each variant. If the in-memory discriminant
+                // differs from the index, the memory layout can't be used as disk format.
+                if opt_in_fast {
+                    abort_call_site!("The #[savefile_require_fast] attribute cannot be used for enums with explicit discriminants different from the index of each variant.");
+                }
+                return implement_reprc_hardcoded_false(name.clone(), &input);
+            }
 
             let mut conditions = vec![];
 
@@ -1492,7 +1547,7 @@ fn derive_reprc_new(input: DeriveInput) -> TokenStream {
 fn implement_introspect(
     field_infos: Vec<FieldInfo>,
     need_self: bool,
-) -> (Vec<TokenStream>, Vec<TokenStream>, Option<TokenStream>) {
+) -> (Vec<TokenStream>, Vec<TokenStream>, Option<TokenStream>, Vec<TokenStream>) {
     let span = proc_macro2::Span::call_site();
     let defspan = proc_macro2::Span::call_site();
 
@@ -1503,8 +1558,12 @@ fn implement_introspect(
     let introspect_item = quote_spanned! { defspan=>
         _savefile::prelude::introspect_item
     };
+    let introspect_mut_field = quote_spanned! { defspan=>
+        _savefile::prelude::IntrospectMutField
+    };
 
     let mut fields = Vec::new();
+    let mut fields_mut = Vec::new();
     let mut fields_names = Vec::new();
     let mut introspect_key = None;
     let mut index_number = 0usize;
@@ -1528,6 +1587,7 @@ fn implement_introspect(
             fieldname_raw = quote! {#id};
 
             fields.push(quote_spanned!( span => if #index1 == #index_number { return Some(#introspect_item(stringify!(#fieldname_raw).to_string(), #fieldname))}));
+            fields_mut.push(quote_spanned!( span => if #index1 == #index_number { return Some((&&#introspect_mut_field::new(&self.#id)).introspect_field_mut(stringify!(#fieldname_raw).to_string(), &mut self.#id))}));
             if verinfo.introspect_key {
                 let fieldname_raw2 = fieldname_raw.clone();
                 introspect_key = Some(quote! {self.#fieldname_raw2});
@@ -1541,6 +1601,7 @@ fn implement_introspect(
             fieldname = id;
             quoted_fieldname = quote! { #fieldname };
             fields.push(quote_spanned!( span => if #index1 == #index_number { return Some(#introspect_item(#raw_fieldname.to_string(), #quoted_fieldname))}));
+            fields_mut.push(quote_spanned!( span => if #index1 == #index_number { return Some((&&#introspect_mut_field::new(&*#quoted_fieldname)).introspect_field_mut(#raw_fieldname.to_string(), #quoted_fieldname))}));
             fields_names.push(quoted_fieldname);
             if verinfo.introspect_key {
                 introspect_key = Some(quote!(#id2))
@@ -1553,6 +1614,7 @@ fn implement_introspect(
             let fieldname2 = fieldname.clone();
             quoted_fieldname = quote! { #fieldname };
             fields.push(quote_spanned!( span => if #index1 == #index_number { return Some(#introspect_item(#raw_fieldname.to_string(), #quoted_fieldname))}));
+            fields_mut.push(quote_spanned!( span => if #index1 == #index_number { return Some((&&#introspect_mut_field::new(&*#quoted_fieldname)).introspect_field_mut(#raw_fieldname.to_string(), #quoted_fieldname))}));
             fields_names.push(quoted_fieldname);
             if verinfo.introspect_key {
                 introspect_key = Some(quote!(#fieldname2))
@@ -1562,7 +1624,7 @@ fn implement_introspect(
         index_number += 1;
     }
 
-    (fields_names, fields, introspect_key)
+    (fields_names, fields, introspect_key, fields_mut)
 }
 
 #[allow(non_snake_case)]
@@ -1581,9 +1643,28 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
     let introspect_item_type = quote_spanned! {defspan=>
         _savefile::prelude::IntrospectItem
     };
+    let introspect_mut = quote_spanned! {defspan=>
+        _savefile::prelude::IntrospectMut
+    };
+    let introspect_item_mut_type = quote_spanned! {defspan=>
+        _savefile::prelude::IntrospectItemMut
+    };
+    let introspection_error = quote_spanned! {defspan=>
+        _savefile::prelude::IntrospectionError
+    };
+    let introspect_value = quote_spanned! {defspan=>
+        _savefile::prelude::IntrospectValue
+    };
+    let introspect_kind = quote_spanned! {defspan=>
+        _savefile::prelude::IntrospectKind
+    };
     let uses = quote_spanned! { defspan =>
         extern crate savefile as _savefile;
     };
+    let uses_mut = quote_spanned! { defspan =>
+        #[allow(unused_imports)]
+        use _savefile::prelude::{IntrospectMutFieldEditable as _, IntrospectMutFieldReadOnly as _};
+    };
 
     //let SchemaStruct = quote_spanned! { defspan => _savefile::prelude::SchemaStruct };
     //let SchemaEnum = quote_spanned! { defspan => _savefile::prelude::SchemaEnum };
@@ -1596,13 +1677,12 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
     let expanded = match &input.data {
         &syn::Data::Enum(ref enum1) => {
             let mut variants = Vec::new();
+            let mut variants_mut = Vec::new();
             let mut value_variants = Vec::new();
+            let mut typed_value_variants = Vec::new();
             let mut len_variants = Vec::new();
+            let mut unit_variants = Vec::new();
             for variant in enum1.variants.iter() {
-                /*if var_idx >= 256 {
-                    panic!("Savefile does not support enums with 256 variants or more. Sorry.");
-                }*/
-                //let var_idx = var_idx as u8;
                 let var_ident = variant.ident.clone();
                 let variant_name = quote! { #var_ident };
                 let variant_name_spanned = quote_spanned! { span => #variant_name};
@@ -1622,17 +1702,28 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                                 attrs: &f.attrs,
                             });
                         }
-                        let (fields_names, fields, introspect_key) = implement_introspect(field_infos, false);
+                        let (fields_names, fields, introspect_key, fields_mut) = implement_introspect(field_infos, false);
                         let fields_names1 = fields_names.clone();
                         let fields_names2 = fields_names.clone();
                         let fields_names3 = fields_names.clone();
+                        let fields_names4 = fields_names.clone();
                         let num_fields = fields_names3.len();
+                        variants_mut.push(quote!( #name::#variant_name_spanned{#(#fields_names4,)*} => {
+                                #(#fields_mut;)*
+                            } ));
                         if let Some(introspect_key) = introspect_key {
+                            typed_value_variants.push(quote!(#name::#variant_name_spanned{#(#fields_names4,)*} => {
+                                #introspect_key.introspect_typed_value()
+                            }
+                            ));
                             value_variants.push(quote!(#name::#variant_name_spanned{#(#fields_names,)*} => {
                                 #introspect_key.to_string()
                             }
                             ));
                         } else {
+                            typed_value_variants.push(quote!( #name::#variant_name_spanned{#(#fields_names4,)*} => {
+                                #introspect_value::Str(#return_value_name.to_string())
+                            } ));
                             value_variants.push(quote!( #name::#variant_name_spanned{#(#fields_names2,)*} => {
                                 #return_value_name.to_string()
                             } ));
@@ -1654,17 +1745,25 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                                 attrs: &f.attrs,
                             });
                         }
-                        let (fields_names, fields, introspect_key) = implement_introspect(field_infos, false);
+                        let (fields_names, fields, introspect_key, fields_mut) = implement_introspect(field_infos, false);
                         let fields_names1 = fields_names.clone();
                         let fields_names2 = fields_names.clone();
                         let fields_names3 = fields_names.clone();
+                        let fields_names4 = fields_names.clone();
                         let num_fields = fields_names3.len();
+                        variants_mut.push(quote!( #name::#variant_name_spanned(#(#fields_names4,)*) => { #(#fields_mut;)* } ));
 
                         if let Some(introspect_key) = introspect_key {
+                            typed_value_variants.push(quote!( #name::#variant_name_spanned(#(#fields_names4,)*) => {
+                                    #introspect_key.introspect_typed_value()
+                            }));
                             value_variants.push(quote!( #name::#variant_name_spanned(#(#fields_names1,)*) => {
                                     #introspect_key.to_string()
                             }));
                         } else {
+                            typed_value_variants.push(
+                                quote!( #name::#variant_name_spanned(#(#fields_names4,)*) => #introspect_value::Str(#return_value_name.to_string()) )
+                            );
                             value_variants.push(
                                 quote!( #name::#variant_name_spanned(#(#fields_names2,)*) => #return_value_name.to_string() )
                             );
@@ -1680,13 +1779,34 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                         variants.push(quote! {
                             #name::#variant_name_spanned => {}
                         });
+                        variants_mut.push(quote! {
+                            #name::#variant_name_spanned => {}
+                        });
+                        let short_name = var_ident.to_string();
+                        unit_variants.push(quote!( #return_value_name | #short_name => {
+                            *self = #name::#variant_name_spanned;
+                            return Ok(());
+                        } ));
                         value_variants.push(quote!( #name::#variant_name_spanned => #return_value_name.to_string() ));
+                        typed_value_variants.push(quote!( #name::#variant_name_spanned => #introspect_value::Str(#return_value_name.to_string()) ));
                         len_variants.push(quote!( #name::#variant_name_spanned => 0));
                     }
                 }
 
                 //variants.push(quote!{})
             }
+            let set_from_str = if unit_variants.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    fn set_from_str(&mut self, value: &str) -> Result<(), #introspection_error> {
+                        match value.trim() {
+                            #(#unit_variants,)*
+                            _ => Err(#introspection_error::InvalidValue),
+                        }
+                    }
+                }
+            };
             quote! {
                 #[allow(non_upper_case_globals)]
                 #[allow(clippy::double_comparisons)]
@@ -1707,6 +1827,13 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                         }
                         #[allow(unused_mut)]
                         #[allow(unused_comparisons, unused_variables)]
+                        fn introspect_typed_value(&self) -> #introspect_value {
+                            match self {
+                                #(#typed_value_variants,)*
+                            }
+                        }
+                        #[allow(unused_mut)]
+                        #[allow(unused_comparisons, unused_variables)]
                         fn introspect_child(&self, index:usize) -> Option<Box<dyn #introspect_item_type+'_>> {
                             match self {
                                 #(#variants,)*
@@ -1722,6 +1849,21 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                         }
 
                     }
+
+                    #[automatically_derived]
+                    #doc_hidden
+                    impl #impl_generics #introspect_mut for #name #ty_generics #where_clause #extra_where {
+                        #[allow(unused_mut)]
+                        #[allow(unused_comparisons, unused_variables)]
+                        fn introspect_child_mut(&mut self, index:usize) -> Option<Box<dyn #introspect_item_mut_type<'_>+'_>> {
+                            #uses_mut
+                            match self {
+                                #(#variants_mut,)*
+                            }
+                            return None;
+                        }
+                        #set_from_str
+                    }
                 };
             }
         }
@@ -1761,17 +1903,26 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                     fields = implement_introspect(field_infos, true);
                 }
                 &syn::Fields::Unit => {
-                    fields = (Vec::new(), Vec::new(), None);
+                    fields = (Vec::new(), Vec::new(), None, Vec::new());
                 }
             }
             let fields1 = fields.1;
+            let fields_mut = fields.3;
             let introspect_key: Option<TokenStream> = fields.2;
             let field_count = fields1.len();
             let value_name;
+            let typed_value;
             if let Some(introspect_key) = introspect_key {
                 value_name = quote! { #introspect_key.to_string()};
+                typed_value = quote! {
+                    fn introspect_typed_value(&self) -> #introspect_value {
+                        #introspect_key.introspect_typed_value()
+                    }
+                };
             } else {
                 value_name = quote! { stringify!(#name).to_string() };
+                // The default implementation returns None for structs
+                typed_value = quote! {};
             }
             quote! {
                 #[allow(non_upper_case_globals)]
@@ -1788,6 +1939,10 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                         fn introspect_value(&self) -> String {
                             #value_name
                         }
+                        #typed_value
+                        fn introspect_kind(&self) -> #introspect_kind {
+                            #introspect_kind::Struct
+                        }
                         #[allow(unused_comparisons)]
                         #[allow(unused_mut, unused_variables)]
                         fn introspect_child(&self, index: usize) -> Option<Box<dyn #introspect_item_type+'_>> {
@@ -1798,6 +1953,18 @@ fn savefile_derive_crate_introspect(input: DeriveInput) -> TokenStream {
                             #field_count
                         }
                     }
+
+                    #[automatically_derived]
+                    #doc_hidden
+                    impl #impl_generics #introspect_mut for #name #ty_generics #where_clause #extra_where {
+                        #[allow(unused_comparisons)]
+                        #[allow(unused_mut, unused_variables)]
+                        fn introspect_child_mut(&mut self, index: usize) -> Option<Box<dyn #introspect_item_mut_type<'_>+'_>> {
+                            #uses_mut
+                            #(#fields_mut;)*
+                            return None;
+                        }
+                    }
                 };
             }
         }
@@ -2033,6 +2200,21 @@ fn savefile_derive_crate_withschema(input: DeriveInput) -> TokenStream {
     let Field = quote_spanned! { defspan => _savefile::prelude::Field };
     let Variant = quote_spanned! { defspan => _savefile::prelude::Variant };
 
+    // The fingerprint of non-generic types is cached in a static. This can't be done
+    // for generic types, since the static would be shared by all instantiations.
+    let schema_fingerprint = if generics.params.is_empty() {
+        quote! {
+            fn schema_fingerprint(version: u32) -> u128 {
+                static CACHE: _savefile::prelude::SchemaFingerprintCache = _savefile::prelude::SchemaFingerprintCache::new();
+                CACHE.get(version, || {
+                    <Self as #withschema>::schema(version, &mut _savefile::prelude::WithSchemaContext::new()).fingerprint()
+                })
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     //let dummy_const = syn::Ident::new("_", proc_macro2::Span::call_site());
 
     let expanded = match &input.data {
@@ -2045,10 +2227,7 @@ fn savefile_derive_crate_withschema(input: DeriveInput) -> TokenStream {
             let mut variants = Vec::new();
             let mut variant_field_offset_extractors = vec![];
             for (var_idx, variant) in enum1.variants.iter().enumerate() {
-                /*if var_idx >= 256 {
-                    panic!("Savefile does not support enums with 256 total variants. Sorry.");
-                }*/
-                let var_idx = var_idx as u8;
+                let var_idx = var_idx as u32;
                 let var_ident = variant.ident.clone();
                 let variant_name = quote! { #var_ident };
                 let variant_name_spanned = quote_spanned! { span => stringify!(#variant_name).to_string()};
@@ -2247,6 +2426,7 @@ fn savefile_derive_crate_withschema(input: DeriveInput) -> TokenStream {
                             )}
                         )
                     }
+                    #schema_fingerprint
                 }
 
             }
@@ -2322,6 +2502,7 @@ fn savefile_derive_crate_withschema(input: DeriveInput) -> TokenStream {
                         )})
 
                     }
+                    #schema_fingerprint
                 }
             }
         }
@@ -0,0 +1,510 @@
+use crate::common::{check_is_remove, parse_attr_tag, parse_hook_attr, FieldInfo};
+use crate::deserialize::implement_deserialize;
+use crate::{doc_hidden, get_enum_size, implement_withschema, FieldOffsetStrategy};
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+use syn::{DeriveInput, Expr, Ident, Index, Lit, Member, Meta, Path, PathArguments};
+
+/// Returns the path given by the `#[savefile_remote="path::to::Type"]` attribute, if present.
+pub(crate) fn get_remote_path(attrs: &[syn::Attribute]) -> Option<Path> {
+    for attr in attrs.iter() {
+        if attr.path().is_ident("savefile_remote") {
+            if let Meta::NameValue(nv) = &attr.meta {
+                if let Expr::Lit(lit) = &nv.value {
+                    if let Lit::Str(litstr) = &lit.lit {
+                        match syn::parse_str::<Path>(&litstr.value()) {
+                            Ok(path) => return Some(path),
+                            Err(_) => abort!(litstr.span(), "Invalid savefile_remote value, expected a type path"),
+                        }
+                    }
+                }
+            }
+            abort!(
+                attr.span(),
+                "Invalid savefile_remote attribute. Use for example #[savefile_remote=\"other_crate::Type\"]"
+            );
+        }
+    }
+    None
+}
+
+/// A field of the mirror type, and how it maps to the remote type.
+struct RemoteField<'a> {
+    field: &'a syn::Field,
+    index: u32,
+    /// Local variable the field value is bound to
+    binding: Ident,
+    /// The corresponding field of the remote type. None for removed fields,
+    /// which need not exist in the remote type.
+    member: Option<Member>,
+}
+
+impl<'a> RemoteField<'a> {
+    fn info(&self) -> FieldInfo<'a> {
+        FieldInfo {
+            field_span: self.field.span(),
+            ident: self.field.ident.clone(),
+            index: self.index,
+            ty: &self.field.ty,
+            attrs: &self.field.attrs,
+        }
+    }
+    fn key(&self) -> String {
+        match &self.field.ident {
+            Some(ident) => ident.to_string(),
+            None => self.index.to_string(),
+        }
+    }
+}
+
+fn remote_fields(fields: &syn::Fields) -> Vec<RemoteField> {
+    let mut result = Vec::new();
+    let mut remote_index = 0;
+    for (idx, field) in fields.iter().enumerate() {
+        let member = if check_is_remove(&field.ty).is_removed() {
+            None
+        } else {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(remote_index)),
+            };
+            remote_index += 1;
+            Some(member)
+        };
+        result.push(RemoteField {
+            field,
+            index: idx as u32,
+            binding: Ident::new(&format!("f{}", idx), Span::call_site()),
+            member,
+        });
+    }
+    result
+}
+
+/// Pattern binding a reference to each field of the remote value. Removed fields are bound
+/// to dummy values, so that they can be handled like other fields.
+fn bind_fields(path: &TokenStream, fields: &[RemoteField]) -> (TokenStream, Vec<TokenStream>) {
+    let mut members = vec![];
+    let mut dummies = vec![];
+    for field in fields {
+        let binding = &field.binding;
+        if let Some(member) = &field.member {
+            members.push(quote! { #member: #binding });
+        } else {
+            let ty = &field.field.ty;
+            dummies.push(quote! { let #binding = &<#ty>::new(); });
+        }
+    }
+    (quote! { #path { #(#members,)* .. } }, dummies)
+}
+
+fn implement_remote_serialize(fields: &[RemoteField]) -> Vec<TokenStream> {
+    let mut output = vec![];
+    for field in fields {
+        let verinfo = parse_attr_tag(&field.field.attrs);
+        if verinfo.ignore {
+            continue;
+        }
+        let (field_from_version, field_to_version) = (verinfo.version_from, verinfo.version_to);
+        let ty = &field.field.ty;
+        let binding = &field.binding;
+        if field_from_version == 0 && field_to_version == u32::MAX {
+            if field.member.is_none() {
+                abort!(
+                    ty.span(),
+                    "The Removed type can only be used for removed fields. Use the savefile_versions attribute."
+                );
+            }
+            output.push(quote! {
+                <#ty as _savefile::prelude::Serialize>::serialize(#binding, serializer)?;
+            });
+        } else {
+            output.push(quote! {
+                if serializer.file_version >= #field_from_version && serializer.file_version <= #field_to_version {
+                    <#ty as _savefile::prelude::Serialize>::serialize(#binding, serializer)?;
+                }
+            });
+        }
+    }
+    output
+}
+
+fn implement_remote_deserialize(path: &TokenStream, fields: &[RemoteField]) -> TokenStream {
+    let mut reads = vec![];
+    let mut members = vec![];
+    for field in fields {
+        let binding = &field.binding;
+        let mut info = field.info();
+        // Without an identifier, implement_deserialize gives just the expression
+        info.ident = None;
+        let src = implement_deserialize(vec![info]);
+        reads.push(quote! { let #binding = #(#src)*; });
+        if let Some(member) = &field.member {
+            members.push(quote! { #member: #binding });
+        }
+    }
+    quote! {
+        #(#reads)*
+        #path { #(#members,)* }
+    }
+}
+
+fn implement_remote_introspect(fields: &[RemoteField]) -> (Vec<TokenStream>, Option<TokenStream>) {
+    let mut children = vec![];
+    let mut introspect_key = None;
+    let mut index_number = 0usize;
+    for field in fields {
+        let verinfo = parse_attr_tag(&field.field.attrs);
+        if verinfo.introspect_key && introspect_key.is_some() {
+            abort!(
+                field.field.span(),
+                "Type had more than one field with savefile_introspect_key - attribute"
+            );
+        }
+        if verinfo.introspect_ignore || field.member.is_none() {
+            continue;
+        }
+        let binding = &field.binding;
+        let key = field.key();
+        children.push(quote! {
+            if index == #index_number {
+                return Some(_savefile::prelude::introspect_item(#key.to_string(), #binding));
+            }
+        });
+        if verinfo.introspect_key {
+            introspect_key = Some(quote! { #binding.to_string() });
+        }
+        index_number += 1;
+    }
+    (children, introspect_key)
+}
+
+/// A match arm reading all fields of the mirror type, and an expression constructing it.
+/// The mirror type is never instantiated, and these keep the compiler from warning about
+/// unused fields and variants.
+fn use_mirror(path: TokenStream, fields: &syn::Fields) -> (TokenStream, TokenStream) {
+    let mut reads = vec![];
+    let mut writes = vec![];
+    for (idx, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(idx)),
+        };
+        let binding = Ident::new(&format!("f{}", idx), Span::call_site());
+        reads.push(quote! { #member: ref #binding });
+        writes.push(quote! { #member: unreachable!() });
+    }
+    (
+        quote! { #path { #(#reads,)* } => {} },
+        quote! { #path { #(#writes,)* } },
+    )
+}
+
+/// The path, without generic arguments, for use in patterns and constructors.
+fn strip_generics(path: &Path) -> Path {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.arguments = PathArguments::None;
+    }
+    path
+}
+
+/// Implements RemoteMirror for a type with the savefile_remote attribute.
+pub(crate) fn savefile_derive_crate_remote(input: DeriveInput, remote: Path, with_introspect: bool) -> TokenStream {
+    let name = &input.ident;
+    let doc_hidden = doc_hidden(&input.attrs);
+    if parse_hook_attr(&input.attrs, "savefile_post_load").is_some()
+        || parse_hook_attr(&input.attrs, "savefile_pre_save").is_some()
+    {
+        abort_call_site!("The savefile_post_load and savefile_pre_save attributes are not supported together with savefile_remote.");
+    }
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let remote_path = strip_generics(&remote);
+    let remote_name = remote
+        .segments
+        .last()
+        .expect("Expected at least one segment")
+        .ident
+        .to_string();
+
+    let Schema = quote! { _savefile::prelude::Schema };
+    let SchemaStruct = quote! { _savefile::prelude::SchemaStruct };
+    let SchemaEnum = quote! { _savefile::prelude::SchemaEnum };
+    let Field = quote! { _savefile::prelude::Field };
+    let Variant = quote! { _savefile::prelude::Variant };
+    let saveerr = quote! { _savefile::prelude::SavefileError };
+
+    let mut mirror_reads = vec![];
+    let mut mirror_writes = vec![];
+    let schema;
+    let serialize;
+    let deserialize;
+    let introspect_value;
+    let introspect_child;
+    let introspect_len;
+
+    match &input.data {
+        syn::Data::Struct(struc) => {
+            let (read, write) = use_mirror(quote! { #name }, &struc.fields);
+            mirror_reads.push(read);
+            mirror_writes.push(write);
+            let fields = remote_fields(&struc.fields);
+            let path = quote! { #remote_path };
+            let (pattern, dummies) = bind_fields(&path, &fields);
+
+            let field_schemas = implement_withschema(
+                &name.to_string(),
+                fields.iter().map(|x| x.info()).collect(),
+                // The memory layout of the remote type is not known
+                FieldOffsetStrategy::EnumWithUnknownOffsets,
+                generics,
+                &ty_generics,
+                &impl_generics,
+            );
+            schema = quote! {
+                let local_version = version;
+                let mut fields1 = Vec::new();
+                #(#field_schemas;)*
+                #Schema::Struct(#SchemaStruct::new(#remote_name.to_string(), fields1))
+            };
+
+            let field_serializers = implement_remote_serialize(&fields);
+            serialize = quote! {
+                let #pattern = value;
+                #(#dummies)*
+                #(#field_serializers)*
+            };
+
+            deserialize = implement_remote_deserialize(&path, &fields);
+
+            let (children, introspect_key) = implement_remote_introspect(&fields);
+            let child_count = children.len();
+            introspect_value = match introspect_key {
+                Some(key) => quote! {
+                    let #pattern = value;
+                    #key
+                },
+                None => quote! { #remote_name.to_string() },
+            };
+            introspect_child = quote! {
+                let #pattern = value;
+                #(#children)*
+                None
+            };
+            introspect_len = quote! { #child_count };
+        }
+        syn::Data::Enum(enum1) => {
+            let enum_size = get_enum_size(&input.attrs, enum1.variants.len());
+            let discriminant_size = enum_size.discriminant_size;
+
+            let mut variant_schemas = vec![];
+            let mut serialize_arms = vec![];
+            let mut deserialize_arms = vec![];
+            let mut value_arms = vec![];
+            let mut child_arms = vec![];
+            let mut len_arms = vec![];
+            for (var_idx_usize, variant) in enum1.variants.iter().enumerate() {
+                let var_idx = var_idx_usize as u32;
+                let var_ident = &variant.ident;
+                let variant_name_str = var_ident.to_string();
+                let full_name_str = format!("{}::{}", remote_name, var_ident);
+                let path = quote! { #remote_path::#var_ident };
+
+                let verinfo = parse_attr_tag(&variant.attrs);
+                let (field_from_version, field_to_version) = (verinfo.version_from, verinfo.version_to);
+                if field_to_version != u32::MAX {
+                    abort!(
+                        variant.span(),
+                        "Savefile automatic derive does not support removal of enum values."
+                    );
+                }
+
+                let (read, write) = use_mirror(quote! { #name::#var_ident }, &variant.fields);
+                mirror_reads.push(read);
+                mirror_writes.push(write);
+                let fields = remote_fields(&variant.fields);
+                let (pattern, dummies) = bind_fields(&path, &fields);
+
+                let field_schemas = implement_withschema(
+                    &name.to_string(),
+                    fields.iter().map(|x| x.info()).collect(),
+                    FieldOffsetStrategy::EnumWithUnknownOffsets,
+                    generics,
+                    &ty_generics,
+                    &impl_generics,
+                );
+                variant_schemas.push(quote! {
+                    (#field_from_version,
+                     #field_to_version,
+                     #Variant { name: #variant_name_str.to_string(), discriminant: #var_idx, fields:
+                        {
+                            let mut fields1 = Vec::<#Field>::new();
+                            #(#field_schemas;)*
+                            fields1
+                        }}
+                    )
+                });
+
+                let variant_serializer = match discriminant_size {
+                    1 => {
+                        let var_idx = var_idx as u8;
+                        quote! { serializer.write_u8(#var_idx)?; }
+                    }
+                    2 => {
+                        let var_idx = var_idx as u16;
+                        quote! { serializer.write_u16(#var_idx)?; }
+                    }
+                    4 => quote! { serializer.write_u32(#var_idx)?; },
+                    _ => unreachable!(),
+                };
+                let field_serializers = implement_remote_serialize(&fields);
+                serialize_arms.push(quote! {
+                    #pattern => {
+                        if serializer.file_version < #field_from_version || serializer.file_version > #field_to_version {
+                            panic!("Enum {}, variant {} is not present in version {}", #remote_name, #variant_name_str, serializer.file_version);
+                        }
+                        #(#dummies)*
+                        #variant_serializer
+                        #(#field_serializers)*
+                    }
+                });
+
+                let variant_deserializer = implement_remote_deserialize(&path, &fields);
+                deserialize_arms.push(quote! {
+                    #var_idx => { #variant_deserializer }
+                });
+
+                let (children, introspect_key) = implement_remote_introspect(&fields);
+                let child_count = children.len();
+                match introspect_key {
+                    Some(key) => value_arms.push(quote! { #pattern => #key }),
+                    None => value_arms.push(quote! { #pattern => #full_name_str.to_string() }),
+                }
+                child_arms.push(quote! {
+                    #pattern => {
+                        #(#children)*
+                    }
+                });
+                len_arms.push(quote! { #pattern => #child_count });
+            }
+
+            // Remote enums may be non_exhaustive, so all matches need a catch-all arm
+            schema = quote! {
+                let local_version = version;
+                #Schema::Enum(#SchemaEnum::new(
+                    #remote_name.to_string(),
+                    #discriminant_size,
+                    (vec![#(#variant_schemas),*]).into_iter().filter_map(|(fromver,tover,x)|{
+                        if local_version >= fromver && local_version <= tover {
+                            Some(x)
+                        } else {
+                            None
+                        }
+                    }).collect(),
+                ))
+            };
+            serialize = quote! {
+                #[allow(unreachable_patterns)]
+                match value {
+                    #(#serialize_arms,)*
+                    _ => return Err(#saveerr::GeneralError{msg: format!("Enum {} has a variant unknown to its savefile_remote mirror.", #remote_name)})
+                }
+            };
+            let variant_reader = match discriminant_size {
+                1 => quote! { deserializer.read_u8()? as u32 },
+                2 => quote! { deserializer.read_u16()? as u32 },
+                4 => quote! { deserializer.read_u32()? },
+                _ => unreachable!(),
+            };
+            deserialize = quote! {
+                match #variant_reader {
+                    #(#deserialize_arms,)*
+                    _ => return Err(#saveerr::GeneralError{msg:format!("Corrupt file - unknown enum variant detected.")})
+                }
+            };
+            introspect_value = quote! {
+                #[allow(unreachable_patterns)]
+                match value {
+                    #(#value_arms,)*
+                    _ => #remote_name.to_string()
+                }
+            };
+            introspect_child = quote! {
+                #[allow(unreachable_patterns)]
+                match value {
+                    #(#child_arms,)*
+                    _ => {}
+                }
+                None
+            };
+            introspect_len = quote! {
+                #[allow(unreachable_patterns)]
+                match value {
+                    #(#len_arms,)*
+                    _ => 0
+                }
+            };
+        }
+        _ => {
+            abort_call_site!("Unsupported datatype");
+        }
+    }
+
+    let introspect = if with_introspect {
+        quote! {
+            #[allow(unused_variables)]
+            fn introspect_value(value: &#remote) -> String {
+                #introspect_value
+            }
+            #[allow(unused_variables)]
+            fn introspect_child(value: &#remote, index: usize) -> Option<Box<dyn _savefile::prelude::IntrospectItem<'_> + '_>> {
+                #introspect_child
+            }
+            #[allow(unused_variables)]
+            fn introspect_len(value: &#remote) -> usize {
+                #introspect_len
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let dummy_const = syn::Ident::new("_", proc_macro2::Span::call_site());
+    quote! {
+        #[allow(non_upper_case_globals)]
+        #[allow(clippy::double_comparisons)]
+        #[allow(clippy::manual_range_contains)]
+        const #dummy_const: () = {
+            extern crate savefile as _savefile;
+
+            #[allow(dead_code, unreachable_code, unused_variables)]
+            fn use_mirror #impl_generics (mirror: &#name #ty_generics) -> Vec<#name #ty_generics> #where_clause {
+                match *mirror {
+                    #(#mirror_reads,)*
+                }
+                vec![#(#mirror_writes,)*]
+            }
+
+            #[automatically_derived]
+            #doc_hidden
+            impl #impl_generics _savefile::prelude::RemoteMirror<#remote> for #name #ty_generics #where_clause {
+                #[allow(unused_mut, unused_comparisons, unused_variables)]
+                fn schema(version: u32, context: &mut _savefile::prelude::WithSchemaContext) -> #Schema {
+                    #schema
+                }
+                #[allow(unused_comparisons, unused_variables)]
+                fn serialize(value: &#remote, serializer: &mut _savefile::prelude::Serializer<impl std::io::Write>) -> Result<(), #saveerr> {
+                    #serialize
+                    Ok(())
+                }
+                #[allow(unused_comparisons, unused_variables)]
+                fn deserialize(deserializer: &mut _savefile::prelude::Deserializer<impl std::io::Read>) -> Result<#remote, #saveerr> {
+                    Ok({ #deserialize })
+                }
+                #introspect
+            }
+        };
+    }
+}
@@ -573,8 +573,8 @@ fn parse_type(
             return ArgType::Reference(Box::new(inner), typref.mutability.is_some(), lifetime);
         }
         Type::Tuple(tuple) => {
-            if tuple.elems.len() > 3 {
-                abort!(tuple.span(), "Savefile presently only supports tuples up to 3 members. Either change to using a struct, or file an issue on savefile!");
+            if tuple.elems.len() > 12 {
+                abort!(tuple.span(), "Savefile presently only supports tuples up to 12 members. Either change to using a struct, or file an issue on savefile!");
             }
             rawtype = typ;
         }
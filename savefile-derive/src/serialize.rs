@@ -1,7 +1,7 @@
 use proc_macro2::{Span, TokenStream};
 use syn::{Attribute, DeriveInput};
 
-use crate::common::{get_extra_where_clauses, parse_attr_tag, FieldInfo};
+use crate::common::{get_extra_where_clauses, parse_attr_tag, parse_hook_attr, FieldInfo};
 use crate::{doc_hidden, get_enum_size};
 use crate::implement_fields_serialize;
 use syn::spanned::Spanned;
@@ -40,6 +40,13 @@ pub(super) fn savefile_derive_crate_serialize(input: DeriveInput) -> TokenStream
 
     let dummy_const = syn::Ident::new("_", proc_macro2::Span::call_site());
 
+    let pre_save = match parse_hook_attr(&input.attrs, "savefile_pre_save") {
+        Some(hook) => quote! {
+            _savefile::HookResult::into_savefile_result(#hook(self))?;
+        },
+        None => quote! {},
+    };
+
     let expanded = match &input.data {
         &syn::Data::Enum(ref enum1) => {
             let mut output = Vec::new();
@@ -144,6 +151,7 @@ pub(super) fn savefile_derive_crate_serialize(input: DeriveInput) -> TokenStream
 
                         #[allow(unused_comparisons, unused_variables)]
                         fn serialize(&self, serializer: &mut #serializer) -> #saveerr {
+                            #pre_save
                             match self {
                                 #(#output,)*
                             }
@@ -210,6 +218,7 @@ pub(super) fn savefile_derive_crate_serialize(input: DeriveInput) -> TokenStream
                     impl #impl_generics #serialize for #name #ty_generics #where_clause #extra_where {
                         #[allow(unused_comparisons, unused_variables)]
                         fn serialize(&self, serializer: &mut #serializer)  -> #saveerr {
+                            #pre_save
                             #fields_serialize
                             Ok(())
                         }
@@ -21,8 +21,10 @@ pub trait AdvancedTestInterface: Send {
 
     fn return_boxed_closure(&self) -> Box<dyn Fn() -> u32>;
     fn return_boxed_closure2(&self) -> Box<dyn Fn()>;
+    #[allow(clippy::type_complexity)]
     fn many_callbacks(&mut self, x: &mut dyn FnMut(&dyn Fn(&dyn Fn() -> u32) -> u32) -> u32) -> u32;
 
+    #[allow(clippy::type_complexity)]
     fn buf_callback(&mut self, cb: Box<dyn Fn(&[u8], String) + Send + Sync>);
     fn return_boxed_closure_result(&self, fail: bool) -> Result<Box<dyn Fn() -> u32>, ()>;
     fn owned_boxed_closure_param(&self, owned: Box<dyn Fn() -> u32>);
@@ -72,10 +74,12 @@ impl AdvancedTestInterface for AdvancedTestInterfaceImpl {
         slice.iter().copied().sum()
     }
 
+    #[allow(clippy::type_complexity)]
     fn many_callbacks(&mut self, x: &mut dyn FnMut(&dyn Fn(&dyn Fn() -> u32) -> u32) -> u32) -> u32 {
         x(&|y| y())
     }
 
+    #[allow(clippy::type_complexity)]
     fn buf_callback(&mut self, cb: Box<dyn Fn(&[u8], String) + Send + Sync>) {
         cb(&[1, 2, 3], "hello".to_string())
     }
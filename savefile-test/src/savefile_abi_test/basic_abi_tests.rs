@@ -30,6 +30,7 @@ pub trait TestInterface {
     fn do_mut_nothing(&mut self);
 
     fn deref_u32(&self, x: &u32) -> u32;
+    #[allow(clippy::ptr_arg)]
     fn count_chars(&self, x: &String) -> usize;
     fn count_chars_str(&self, x: &str) -> usize;
 
@@ -39,6 +40,8 @@ pub trait TestInterface {
     fn tuple_add1(&self, a: (u32,), b: (u32,)) -> (u32,);
     fn tuple_add2(&self, a: (u32, u32), b: (u32, u32)) -> (u32, u32);
     fn tuple_add3(&self, a: (u32, u32, u32), b: (u32, u32, u32)) -> (u32, u32, u32);
+    fn tuple_split4(&self, a: (u8, u16, u32, u64)) -> (u64, u32, u16, u8);
+    fn tuple_concat5(&self, a: (String, String, String, String, String)) -> String;
 
     fn boxes(&self, a: Box<u32>) -> Box<u32>;
 
@@ -49,6 +52,7 @@ pub trait TestInterface {
     fn get_static_str(&self) -> &'static str;
     // Test using lots of symbol-names from the derive-macro, to verify
     // there's no crashes
+    #[allow(clippy::too_many_arguments)]
     fn test_macro_hygiene(
         &self,
         context: SomeRandomType,
@@ -133,6 +137,14 @@ impl TestInterface for TestInterfaceImpl {
         (a.0 + b.0, a.1 + b.1, a.2 + b.2)
     }
 
+    fn tuple_split4(&self, a: (u8, u16, u32, u64)) -> (u64, u32, u16, u8) {
+        (a.3, a.2, a.1, a.0)
+    }
+
+    fn tuple_concat5(&self, a: (String, String, String, String, String)) -> String {
+        a.0 + &a.1 + &a.2 + &a.3 + &a.4
+    }
+
     fn boxes(&self, a: Box<u32>) -> Box<u32> {
         a
     }
@@ -176,6 +188,17 @@ fn test_basic_call_abi() {
     assert_eq!(conn.tuple_add1((1,), (2,)), (3,));
     assert_eq!(conn.tuple_add2((1, 1), (2, 2)), (3, 3));
     assert_eq!(conn.tuple_add3((1, 1, 1), (2, 2, 2)), (3, 3, 3));
+    assert_eq!(conn.tuple_split4((1, 2, 3, 4)), (4, 3, 2, 1));
+    assert_eq!(
+        conn.tuple_concat5((
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+            "e".to_string()
+        )),
+        "abcde"
+    );
     assert_eq!(conn.boxes(Box::new(42u32)), Box::new(42u32));
     assert_eq!(conn.test_default_impl(), "hello");
 
@@ -0,0 +1,194 @@
+use savefile::codegen::schema_to_python;
+use savefile::prelude::*;
+use savefile::save_file_compressed;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+#[derive(Savefile, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Savefile, Debug)]
+pub enum Shape {
+    Circle(f64),
+    Polygon { corners: Vec<Point> },
+    Empty,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Tree {
+    pub label: String,
+    pub children: Vec<Tree>,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Simulation {
+    pub name: String,
+    pub step: u64,
+    pub samples: Vec<f64>,
+    pub points: Vec<Point>,
+    pub shapes: Vec<Shape>,
+    pub params: BTreeMap<String, i32>,
+    pub origin: Option<Point>,
+    pub range: (i8, u128),
+    pub outcome: Result<u16, String>,
+    pub flags: [bool; 3],
+    pub tree: Tree,
+    pub elapsed: Duration,
+    pub initial: char,
+}
+
+fn simulation() -> Simulation {
+    Simulation {
+        name: "run \"1\"".to_string(),
+        step: 1 << 40,
+        samples: vec![0.5, -1.25],
+        points: vec![Point { x: 1.0, y: 2.0 }, Point { x: -3.0, y: 0.5 }],
+        shapes: vec![
+            Shape::Circle(2.0),
+            Shape::Polygon {
+                corners: vec![Point { x: 0.0, y: 1.0 }],
+            },
+            Shape::Empty,
+        ],
+        params: [("a".to_string(), 1), ("b".to_string(), -2)].into_iter().collect(),
+        origin: None,
+        range: (-3, 1 << 100),
+        outcome: Err("bad".to_string()),
+        flags: [true, false, true],
+        tree: Tree {
+            label: "root".to_string(),
+            children: vec![Tree {
+                label: "leaf".to_string(),
+                children: vec![],
+            }],
+        },
+        elapsed: Duration::from_millis(1500),
+        initial: 'x',
+    }
+}
+
+const EXPECTED: &str = "Simulation(name='run \"1\"', step=1099511627776, samples=[0.5, -1.25], \
+    points=[Point(x=1.0, y=2.0), Point(x=-3.0, y=0.5)], \
+    shapes=[Shape_Circle(_0=2.0), Shape_Polygon(corners=[Point(x=0.0, y=1.0)]), Shape_Empty()], \
+    params={'a': 1, 'b': -2}, origin=None, range=(-3, 1267650600228229401496703205376), \
+    outcome=Err(value='bad'), flags=[True, False, True], \
+    tree=Tree(label='root', children=[Tree(label='leaf', children=[])]), \
+    elapsed=datetime.timedelta(seconds=1, microseconds=500000), initial='x')";
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("savefile-codegen-python-{}-{}", name, std::process::id()));
+    _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+/// Run a Python script in 'dir', which contains the generated module 'reader.py'.
+/// These tests require python3.
+fn run_python(dir: &Path, script: &str) -> String {
+    std::fs::write(dir.join("reader.py"), schema_to_python(&get_schema::<Simulation>(0))).unwrap();
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(script)
+        .current_dir(dir)
+        .output()
+        .unwrap_or_else(|err| panic!("python3 could not be run: {}", err));
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// A file with an embedded schema, in the format of savefile format version 'lib_version'.
+/// Before version 3, the schema has no length prefix, and there is no schema fingerprint.
+/// Version 0 schemas can't be written anymore.
+fn old_format_file(lib_version: u16) -> Vec<u8> {
+    let mut data = b"savefile\0".to_vec();
+    data.extend(lib_version.to_le_bytes());
+    data.extend(0u32.to_le_bytes());
+    data.push(0);
+    get_schema::<Simulation>(0)
+        .serialize(&mut Serializer::<Vec<u8>>::new_raw(&mut data, lib_version as u32))
+        .unwrap();
+    Serializer::<Vec<u8>>::bare_serialize(&mut data, 0, &simulation()).unwrap();
+    data
+}
+
+/// Load files with the generated module, without numpy
+fn load_with_python(dir: &Path, load: &str) -> String {
+    let script = format!(
+        "import sys\nsys.modules['numpy'] = None\nimport reader\nprint(repr({}))",
+        load
+    );
+    run_python(dir, &script)
+}
+
+#[test]
+fn test_python_loads_saved_files() {
+    let dir = temp_dir("load");
+    save_file(dir.join("plain.bin"), 0, &simulation()).unwrap();
+    save_file_compressed(dir.join("compressed.bin"), 0, &simulation()).unwrap();
+    save_file_noschema(dir.join("noschema.bin"), 0, &simulation()).unwrap();
+    save_file_with_metadata(dir.join("metadata.bin"), 0, &simulation(), &"metadata".to_string()).unwrap();
+
+    assert_eq!(load_with_python(&dir, "reader.load_file('plain.bin')"), EXPECTED);
+    assert_eq!(load_with_python(&dir, "reader.load_file('compressed.bin')"), EXPECTED);
+    assert_eq!(
+        load_with_python(&dir, "reader.load_file('noschema.bin', embedded_schema=False)"),
+        EXPECTED
+    );
+    assert_eq!(load_with_python(&dir, "reader.load_file('noschema.bin')"), EXPECTED);
+    assert_eq!(load_with_python(&dir, "reader.load_file('metadata.bin')"), EXPECTED);
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_python_loads_old_format_files() {
+    let dir = temp_dir("old");
+    for lib_version in [1u16, 2] {
+        let data = old_format_file(lib_version);
+        // Check that the hand-written file is valid
+        let loaded: Simulation = load(&mut &data[..], 0).unwrap();
+        assert_eq!(loaded.name, simulation().name);
+        std::fs::write(dir.join(format!("old{}.bin", lib_version)), data).unwrap();
+        let output = load_with_python(&dir, &format!("reader.load_file('old{}.bin')", lib_version));
+        assert_eq!(output, EXPECTED);
+    }
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_python_rejects_other_schema() {
+    let dir = temp_dir("reject");
+    save_file(dir.join("other.bin"), 0, &vec![1u32, 2, 3]).unwrap();
+    let script = "\
+import reader
+try:
+    reader.load_file('other.bin')
+except reader.SavefileError as err:
+    print(err)";
+    let output = run_python(&dir, script);
+    assert!(output.starts_with("File has schema fingerprint"), "{}", output);
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+#[ignore] // Requires numpy. Run using 'cargo test -- --ignored', as CI does.
+fn test_python_numpy_fast_paths() {
+    let dir = temp_dir("numpy");
+    save_file(dir.join("plain.bin"), 0, &simulation()).unwrap();
+    let script = "\
+import numpy
+import reader
+value = reader.load_file('plain.bin')
+assert isinstance(value.samples, numpy.ndarray)
+print(value.samples.tolist(), value.points['x'].tolist(), value.points['y'].tolist(), value.flags.tolist())";
+    assert_eq!(
+        run_python(&dir, script),
+        "[0.5, -1.25] [1.0, -3.0] [2.0, 0.5] [True, False, True]"
+    );
+    _ = std::fs::remove_dir_all(&dir);
+}
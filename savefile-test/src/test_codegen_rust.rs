@@ -0,0 +1,142 @@
+use savefile::codegen::{schema_to_rust, RustCodegenOptions};
+use savefile::prelude::*;
+
+mod original {
+    use savefile::prelude::*;
+    use std::collections::HashMap;
+
+    #[derive(Savefile, Debug)]
+    pub struct Inventory {
+        pub owner: Person,
+        pub items: Vec<Item>,
+        pub tags: HashMap<String, u32>,
+        pub location: Option<(f64, f64)>,
+        pub checksum: [u8; 4],
+        pub last_result: Result<u32, String>,
+        pub r#type: Wrapper<u32>,
+        pub other: Wrapper<String>,
+        pub tree: Tree,
+        pub list: List,
+        pub wide: Wide,
+    }
+
+    #[derive(Savefile, Debug)]
+    pub struct Person {
+        pub name: String,
+        pub age: u16,
+    }
+
+    #[derive(Savefile, Debug)]
+    pub enum Item {
+        Tool(String),
+        Food {
+            calories: u32,
+        },
+        #[savefile_versions = "1.."]
+        Added,
+        Nothing,
+    }
+
+    #[derive(Savefile, Debug)]
+    pub struct Wrapper<T> {
+        pub value: T,
+    }
+
+    #[derive(Savefile, Debug)]
+    pub struct Tree {
+        pub label: String,
+        pub children: Vec<Tree>,
+    }
+
+    #[derive(Savefile, Debug)]
+    pub enum List {
+        Cons(u32, Box<List>),
+        Nil,
+    }
+
+    #[derive(Savefile, Debug)]
+    #[repr(u16)]
+    pub enum Wide {
+        A,
+        B,
+    }
+}
+
+#[allow(dead_code)]
+mod generated {
+    include!("test_codegen_rust_expected.rs");
+}
+
+fn sample() -> original::Inventory {
+    original::Inventory {
+        owner: original::Person {
+            name: "Ada".to_string(),
+            age: 36,
+        },
+        items: vec![
+            original::Item::Tool("hammer".to_string()),
+            original::Item::Food { calories: 250 },
+            original::Item::Nothing,
+        ],
+        tags: [("shelf".to_string(), 3)].into_iter().collect(),
+        location: Some((1.5, -2.0)),
+        checksum: [1, 2, 3, 4],
+        last_result: Err("failed".to_string()),
+        r#type: original::Wrapper { value: 7 },
+        other: original::Wrapper {
+            value: "wrapped".to_string(),
+        },
+        tree: original::Tree {
+            label: "root".to_string(),
+            children: vec![original::Tree {
+                label: "leaf".to_string(),
+                children: vec![],
+            }],
+        },
+        list: original::List::Cons(1, Box::new(original::List::Cons(2, Box::new(original::List::Nil)))),
+        wide: original::Wide::B,
+    }
+}
+
+#[test]
+fn test_generated_rust_is_up_to_date() {
+    let generated = schema_to_rust(&get_schema::<original::Inventory>(0), &RustCodegenOptions::default());
+    assert_eq!(generated, include_str!("test_codegen_rust_expected.rs"));
+}
+
+#[test]
+fn test_generated_rust_loads_original_data() {
+    let data = save_to_mem(0, &sample()).unwrap();
+    let loaded: generated::Inventory = load_from_mem(&data, 0).unwrap();
+
+    // The generated types must serialize the data exactly like the original types
+    let mut original_bytes = Vec::new();
+    save_noschema(&mut original_bytes, 0, &sample()).unwrap();
+    let mut generated_bytes = Vec::new();
+    save_noschema(&mut generated_bytes, 0, &loaded).unwrap();
+    assert_eq!(original_bytes, generated_bytes);
+}
+
+#[test]
+fn test_generated_rust_type_alias() {
+    let options = RustCodegenOptions {
+        root_name: "Trees".to_string(),
+        derives: vec![],
+        ..RustCodegenOptions::default()
+    };
+    let generated = schema_to_rust(&get_schema::<Vec<original::Tree>>(0), &options);
+    assert_eq!(
+        generated,
+        "// Generated by savefile::codegen::schema_to_rust
+use savefile::prelude::*;
+
+pub type Trees = Vec<Tree>;
+
+#[derive(Savefile)]
+pub struct Tree {
+    pub label: String,
+    pub children: Vec<Box<Tree>>,
+}
+"
+    );
+}
@@ -0,0 +1,63 @@
+// Generated by savefile::codegen::schema_to_rust
+use savefile::prelude::*;
+
+#[derive(Savefile, Debug)]
+pub struct Inventory {
+    pub owner: Person,
+    pub items: Vec<Item>,
+    pub tags: std::collections::HashMap<String, u32>,
+    pub location: Option<(f64, f64)>,
+    pub checksum: [u8; 4],
+    pub last_result: Result<u32, String>,
+    pub r#type: Wrapper,
+    pub other: Wrapper2,
+    pub tree: Tree,
+    pub list: List,
+    pub wide: Wide,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Person {
+    pub name: String,
+    pub age: u16,
+}
+
+#[derive(Savefile, Debug)]
+pub enum Item {
+    Tool(String),
+    Food {
+        calories: u32,
+    },
+    #[savefile_versions = "1.."]
+    Unused2,
+    Nothing,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Wrapper {
+    pub value: u32,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Wrapper2 {
+    pub value: String,
+}
+
+#[derive(Savefile, Debug)]
+pub struct Tree {
+    pub label: String,
+    pub children: Vec<Tree>,
+}
+
+#[derive(Savefile, Debug)]
+pub enum List {
+    Cons(u32, Box<List>),
+    Nil,
+}
+
+#[derive(Savefile, Debug)]
+#[repr(u16)]
+pub enum Wide {
+    A,
+    B,
+}
@@ -0,0 +1,97 @@
+use crate::{assert_roundtrip, get_schema, load_from_mem, roundtrip, save_to_mem};
+use roaring::RoaringBitmap;
+use savefile::prelude::*;
+use slab::Slab;
+use tinyvec::TinyVec;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Collections {
+    map: hashbrown::HashMap<String, u32>,
+    set: hashbrown::HashSet<u64>,
+    persistent: im::Vector<i32>,
+    persistent_map: im::HashMap<u8, String>,
+    small: TinyVec<[u16; 4]>,
+    bitmap: RoaringBitmap,
+}
+
+#[test]
+fn test_collections_roundtrip() {
+    let mut small = TinyVec::<[u16; 4]>::new();
+    small.extend([1, 2, 3, 4, 5, 6]);
+    assert!(small.is_heap());
+    assert_roundtrip(Collections {
+        map: [("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect(),
+        set: [1, 2, 3].into_iter().collect(),
+        persistent: (0..100).collect(),
+        persistent_map: [(1, "x".to_string()), (2, "y".to_string())].into_iter().collect(),
+        small,
+        bitmap: [1, 5, 1000, 100_000].into_iter().collect(),
+    });
+    assert_roundtrip(TinyVec::<[u8; 8]>::from([1u8, 2, 3, 0, 0, 0, 0, 0]));
+    assert_roundtrip(RoaringBitmap::new());
+    assert_roundtrip((0..200_000).collect::<RoaringBitmap>());
+}
+
+#[test]
+fn test_hashbrown_is_compatible_with_std() {
+    let map: hashbrown::HashMap<u32, String> = [(1, "one".to_string()), (2, "two".to_string())].into_iter().collect();
+    let data = save_to_mem(0, &map).unwrap();
+    let loaded: std::collections::HashMap<u32, String> = load_from_mem(&data, 0).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[&2], "two");
+
+    assert_eq!(
+        get_schema::<im::HashMap<u32, String>>(0),
+        get_schema::<std::collections::HashMap<u32, String>>(0)
+    );
+    assert_eq!(
+        get_schema::<im::Vector<u32>>(0),
+        get_schema::<std::collections::VecDeque<u32>>(0)
+    );
+}
+
+#[test]
+fn test_slab_keys_survive_roundtrip() {
+    let mut slab = Slab::new();
+    let a = slab.insert("a".to_string());
+    let b = slab.insert("b".to_string());
+    let c = slab.insert("c".to_string());
+    let d = slab.insert("d".to_string());
+    slab.remove(b);
+    slab.remove(d);
+
+    let mut loaded = roundtrip(slab);
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[a], "a");
+    assert_eq!(loaded[c], "c");
+    assert!(!loaded.contains(b));
+    assert!(!loaded.contains(d));
+    let e = loaded.insert("e".to_string());
+    assert!(e == b || e == d);
+
+    let loaded = roundtrip(Slab::<u32>::new());
+    assert!(loaded.is_empty());
+}
+
+#[test]
+fn test_slab_introspect() {
+    let mut slab = Slab::new();
+    slab.insert(10u32);
+    let removed = slab.insert(20u32);
+    slab.insert(30u32);
+    slab.remove(removed);
+    assert_eq!(slab.introspect_len(), 2);
+    let child = slab.introspect_child(1).unwrap();
+    assert_eq!(child.key(), "2");
+    assert_eq!(child.val().introspect_value(), "30");
+}
+
+#[test]
+fn test_corrupt_roaring_bitmap() {
+    let mut data = Vec::new();
+    Serializer::bare_serialize(&mut data, 0, &vec![0xffu8; 16]).unwrap();
+    assert!(matches!(
+        Deserializer::bare_deserialize::<RoaringBitmap>(&mut &data[..], 0),
+        Err(SavefileError::GeneralError { .. })
+    ));
+}
@@ -0,0 +1,123 @@
+use crate::assert_roundtrip;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use savefile::prelude::*;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Appointment {
+    day: NaiveDate,
+    start: NaiveTime,
+    created: NaiveDateTime,
+    local: DateTime<FixedOffset>,
+    length: Duration,
+}
+
+fn sample_appointment() -> Appointment {
+    let day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    let start = NaiveTime::from_hms_nano_opt(13, 45, 7, 123_456_789).unwrap();
+    Appointment {
+        day,
+        start,
+        created: day.and_time(start),
+        local: DateTime::parse_from_rfc3339("1969-07-20T20:17:40.5-05:30").unwrap(),
+        length: Duration::milliseconds(-1500),
+    }
+}
+
+#[test]
+fn test_chrono_roundtrip() {
+    assert_roundtrip(sample_appointment());
+    assert_roundtrip(NaiveDate::MIN);
+    assert_roundtrip(NaiveDate::MAX);
+    assert_roundtrip(NaiveDateTime::MAX);
+    assert_roundtrip(Duration::MAX);
+    assert_roundtrip(Duration::MIN);
+    // Leap second
+    assert_roundtrip(NaiveTime::from_hms_milli_opt(23, 59, 59, 1500).unwrap());
+}
+
+#[test]
+fn test_chrono_introspect_is_iso8601() {
+    let appointment = sample_appointment();
+    assert_eq!(appointment.day.introspect_value(), "2024-02-29");
+    assert_eq!(appointment.start.introspect_value(), "13:45:07.123456789");
+    assert_eq!(appointment.created.introspect_value(), "2024-02-29T13:45:07.123456789");
+    assert_eq!(appointment.local.introspect_value(), "1969-07-20T20:17:40.500-05:30");
+    assert_eq!(appointment.length.introspect_value(), "-PT1.5S");
+}
+
+#[test]
+fn test_date_schema_is_not_integer() {
+    let data = save_to_mem(0, &17i32).unwrap();
+    assert!(matches!(
+        load_from_mem::<NaiveDate>(&data, 0),
+        Err(SavefileError::IncompatibleSchema { .. })
+    ));
+}
+
+#[test]
+fn test_chrono_out_of_range() {
+    let mut buf = Vec::new();
+    Serializer::bare_serialize(&mut buf, 0, &NaiveDate::MIN).unwrap();
+    let days = i32::from_le_bytes(buf[..].try_into().unwrap()) - 1;
+    let mut buf = Vec::new();
+    Serializer::bare_serialize(&mut buf, 0, &days).unwrap();
+    assert!(matches!(
+        Deserializer::bare_deserialize::<NaiveDate>(&mut &buf[..], 0),
+        Err(SavefileError::DateTimeOutOfRange { .. })
+    ));
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Release {
+    date: Date,
+    built: PrimitiveDateTime,
+    published: OffsetDateTime,
+}
+
+fn sample_release() -> Release {
+    let date = Date::from_calendar_date(2023, Month::December, 31).unwrap();
+    let built = date.with_hms_nano(23, 59, 58, 250_000_000).unwrap();
+    Release {
+        date,
+        built,
+        published: built.assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap()),
+    }
+}
+
+#[test]
+fn test_time_roundtrip() {
+    assert_roundtrip(sample_release());
+    assert_roundtrip(Date::MIN);
+    assert_roundtrip(PrimitiveDateTime::MAX);
+    assert_roundtrip(OffsetDateTime::UNIX_EPOCH);
+}
+
+#[test]
+fn test_time_introspect_is_iso8601() {
+    let release = sample_release();
+    assert_eq!(release.date.introspect_value(), "2023-12-31");
+    assert_eq!(release.built.introspect_value(), "2023-12-31T23:59:58.250");
+    assert_eq!(release.published.introspect_value(), "2023-12-31T23:59:58.250+02:00");
+}
+
+#[test]
+fn test_chrono_and_time_are_interchangeable() {
+    let appointment = sample_appointment();
+    let data = save_to_mem(0, &(appointment.day, appointment.created, appointment.local)).unwrap();
+    let (date, datetime, offset): (Date, PrimitiveDateTime, OffsetDateTime) = load_from_mem(&data, 0).unwrap();
+    assert_eq!(date, Date::from_calendar_date(2024, Month::February, 29).unwrap());
+    assert_eq!(datetime.introspect_value(), appointment.created.introspect_value());
+    assert_eq!(offset.unix_timestamp(), appointment.local.timestamp());
+    assert_eq!(
+        offset.offset().whole_seconds(),
+        appointment.local.offset().local_minus_utc()
+    );
+
+    // Chrono supports a wider range of dates than the time crate
+    let data = save_to_mem(0, &NaiveDate::MAX).unwrap();
+    assert!(matches!(
+        load_from_mem::<Date>(&data, 0),
+        Err(SavefileError::DateTimeOutOfRange { .. })
+    ));
+}
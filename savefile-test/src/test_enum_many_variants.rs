@@ -1,4 +1,5 @@
 use crate::assert_roundtrip;
+use savefile::prelude::*;
 
 #[repr(u16)]
 #[derive(Savefile, Debug, PartialEq)]
@@ -286,3 +287,372 @@ fn test_enum_u32_discriminant() {
     assert_roundtrip(U32Discriminant::Variant1);
     assert_roundtrip(U32Discriminant::Variant4294967295);
 }
+
+#[derive(Savefile, Debug, PartialEq)]
+enum ManyVariantsWithFields {
+    Variant0(u32),
+    Variant1,
+    Variant2,
+    Variant3,
+    Variant4,
+    Variant5,
+    Variant6,
+    Variant7,
+    Variant8,
+    Variant9,
+    Variant10,
+    Variant11,
+    Variant12,
+    Variant13,
+    Variant14,
+    Variant15,
+    Variant16,
+    Variant17,
+    Variant18,
+    Variant19,
+    Variant20,
+    Variant21,
+    Variant22,
+    Variant23,
+    Variant24,
+    Variant25,
+    Variant26,
+    Variant27,
+    Variant28,
+    Variant29,
+    Variant30,
+    Variant31,
+    Variant32,
+    Variant33,
+    Variant34,
+    Variant35,
+    Variant36,
+    Variant37,
+    Variant38,
+    Variant39,
+    Variant40,
+    Variant41,
+    Variant42,
+    Variant43,
+    Variant44,
+    Variant45,
+    Variant46,
+    Variant47,
+    Variant48,
+    Variant49,
+    Variant50,
+    Variant51,
+    Variant52,
+    Variant53,
+    Variant54,
+    Variant55,
+    Variant56,
+    Variant57,
+    Variant58,
+    Variant59,
+    Variant60,
+    Variant61,
+    Variant62,
+    Variant63,
+    Variant64,
+    Variant65,
+    Variant66,
+    Variant67,
+    Variant68,
+    Variant69,
+    Variant70,
+    Variant71,
+    Variant72,
+    Variant73,
+    Variant74,
+    Variant75,
+    Variant76,
+    Variant77,
+    Variant78,
+    Variant79,
+    Variant80,
+    Variant81,
+    Variant82,
+    Variant83,
+    Variant84,
+    Variant85,
+    Variant86,
+    Variant87,
+    Variant88,
+    Variant89,
+    Variant90,
+    Variant91,
+    Variant92,
+    Variant93,
+    Variant94,
+    Variant95,
+    Variant96,
+    Variant97,
+    Variant98,
+    Variant99,
+    Variant100,
+    Variant101,
+    Variant102,
+    Variant103,
+    Variant104,
+    Variant105,
+    Variant106,
+    Variant107,
+    Variant108,
+    Variant109,
+    Variant110,
+    Variant111,
+    Variant112,
+    Variant113,
+    Variant114,
+    Variant115,
+    Variant116,
+    Variant117,
+    Variant118,
+    Variant119,
+    Variant120,
+    Variant121,
+    Variant122,
+    Variant123,
+    Variant124,
+    Variant125,
+    Variant126,
+    Variant127,
+    Variant128,
+    Variant129,
+    Variant130,
+    Variant131,
+    Variant132,
+    Variant133,
+    Variant134,
+    Variant135,
+    Variant136,
+    Variant137,
+    Variant138,
+    Variant139,
+    Variant140,
+    Variant141,
+    Variant142,
+    Variant143,
+    Variant144,
+    Variant145,
+    Variant146,
+    Variant147,
+    Variant148,
+    Variant149,
+    Variant150,
+    Variant151,
+    Variant152,
+    Variant153,
+    Variant154,
+    Variant155,
+    Variant156,
+    Variant157,
+    Variant158,
+    Variant159,
+    Variant160,
+    Variant161,
+    Variant162,
+    Variant163,
+    Variant164,
+    Variant165,
+    Variant166,
+    Variant167,
+    Variant168,
+    Variant169,
+    Variant170,
+    Variant171,
+    Variant172,
+    Variant173,
+    Variant174,
+    Variant175,
+    Variant176,
+    Variant177,
+    Variant178,
+    Variant179,
+    Variant180,
+    Variant181,
+    Variant182,
+    Variant183,
+    Variant184,
+    Variant185,
+    Variant186,
+    Variant187,
+    Variant188,
+    Variant189,
+    Variant190,
+    Variant191,
+    Variant192,
+    Variant193,
+    Variant194,
+    Variant195,
+    Variant196,
+    Variant197,
+    Variant198,
+    Variant199,
+    Variant200,
+    Variant201,
+    Variant202,
+    Variant203,
+    Variant204,
+    Variant205,
+    Variant206,
+    Variant207,
+    Variant208,
+    Variant209,
+    Variant210,
+    Variant211,
+    Variant212,
+    Variant213,
+    Variant214,
+    Variant215,
+    Variant216,
+    Variant217,
+    Variant218,
+    Variant219,
+    Variant220,
+    Variant221,
+    Variant222,
+    Variant223,
+    Variant224,
+    Variant225,
+    Variant226,
+    Variant227,
+    Variant228,
+    Variant229,
+    Variant230,
+    Variant231,
+    Variant232,
+    Variant233,
+    Variant234,
+    Variant235,
+    Variant236,
+    Variant237,
+    Variant238,
+    Variant239,
+    Variant240,
+    Variant241,
+    Variant242,
+    Variant243,
+    Variant244,
+    Variant245,
+    Variant246,
+    Variant247,
+    Variant248,
+    Variant249,
+    Variant250,
+    Variant251,
+    Variant252,
+    Variant253,
+    Variant254,
+    Variant255,
+    Variant256,
+    Variant257,
+    Variant258,
+    Variant259,
+    Variant260,
+    Variant261,
+    Variant262,
+    Variant263,
+    Variant264,
+    Variant265,
+    Variant266,
+    Variant267,
+    Variant268,
+    Variant269,
+    Variant270,
+    Variant271,
+    Variant272,
+    Variant273,
+    Variant274,
+    Variant275,
+    Variant276,
+    Variant277,
+    Variant278,
+    Variant279,
+    Variant280,
+    Variant281,
+    Variant282,
+    Variant283,
+    Variant284,
+    Variant285,
+    Variant286,
+    Variant287,
+    Variant288,
+    Variant289,
+    Variant290,
+    Variant291,
+    Variant292,
+    Variant293,
+    Variant294,
+    Variant295,
+    Variant296,
+    Variant297,
+    Variant298,
+    Variant299 { x: u8, y: String },
+}
+
+#[test]
+fn test_enum_many_variants_with_fields() {
+    assert_roundtrip(ManyVariantsWithFields::Variant0(42));
+    assert_roundtrip(ManyVariantsWithFields::Variant256);
+    assert_roundtrip(ManyVariantsWithFields::Variant299 {
+        x: 1,
+        y: "hello".to_string(),
+    });
+    assert_roundtrip(vec![
+        ManyVariantsWithFields::Variant1,
+        ManyVariantsWithFields::Variant298,
+        ManyVariantsWithFields::Variant0(3),
+    ]);
+}
+
+#[test]
+fn test_enum_many_variants_vec() {
+    assert_roundtrip(vec![
+        ManyVariants::Variant0,
+        ManyVariants::Variant256,
+        ManyVariants::Variant259,
+        ManyVariants::Variant3,
+    ]);
+}
+
+#[test]
+fn test_enum_many_variants_schema() {
+    let Schema::Enum(schema) = get_schema::<ManyVariantsWithFields>(0) else {
+        panic!("Expected enum schema");
+    };
+    assert_eq!(schema.discriminant_size, 2);
+    assert_eq!(schema.variants.len(), 300);
+    for (index, variant) in schema.variants.iter().enumerate() {
+        assert_eq!(variant.discriminant, index as u32);
+    }
+}
+
+#[test]
+fn test_enum_many_variants_old_schema_format() {
+    let schema = get_schema::<ManyVariantsWithFields>(0);
+    let mut data = Vec::new();
+    Serializer::bare_serialize(&mut data, 2, &schema).unwrap();
+    let old_format_schema: Schema = Deserializer::bare_deserialize(&mut &data[..], 2).unwrap();
+    assert_eq!(schema, old_format_schema);
+}
+
+#[test]
+fn test_enum_many_variants_packed() {
+    assert!(unsafe { ManyVariants::repr_c_optimization_safe(0).is_yes() });
+    // The in-memory discriminants are not the variant indices
+    assert!(unsafe { U32Discriminant::repr_c_optimization_safe(0).is_false() });
+}
+
+#[test]
+fn test_enum_many_variants_introspect() {
+    assert_eq!(ManyVariants::Variant259.introspect_value(), "ManyVariants::Variant259");
+    let value = ManyVariantsWithFields::Variant299 {
+        x: 7,
+        y: "hello".to_string(),
+    };
+    assert_eq!(value.introspect_len(), 2);
+    assert_eq!(value.introspect_child(0).unwrap().key(), "x");
+    assert_eq!(value.introspect_child(1).unwrap().val().introspect_value(), "hello");
+}
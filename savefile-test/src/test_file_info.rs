@@ -0,0 +1,115 @@
+use savefile::prelude::*;
+use savefile::{save_file_compressed, MemorySchemaRegistry, CURRENT_SAVEFILE_LIB_VERSION};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Savefile, Debug, PartialEq)]
+pub struct GameState {
+    pub level: u32,
+    pub player: String,
+}
+
+#[derive(Savefile, Debug, PartialEq)]
+pub struct SaveHeader {
+    pub saved_at: u64,
+    pub app_version: String,
+}
+
+fn game_state() -> GameState {
+    GameState {
+        level: 3,
+        player: "ferris".to_string(),
+    }
+}
+
+#[cfg(not(miri))]
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("savefile-file-info-{}-{}", name, std::process::id()))
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_metadata_map() {
+    let path = temp_path("map");
+    let mut metadata = BTreeMap::new();
+    metadata.insert("thumbnail".to_string(), vec![1u8, 2, 3]);
+    save_file_with_metadata(&path, 1, &game_state(), &metadata).unwrap();
+
+    let info = read_file_info(&path).unwrap();
+    assert_eq!(info.lib_version, CURRENT_SAVEFILE_LIB_VERSION);
+    assert_eq!(info.data_version, 1);
+    assert!(!info.compressed);
+    assert_eq!(info.schema, Some(get_schema::<GameState>(1)));
+    assert_eq!(info.load_metadata(1).unwrap(), Some(metadata));
+
+    // The metadata is skipped by the regular load functions
+    let loaded: GameState = load_file(&path, 1).unwrap();
+    assert_eq!(loaded, game_state());
+    _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_typed_metadata() {
+    let header = SaveHeader {
+        saved_at: 1_700_000_000,
+        app_version: "1.2.0".to_string(),
+    };
+    let mut data = Vec::new();
+    save_with_metadata(&mut data, 0, &game_state(), &header).unwrap();
+
+    let info = read_info(&mut &data[..]).unwrap();
+    assert_eq!(info.load_metadata::<SaveHeader>(0).unwrap(), Some(header));
+    assert!(info.load_metadata::<u32>(0).is_err());
+    assert_eq!(load_from_mem::<GameState>(&data, 0).unwrap(), game_state());
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_info_without_metadata() {
+    let path = temp_path("compressed");
+    save_file_compressed(&path, 0, &game_state()).unwrap();
+    let info = read_file_info(&path).unwrap();
+    assert!(info.compressed);
+    assert_eq!(info.schema, Some(get_schema::<GameState>(0)));
+    assert!(info.metadata.is_none());
+    assert_eq!(info.load_metadata::<SaveHeader>(0).unwrap(), None);
+    _ = std::fs::remove_file(&path);
+
+    let mut data = Vec::new();
+    save_noschema(&mut data, 2, &game_state()).unwrap();
+    let info = read_info(&mut &data[..]).unwrap();
+    assert_eq!(info.data_version, 2);
+    assert!(info.schema.is_none());
+
+    let mut data = Vec::new();
+    save_with_schema_ref(&mut data, 0, &game_state(), &mut MemorySchemaRegistry::new()).unwrap();
+    assert!(read_info(&mut &data[..]).unwrap().schema.is_none());
+}
+
+#[test]
+fn test_schema_flag() {
+    let mut data = Vec::new();
+    save_noschema(&mut data, 0, &game_state()).unwrap();
+    let err = load_from_mem::<GameState>(&data, 0).unwrap_err();
+    let SavefileError::GeneralError { msg } = err else {
+        panic!("Unexpected error {:?}", err);
+    };
+    assert!(msg.starts_with("The file has no schema"), "{}", msg);
+
+    // The schema is skipped when loading without checking it
+    let data = save_to_mem(0, &game_state()).unwrap();
+    assert_eq!(load_noschema::<GameState>(&mut &data[..], 0).unwrap(), game_state());
+
+    // A schema can't be both embedded and referenced
+    let mut corrupt = data.clone();
+    corrupt[15] |= 2;
+    for err in [
+        load_from_mem::<GameState>(&corrupt, 0).unwrap_err(),
+        read_info(&mut &corrupt[..]).unwrap_err(),
+    ] {
+        let SavefileError::GeneralError { msg } = err else {
+            panic!("Unexpected error {:?}", err);
+        };
+        assert_eq!(msg, "Corrupt savefile header.");
+    }
+}
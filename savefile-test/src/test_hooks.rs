@@ -0,0 +1,141 @@
+use crate::roundtrip;
+use savefile::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+#[derive(Debug, Savefile)]
+#[savefile_post_load = "rebuild_index"]
+struct Phonebook {
+    names: Vec<String>,
+    #[savefile_ignore]
+    #[savefile_introspect_ignore]
+    index: HashMap<String, usize>,
+}
+
+impl Phonebook {
+    fn new(names: &[&str]) -> Phonebook {
+        let mut temp = Phonebook {
+            names: names.iter().map(|x| x.to_string()).collect(),
+            index: HashMap::new(),
+        };
+        temp.rebuild_index().unwrap();
+        temp
+    }
+    fn rebuild_index(&mut self) -> Result<(), SavefileError> {
+        self.index.clear();
+        for (i, name) in self.names.iter().enumerate() {
+            if self.index.insert(name.clone(), i).is_some() {
+                return Err(SavefileError::ValidationFailed {
+                    msg: format!("Duplicate name {}", name),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_post_load_rebuilds_ignored_field() {
+    let loaded = roundtrip(Phonebook::new(&["Ada", "Bob", "Cid"]));
+    assert_eq!(loaded.index.len(), 3);
+    assert_eq!(loaded.index["Bob"], 1);
+}
+
+#[test]
+fn test_post_load_error_fails_load() {
+    let invalid = Phonebook {
+        names: vec!["Ada".to_string(), "Ada".to_string()],
+        index: HashMap::new(),
+    };
+    let data = save_to_mem(0, &invalid).unwrap();
+    match load_from_mem::<Phonebook>(&data, 0) {
+        Err(SavefileError::ValidationFailed { msg }) => assert_eq!(msg, "Duplicate name Ada"),
+        other => panic!("Expected validation error, got {:?}", other),
+    }
+}
+
+fn check_total(total: &Counters) {
+    total.checks.set(total.checks.get() + 1);
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+#[savefile_pre_save = "self::check_total"]
+#[savefile_post_load = "Counters::recalculate"]
+struct Counters {
+    a: u32,
+    b: u32,
+    #[savefile_ignore]
+    #[savefile_introspect_ignore]
+    sum: u32,
+    #[savefile_ignore]
+    #[savefile_introspect_ignore]
+    checks: Cell<u32>,
+}
+
+impl Counters {
+    fn recalculate(&mut self) {
+        self.sum = self.a + self.b;
+    }
+}
+
+#[test]
+fn test_hooks_with_paths() {
+    let counters = Counters {
+        a: 1,
+        b: 2,
+        sum: 3,
+        checks: Cell::new(0),
+    };
+    let loaded = roundtrip(counters);
+    assert_eq!(loaded.sum, 3);
+    assert_eq!(loaded.checks.get(), 0);
+
+    let items = vec![
+        loaded,
+        Counters {
+            a: 5,
+            b: 5,
+            sum: 10,
+            checks: Cell::new(0),
+        },
+    ];
+    save_to_mem(0, &items).unwrap();
+    assert!(items.iter().all(|x| x.checks.get() == 1));
+}
+
+#[test]
+fn test_hooks_disable_packed() {
+    assert!(unsafe { <Counters as Packed>::repr_c_optimization_safe(0) }.is_false());
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+#[savefile_pre_save = "validate"]
+#[savefile_post_load = "validate"]
+enum Temperature {
+    Celsius(f32),
+    Kelvin(f32),
+}
+
+impl Temperature {
+    fn validate(&self) -> Result<(), SavefileError> {
+        let kelvin = match self {
+            Temperature::Celsius(c) => *c + 273.15,
+            Temperature::Kelvin(k) => *k,
+        };
+        if kelvin < 0.0 {
+            return Err(SavefileError::ValidationFailed {
+                msg: "Below absolute zero".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_enum_hooks() {
+    assert_eq!(roundtrip(Temperature::Celsius(20.0)), Temperature::Celsius(20.0));
+    assert!(matches!(
+        save_to_mem(0, &Temperature::Kelvin(-1.0)),
+        Err(SavefileError::ValidationFailed { .. })
+    ));
+}
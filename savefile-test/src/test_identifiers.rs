@@ -0,0 +1,68 @@
+use crate::{assert_roundtrip, get_schema, load_from_mem, save_to_mem};
+use bytes::{Bytes, BytesMut};
+use savefile::prelude::*;
+use semver::{Version, VersionReq};
+use url::Url;
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Package {
+    id: Uuid,
+    version: Version,
+    requires: VersionReq,
+    homepage: Url,
+    checksum: Bytes,
+    scratch: BytesMut,
+}
+
+fn sample_package() -> Package {
+    Package {
+        id: Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+        version: Version::parse("1.2.3-beta.1+build.5").unwrap(),
+        requires: VersionReq::parse(">=1.0, <2.0").unwrap(),
+        homepage: Url::parse("https://example.com/a/b?q=1#frag").unwrap(),
+        checksum: Bytes::from_static(&[1, 2, 3, 255]),
+        scratch: BytesMut::from(&b"abc"[..]),
+    }
+}
+
+#[test]
+fn test_identifiers_roundtrip() {
+    assert_roundtrip(sample_package());
+    assert_roundtrip(vec![Uuid::nil(), Uuid::max(), Uuid::from_u128(0x1234)]);
+    assert_roundtrip(Bytes::new());
+}
+
+#[test]
+fn test_uuid_is_packed_with_own_schema() {
+    assert!(unsafe { <Uuid as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert_eq!(get_schema::<Uuid>(0), Schema::Primitive(SchemaPrimitive::schema_uuid));
+
+    let id = Uuid::from_u128(0x0102030405060708090a0b0c0d0e0f10);
+    let data = save_to_mem(0, &id).unwrap();
+    assert!(load_from_mem::<[u8; 16]>(&data, 0).is_err());
+}
+
+#[test]
+fn test_identifiers_introspect() {
+    let package = sample_package();
+    assert_eq!(package.id.introspect_value(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(package.version.introspect_value(), "1.2.3-beta.1+build.5");
+    assert_eq!(package.requires.introspect_value(), ">=1.0, <2.0");
+    assert_eq!(package.homepage.introspect_value(), "https://example.com/a/b?q=1#frag");
+    assert_eq!(package.checksum.introspect_value(), "b\"\\x01\\x02\\x03\\xff\"");
+}
+
+#[test]
+fn test_invalid_strings_fail_to_load() {
+    let data = save_to_mem(0, &"not a version".to_string()).unwrap();
+    assert!(matches!(
+        load_from_mem::<Version>(&data, 0),
+        Err(SavefileError::GeneralError { .. })
+    ));
+    assert!(load_from_mem::<VersionReq>(&data, 0).is_err());
+    assert!(load_from_mem::<Url>(&data, 0).is_err());
+
+    let data = save_to_mem(0, &"1.2.3".to_string()).unwrap();
+    assert_eq!(load_from_mem::<Version>(&data, 0).unwrap(), Version::new(1, 2, 3));
+}
@@ -143,7 +143,7 @@ pub fn do_test_silly_struct() {
 pub fn do_test1() {
     let test = SimpleStruct { item1: 342 };
 
-    let x = (&test).introspect_value();
+    let x = test.introspect_value();
     assert_eq!(x, "SimpleStruct");
 
     assert_eq!(test.introspect_len(), 1);
@@ -154,7 +154,7 @@ pub fn do_test1() {
 pub fn do_test_refcell() {
     let test = RefCell::new(32);
 
-    let x = (&test).introspect_value();
+    let x = test.introspect_value();
     assert_eq!(x, "RefCell(Ref(32))");
 
     assert_eq!(test.introspect_len(), 1);
@@ -165,7 +165,7 @@ pub fn do_test_refcell() {
 pub fn do_test_rc() {
     let test = Rc::new(32);
 
-    let x = (&test).introspect_value();
+    let x = test.introspect_value();
     assert_eq!(x, "Rc(32)");
 
     assert_eq!(test.introspect_len(), 0);
@@ -174,7 +174,7 @@ pub fn do_test_rc() {
 pub fn do_test_arc() {
     let test = Arc::new(32);
 
-    let x = (&test).introspect_value();
+    let x = test.introspect_value();
     assert_eq!(x, "Arc(32)");
 
     assert_eq!(test.introspect_len(), 0);
@@ -184,7 +184,7 @@ pub fn do_test_arc() {
 pub fn do_test_rwlock() {
     let test = RwLock::new(SimpleStruct { item1: 342 });
 
-    let _x = (&test).introspect_value();
+    let _x = test.introspect_value();
 
     assert_eq!(test.introspect_len(), 1);
     assert_eq!(test.introspect_child(0).unwrap().key(), "0");
@@ -205,7 +205,7 @@ pub fn do_test_rwlock() {
 pub fn do_test_mutex() {
     let test = Mutex::new(SimpleStruct { item1: 343 });
 
-    let _x = (&test).introspect_value();
+    let _x = test.introspect_value();
 
     assert_eq!(test.introspect_len(), 1);
     assert_eq!(test.introspect_child(0).unwrap().key(), "0");
@@ -280,7 +280,7 @@ pub fn test_introspector_simpler_case0() {
         .expect("Leafs should also be selectable");
 
     assert_eq!(result.frames.len(), 2);
-    assert_eq!(result.frames[1].keyvals[0].selected, true);
+    assert!(result.frames[1].keyvals[0].selected);
 }
 #[test]
 pub fn test_introspector_simpler_case1() {
@@ -0,0 +1,179 @@
+use savefile::prelude::*;
+use savefile::{introspect_diff_with_limits, IntrospectDiffLimits, MAX_CHILDREN};
+use std::collections::HashMap;
+
+#[derive(Savefile, Clone)]
+pub struct Unit {
+    name: String,
+    hp: u32,
+    pos: (i32, i32),
+}
+
+#[derive(Savefile, Clone)]
+pub struct State {
+    tick: u64,
+    units: Vec<Unit>,
+    scores: HashMap<String, u32>,
+}
+
+#[derive(Savefile, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerId {
+    #[savefile_introspect_key]
+    name: String,
+    slot: u8,
+}
+
+fn unit(name: &str, hp: u32) -> Unit {
+    Unit {
+        name: name.to_string(),
+        hp,
+        pos: (0, 0),
+    }
+}
+
+fn sample_state() -> State {
+    State {
+        tick: 100,
+        units: vec![unit("archer", 10), unit("knight", 20)],
+        scores: (0..20).map(|i| (format!("player{}", i), i)).collect(),
+    }
+}
+
+fn paths(diff: &[IntrospectDiffEntry]) -> Vec<String> {
+    diff.iter().map(|x| x.path_string()).collect()
+}
+
+#[test]
+fn test_diff_identical() {
+    let a = sample_state();
+    // Rebuilding the map gives a different iteration order, which must not matter
+    let b = sample_state();
+    assert!(introspect_diff(&a, &b).is_empty());
+}
+
+#[test]
+fn test_diff_changed_fields() {
+    let a = sample_state();
+    let mut b = a.clone();
+    b.tick = 101;
+    b.units[1].pos.1 = -4;
+    let diff = introspect_diff(&a, &b);
+    assert_eq!(paths(&diff), vec!["tick", "units.1.pos.1"]);
+    assert_eq!(diff[0].kind, IntrospectDiffKind::Changed);
+    assert_eq!(diff[0].old_value.as_deref(), Some("100"));
+    assert_eq!(diff[0].new_value.as_deref(), Some("101"));
+    assert_eq!(diff[1].old_value.as_deref(), Some("0"));
+    assert_eq!(diff[1].new_value.as_deref(), Some("-4"));
+}
+
+#[test]
+fn test_diff_added_and_removed() {
+    let a = sample_state();
+    let mut b = a.clone();
+    b.units.push(unit("mage", 5));
+    b.scores.remove("player3");
+    b.scores.insert("newcomer".to_string(), 1);
+    *b.scores.get_mut("player7").unwrap() = 70;
+    let diff = introspect_diff(&a, &b);
+    let mut entries: Vec<_> = diff
+        .iter()
+        .map(|x| (x.path_string(), x.kind, x.old_value.clone(), x.new_value.clone()))
+        .collect();
+    entries.sort_by(|x, y| x.0.cmp(&y.0));
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "scores.newcomer".to_string(),
+                IntrospectDiffKind::Added,
+                None,
+                Some("1".to_string())
+            ),
+            (
+                "scores.player3".to_string(),
+                IntrospectDiffKind::Removed,
+                Some("3".to_string()),
+                None
+            ),
+            (
+                "scores.player7".to_string(),
+                IntrospectDiffKind::Changed,
+                Some("7".to_string()),
+                Some("70".to_string())
+            ),
+            (
+                "units.2".to_string(),
+                IntrospectDiffKind::Added,
+                None,
+                Some("Unit".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_map_matched_by_introspect_key() {
+    let id = |name: &str, slot| PlayerId {
+        name: name.to_string(),
+        slot,
+    };
+    let a: HashMap<PlayerId, Unit> = (0..10)
+        .map(|i| (id(&format!("p{}", i), i), unit("archer", i as u32)))
+        .collect();
+    let mut b: HashMap<PlayerId, Unit> = (0..10)
+        .map(|i| (id(&format!("p{}", i), i), unit("archer", i as u32)))
+        .collect();
+    b.get_mut(&id("p4", 4)).unwrap().hp = 1000;
+    let diff = introspect_diff(&a, &b);
+    assert_eq!(paths(&diff), vec!["p4.hp"]);
+}
+
+#[test]
+fn test_diff_indexmap_matched_by_key() {
+    let a: indexmap::IndexMap<u32, u32> = [(1, 10), (2, 20), (3, 30)].into_iter().collect();
+    let b: indexmap::IndexMap<u32, u32> = [(3, 30), (2, 21), (1, 10)].into_iter().collect();
+    let diff = introspect_diff(&a, &b);
+    assert_eq!(paths(&diff), vec!["2"]);
+    assert_eq!(diff[0].new_value.as_deref(), Some("21"));
+}
+
+#[test]
+fn test_diff_limits() {
+    let a = sample_state();
+    let mut b = a.clone();
+    b.tick = 1;
+    for u in b.units.iter_mut() {
+        u.hp += 1;
+    }
+
+    let shallow = introspect_diff_with_limits(
+        &a,
+        &b,
+        IntrospectDiffLimits {
+            max_depth: 2,
+            max_entries: 100,
+        },
+    );
+    // The hp fields are at depth 3
+    assert_eq!(paths(&shallow), vec!["tick"]);
+
+    let few = introspect_diff_with_limits(
+        &a,
+        &b,
+        IntrospectDiffLimits {
+            max_depth: 10,
+            max_entries: 2,
+        },
+    );
+    assert_eq!(paths(&few), vec!["tick", "units.0.hp"]);
+}
+
+#[test]
+fn test_diff_compares_at_most_max_children() {
+    let a: Vec<u32> = (0..2 * MAX_CHILDREN as u32).collect();
+    let mut b = a.clone();
+    b[3] = 0;
+    b[MAX_CHILDREN + 3] = 0;
+    let diff = introspect_diff(&a, &b);
+    assert_eq!(paths(&diff), vec!["3"]);
+}
@@ -0,0 +1,213 @@
+use savefile::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Savefile)]
+pub struct Credentials {
+    user: String,
+    password: String,
+}
+
+#[derive(Savefile)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Empty,
+}
+
+#[derive(Savefile, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlayerId {
+    #[savefile_introspect_key]
+    name: String,
+    slot: u8,
+}
+
+#[derive(Savefile)]
+pub struct Config {
+    name: String,
+    retries: u32,
+    ratio: f64,
+    enabled: bool,
+    nickname: Option<String>,
+    ports: Vec<u16>,
+    shapes: Vec<Shape>,
+    login: Credentials,
+    scores: BTreeMap<PlayerId, i32>,
+    #[savefile_introspect_ignore]
+    cache: Vec<u8>,
+}
+
+#[derive(Savefile)]
+pub struct Nothing {}
+
+#[derive(Savefile)]
+pub struct Empties {
+    list: Vec<u32>,
+    set: HashSet<u32>,
+    map: BTreeMap<String, u32>,
+    shared: Option<Rc<Vec<u8>>>,
+    nothing: Nothing,
+    missing: Option<Vec<u8>>,
+}
+
+fn sample_config() -> Config {
+    let mut scores = BTreeMap::new();
+    scores.insert(
+        PlayerId {
+            name: "alice".to_string(),
+            slot: 1,
+        },
+        -5,
+    );
+    Config {
+        name: "server \"one\"".to_string(),
+        retries: 3,
+        ratio: 0.5,
+        enabled: true,
+        nickname: None,
+        ports: vec![80, 443],
+        shapes: vec![Shape::Circle { radius: 1.5 }, Shape::Empty],
+        login: Credentials {
+            user: "admin".to_string(),
+            password: "hunter2".to_string(),
+        },
+        scores,
+        cache: vec![1, 2, 3],
+    }
+}
+
+fn redacting_options() -> IntrospectDumpOptions {
+    IntrospectDumpOptions {
+        redacted_keys: vec!["password".to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_introspect_to_json() {
+    let config = sample_config();
+    assert_eq!(config.cache.len(), 3);
+    let json = introspect_to_json(&config, &redacting_options());
+    assert_eq!(
+        json,
+        r#"{
+  "name": "server \"one\"",
+  "retries": 3,
+  "ratio": 0.5,
+  "enabled": true,
+  "nickname": null,
+  "ports": [
+    80,
+    443
+  ],
+  "shapes": [
+    {
+      "$value": "Shape::Circle",
+      "radius": 1.5
+    },
+    "Shape::Empty"
+  ],
+  "login": {
+    "user": "admin",
+    "password": "<redacted>"
+  },
+  "scores": {
+    "alice": -5
+  }
+}
+"#
+    );
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed["login"]["user"], "admin");
+}
+
+#[test]
+fn test_introspect_to_json_empty_collections() {
+    let empties = Empties {
+        list: vec![],
+        set: HashSet::new(),
+        map: BTreeMap::new(),
+        shared: Some(Rc::new(vec![])),
+        nothing: Nothing {},
+        missing: None,
+    };
+    let json = introspect_to_json(&empties, &IntrospectDumpOptions::default());
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!({
+            "list": [],
+            "set": [],
+            "map": {},
+            "shared": [],
+            "nothing": {},
+            "missing": null
+        })
+    );
+    assert_eq!(
+        introspect_to_json(&Vec::<u32>::new(), &IntrospectDumpOptions::default()),
+        "[]\n"
+    );
+}
+
+#[test]
+fn test_introspect_to_text() {
+    let text = introspect_to_text(&sample_config(), &redacting_options());
+    assert_eq!(
+        text,
+        r#"name: server "one"
+retries: 3
+ratio: 0.5
+enabled: true
+nickname: None
+ports:
+  0: 80
+  1: 443
+shapes:
+  0: Shape::Circle
+    radius: 1.5
+  1: Shape::Empty
+login:
+  user: admin
+  password: <redacted>
+scores:
+  alice: -5
+"#
+    );
+}
+
+#[test]
+fn test_introspect_dump_limits() {
+    let data: Vec<Vec<u32>> = vec![(0..10).collect(), vec![7]];
+    let options = IntrospectDumpOptions {
+        max_depth: 1,
+        max_children: 1,
+        redacted_keys: vec![],
+    };
+    assert_eq!(introspect_to_json(&data, &options), "[\n  \"vec[]\",\n  \"...\"\n]\n");
+    assert_eq!(introspect_to_text(&data, &options), "0: vec[] ...\n...\n");
+
+    let options = IntrospectDumpOptions {
+        max_depth: 2,
+        max_children: 3,
+        redacted_keys: vec![],
+    };
+    let parsed: serde_json::Value = serde_json::from_str(&introspect_to_json(&data, &options)).unwrap();
+    assert_eq!(parsed, serde_json::json!([[0, 1, 2, "..."], [7]]));
+}
+
+#[test]
+fn test_introspect_dump_escaping() {
+    let tricky = vec!["line\nbreak\ttab\\ \u{1} \"quoted\"".to_string()];
+    let json = introspect_to_json(&tricky, &IntrospectDumpOptions::default());
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed[0], tricky[0].as_str());
+    assert_eq!(
+        introspect_to_text(&tricky, &IntrospectDumpOptions::default()),
+        "0: line\\nbreak\ttab\\ \u{1} \"quoted\"\n"
+    );
+    assert_eq!(
+        introspect_to_json(&f64::NAN, &IntrospectDumpOptions::default()),
+        "\"NaN\"\n"
+    );
+    assert_eq!(introspect_to_text(&17u8, &IntrospectDumpOptions::default()), "17\n");
+}
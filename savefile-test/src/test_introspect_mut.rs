@@ -0,0 +1,214 @@
+use savefile::prelude::*;
+use savefile::{IntrospectedElementKey, Introspector, IntrospectorNavCommand};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Default, Savefile)]
+pub enum Difficulty {
+    #[default]
+    Easy,
+    Hard,
+    Custom(u8),
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+pub struct Player {
+    name: String,
+    health: f32,
+    lives: u8,
+    alive: bool,
+    target: Option<u32>,
+    difficulty: Difficulty,
+    shared: Arc<u32>,
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+pub struct World {
+    players: Vec<Player>,
+    tick: u64,
+    #[savefile_introspect_ignore]
+    hidden: u32,
+    marker: char,
+}
+
+fn sample_world() -> World {
+    World {
+        players: vec![Player {
+            name: "Ada".to_string(),
+            health: 100.0,
+            lives: 3,
+            alive: true,
+            target: None,
+            difficulty: Difficulty::Easy,
+            shared: Arc::new(7),
+        }],
+        tick: 1,
+        hidden: 0,
+        marker: 'x',
+    }
+}
+
+fn set_field(object: &mut dyn IntrospectMut, key: &str, value: &str) -> Result<(), IntrospectionError> {
+    let index = (0..object.introspect_len())
+        .find(|i| object.introspect_child(*i).unwrap().key() == key)
+        .expect("field exists");
+    let mut child = object.introspect_child_mut(index).unwrap();
+    assert_eq!(child.key(), key);
+    child.val_mut().set_from_str(value)
+}
+
+#[test]
+fn test_set_primitives_strings_and_options() {
+    let mut world = sample_world();
+    let player = &mut world.players[0];
+    set_field(player, "name", "Bob").unwrap();
+    set_field(player, "health", "12.5").unwrap();
+    set_field(player, "lives", "9").unwrap();
+    set_field(player, "alive", "false").unwrap();
+    set_field(player, "target", "42").unwrap();
+    assert_eq!(player.name, "Bob");
+    assert_eq!(player.health, 12.5);
+    assert_eq!(player.lives, 9);
+    assert!(!player.alive);
+    assert_eq!(player.target, Some(42));
+
+    set_field(player, "target", "Some(43)").unwrap();
+    assert_eq!(player.target, Some(43));
+    set_field(player, "target", "None").unwrap();
+    assert_eq!(player.target, None);
+
+    set_field(&mut world, "marker", " ").unwrap();
+    assert_eq!(world.marker, ' ');
+}
+
+#[test]
+fn test_set_invalid_and_read_only() {
+    let mut player = sample_world().players.remove(0);
+    assert_eq!(
+        set_field(&mut player, "lives", "300"),
+        Err(IntrospectionError::InvalidValue)
+    );
+    assert_eq!(
+        set_field(&mut player, "alive", "maybe"),
+        Err(IntrospectionError::InvalidValue)
+    );
+    assert_eq!(player.lives, 3);
+
+    // Arc<u32> only implements Introspect, so it can be viewed but not modified
+    assert_eq!(set_field(&mut player, "shared", "8"), Err(IntrospectionError::ReadOnly));
+    assert_eq!(player.introspect_child_mut(6).unwrap().key(), "shared");
+
+    assert_eq!(player.set_from_str("x"), Err(IntrospectionError::ReadOnly));
+}
+
+#[test]
+fn test_set_enum_unit_variant() {
+    let mut difficulty = Difficulty::Easy;
+    difficulty.set_from_str("Hard").unwrap();
+    assert_eq!(difficulty, Difficulty::Hard);
+    difficulty.set_from_str("Difficulty::Easy").unwrap();
+    assert_eq!(difficulty, Difficulty::Easy);
+    assert_eq!(difficulty.set_from_str("Custom"), Err(IntrospectionError::InvalidValue));
+
+    let mut custom = Difficulty::Custom(1);
+    let mut child = custom.introspect_child_mut(0).unwrap();
+    child.val_mut().set_from_str("5").unwrap();
+    drop(child);
+    assert_eq!(custom, Difficulty::Custom(5));
+}
+
+#[test]
+fn test_child_mut_matches_child() {
+    let mut world = sample_world();
+    let keys: Vec<String> = (0..world.introspect_len())
+        .map(|i| world.introspect_child(i).unwrap().key().to_string())
+        .collect();
+    let keys_mut: Vec<String> = (0..world.introspect_len())
+        .map(|i| world.introspect_child_mut(i).unwrap().key().to_string())
+        .collect();
+    assert_eq!(keys, keys_mut);
+    assert_eq!(keys, ["players", "tick", "marker"]);
+    assert!(world.introspect_child_mut(3).is_none());
+}
+
+#[test]
+fn test_introspector_set_selected_value() {
+    let mut world = sample_world();
+    let mut introspector = Introspector::new();
+    assert_eq!(
+        introspector.set_selected_value(&mut world, "1"),
+        Err(IntrospectionError::NothingSelected)
+    );
+
+    introspector
+        .do_introspect(
+            &world,
+            IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+                depth: 0,
+                key: "players".to_string(),
+                key_disambiguator: 0,
+            }),
+        )
+        .unwrap();
+    introspector
+        .do_introspect(
+            &world,
+            IntrospectorNavCommand::SelectNth {
+                select_depth: 1,
+                select_index: 0,
+            },
+        )
+        .unwrap();
+    introspector
+        .do_introspect(
+            &world,
+            IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+                depth: 2,
+                key: "difficulty".to_string(),
+                key_disambiguator: 0,
+            }),
+        )
+        .unwrap();
+    introspector.set_selected_value(&mut world, "Hard").unwrap();
+    assert_eq!(world.players[0].difficulty, Difficulty::Hard);
+
+    let result = introspector
+        .do_introspect(&world, IntrospectorNavCommand::Nothing)
+        .unwrap();
+    assert_eq!(result.frames[2].keyvals[5].value, "Difficulty::Hard");
+
+    introspector.do_introspect(&world, IntrospectorNavCommand::Up).unwrap();
+    introspector.do_introspect(&world, IntrospectorNavCommand::Up).unwrap();
+    assert_eq!(
+        introspector.set_selected_value(&mut world, "Hard"),
+        Err(IntrospectionError::ReadOnly)
+    );
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+pub enum Shape {
+    Rect { width: u32, height: u32 },
+    Line { height: u32 },
+}
+
+#[test]
+fn test_introspector_set_selected_value_after_change() {
+    let mut shape = Shape::Rect { width: 1, height: 2 };
+    let mut introspector = Introspector::new();
+    introspector
+        .do_introspect(
+            &shape,
+            IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+                depth: 0,
+                key: "height".to_string(),
+                key_disambiguator: 0,
+            }),
+        )
+        .unwrap();
+    introspector.set_selected_value(&mut shape, "3").unwrap();
+    assert_eq!(shape, Shape::Rect { width: 1, height: 3 });
+
+    // The selected field has moved to another index
+    let mut shape = Shape::Line { height: 2 };
+    introspector.set_selected_value(&mut shape, "4").unwrap();
+    assert_eq!(shape, Shape::Line { height: 4 });
+}
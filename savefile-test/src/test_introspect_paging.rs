@@ -0,0 +1,253 @@
+use savefile::prelude::*;
+use savefile::{IntrospectedElementKey, Introspector, IntrospectorNavCommand};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Savefile)]
+pub struct Item {
+    id: u32,
+    tags: Vec<String>,
+}
+
+#[derive(Savefile)]
+pub struct Inventory {
+    owner: String,
+    items: Vec<Item>,
+}
+
+fn child_keys(object: &dyn Introspect) -> Vec<String> {
+    (0..)
+        .map_while(|i| object.introspect_child(i))
+        .map(|x| x.key().to_string())
+        .collect()
+}
+
+fn paged_keys(object: &dyn Introspect, page_size: usize) -> Vec<String> {
+    let mut result = Vec::new();
+    loop {
+        let page = object.introspect_children_range(result.len(), page_size);
+        if page.is_empty() {
+            return result;
+        }
+        result.extend(page.iter().map(|x| x.key().to_string()));
+    }
+}
+
+#[test]
+fn test_children_range_matches_introspect_child() {
+    let vec: Vec<u32> = (0..25).collect();
+    assert_eq!(paged_keys(&vec, 7), child_keys(&vec));
+
+    // The key type has no ToString, so the map has separate key and value children
+    // also when specialization is available (on nightly)
+    let map: HashMap<(u32,), u32> = (0..25).map(|x| ((x,), x * x)).collect();
+    assert_eq!(map.introspect_len(), 50);
+    // Odd page sizes split key/value pairs between pages
+    assert_eq!(paged_keys(&map, 7), child_keys(&map));
+    let values: Vec<String> = map
+        .introspect_children_range(11, 3)
+        .iter()
+        .map(|x| x.val().introspect_value())
+        .collect();
+    let expected: Vec<String> = (11..14)
+        .map(|i| map.introspect_child(i).unwrap().val().introspect_value())
+        .collect();
+    assert_eq!(values, expected);
+
+    let btree: BTreeMap<u32, u32> = (0..25).map(|x| (x, x)).collect();
+    assert_eq!(paged_keys(&btree, 4), child_keys(&btree));
+
+    let set: HashSet<u32> = (0..25).collect();
+    assert_eq!(paged_keys(&set, 10), child_keys(&set));
+
+    assert!(vec.introspect_children_range(30, 10).is_empty());
+    assert_eq!(vec.introspect_children_range(20, usize::MAX).len(), 5);
+}
+
+#[test]
+fn test_page_large_vec() {
+    let data: Vec<u32> = (0..100_000).collect();
+    let mut introspector = Introspector::new_with(100);
+
+    let result = introspector
+        .do_introspect(&data, IntrospectorNavCommand::Nothing)
+        .unwrap();
+    assert_eq!(result.frames[0].keyvals.len(), 100);
+    assert_eq!(result.frames[0].offset, 0);
+    assert!(result.frames[0].limit_reached);
+
+    let result = introspector
+        .do_introspect(
+            &data,
+            IntrospectorNavCommand::Page {
+                depth: 0,
+                offset: 99_950,
+            },
+        )
+        .unwrap();
+    let frame = &result.frames[0];
+    assert_eq!(frame.offset, 99_950);
+    assert_eq!(frame.keyvals.len(), 50);
+    assert!(!frame.limit_reached);
+    assert_eq!(frame.keyvals[0].key.key, "99950");
+    assert_eq!(frame.keyvals[0].value, "99950");
+
+    // The page is remembered by later commands
+    let result = introspector
+        .do_introspect(&data, IntrospectorNavCommand::Nothing)
+        .unwrap();
+    assert_eq!(result.frames[0].keyvals[0].key.key, "99950");
+}
+
+#[test]
+fn test_page_nested() {
+    let inventory = Inventory {
+        owner: "Bob".to_string(),
+        items: (0..1000)
+            .map(|id| Item {
+                id,
+                tags: vec![format!("tag{}", id)],
+            })
+            .collect(),
+    };
+    let mut introspector = Introspector::new_with(10);
+    introspector
+        .do_introspect(
+            &inventory,
+            IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+                depth: 0,
+                key: "items".to_string(),
+                key_disambiguator: 0,
+            }),
+        )
+        .unwrap();
+    let result = introspector
+        .do_introspect(&inventory, IntrospectorNavCommand::Page { depth: 1, offset: 900 })
+        .unwrap();
+    assert_eq!(result.frames.len(), 2);
+    assert_eq!(result.frames[0].selected, Some(1));
+    assert_eq!(result.frames[1].keyvals[0].key.key, "900");
+
+    // SelectNth uses the index of the child, not the index within the page
+    let result = introspector
+        .do_introspect(
+            &inventory,
+            IntrospectorNavCommand::SelectNth {
+                select_depth: 1,
+                select_index: 905,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.frames.len(), 3);
+    assert_eq!(result.frames[1].selected, Some(5));
+    assert_eq!(result.frames[2].keyvals[0].value, "905");
+    let err = introspector
+        .do_introspect(
+            &inventory,
+            IntrospectorNavCommand::SelectNth {
+                select_depth: 1,
+                select_index: 3,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, IntrospectionError::IndexOutOfRange);
+
+    // Going up keeps the page
+    let result = introspector
+        .do_introspect(&inventory, IntrospectorNavCommand::Up)
+        .unwrap();
+    assert_eq!(result.frames.len(), 2);
+    assert_eq!(result.frames[1].offset, 900);
+
+    // Paging a level deselects it
+    let result = introspector
+        .do_introspect(&inventory, IntrospectorNavCommand::Page { depth: 0, offset: 1 })
+        .unwrap();
+    assert_eq!(result.frames.len(), 1);
+    assert_eq!(result.frames[0].keyvals[0].key.key, "items");
+    let err = introspector
+        .do_introspect(&inventory, IntrospectorNavCommand::Page { depth: 2, offset: 0 })
+        .unwrap_err();
+    assert_eq!(err, IntrospectionError::BadDepth);
+}
+
+struct Duplicates;
+
+impl Introspect for Duplicates {
+    fn introspect_value(&self) -> String {
+        "Duplicates".to_string()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index < 10 {
+            Some(introspect_item("dup".to_string(), &0u8))
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_page_key_disambiguators() {
+    let mut introspector = Introspector::new_with(4);
+    let result = introspector
+        .do_introspect(&Duplicates, IntrospectorNavCommand::Page { depth: 0, offset: 6 })
+        .unwrap();
+    let disambiguators: Vec<usize> = result.frames[0]
+        .keyvals
+        .iter()
+        .map(|x| x.key.key_disambiguator)
+        .collect();
+    assert_eq!(disambiguators, vec![6, 7, 8, 9]);
+
+    // Later navigation on the same page reuses the counted disambiguators
+    let result = introspector
+        .do_introspect(
+            &Duplicates,
+            IntrospectorNavCommand::SelectNth {
+                select_depth: 0,
+                select_index: 8,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.frames[0].selected, Some(2));
+    let selected = &result.frames[0].keyvals[2];
+    assert!(selected.selected);
+    assert_eq!(selected.key.key_disambiguator, 8);
+}
+
+struct Keys(Vec<&'static str>);
+
+impl Introspect for Keys {
+    fn introspect_value(&self) -> String {
+        "Keys".to_string()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        let key = self.0.get(index)?;
+        Some(introspect_item(key.to_string(), &0u8))
+    }
+}
+
+#[test]
+fn test_page_key_disambiguators_after_change() {
+    let mut introspector = Introspector::new_with(4);
+    let disambiguators = |introspector: &mut Introspector, keys: &Keys| -> Vec<usize> {
+        let result = introspector
+            .do_introspect(keys, IntrospectorNavCommand::Nothing)
+            .unwrap();
+        result.frames[0]
+            .keyvals
+            .iter()
+            .map(|x| x.key.key_disambiguator)
+            .collect()
+    };
+    let keys = Keys(vec!["a", "b", "a", "b"]);
+    introspector
+        .do_introspect(&keys, IntrospectorNavCommand::Page { depth: 0, offset: 2 })
+        .unwrap();
+    assert_eq!(disambiguators(&mut introspector, &keys), vec![1, 1]);
+
+    // The object changed between requests, which is common for live objects
+    let keys = Keys(vec!["a", "a", "a", "b", "a"]);
+    assert_eq!(disambiguators(&mut introspector, &keys), vec![2, 0, 3]);
+}
@@ -0,0 +1,149 @@
+use savefile::prelude::*;
+use savefile::{IntrospectedElementKey, Introspector, IntrospectorNavCommand};
+
+#[derive(Savefile)]
+pub struct Item {
+    name: String,
+    weight: u32,
+}
+
+#[derive(Savefile)]
+pub struct Player {
+    name: String,
+    inventory: Vec<Item>,
+}
+
+#[derive(Savefile)]
+pub struct Game {
+    players: Vec<Player>,
+    settings: hashbrown::HashMap<String, u32>,
+}
+
+fn item(name: &str, weight: u32) -> Item {
+    Item {
+        name: name.to_string(),
+        weight,
+    }
+}
+
+fn sample_game() -> Game {
+    Game {
+        players: vec![
+            Player {
+                name: "Ada".to_string(),
+                inventory: vec![item("sword", 10), item("shield", 20)],
+            },
+            Player {
+                name: "Bob".to_string(),
+                inventory: vec![item("bow", 5)],
+            },
+        ],
+        settings: [("volume.music".to_string(), 3)].into_iter().collect(),
+    }
+}
+
+fn values(game: &Game, query: &str) -> Vec<String> {
+    Introspector::query(game, query)
+        .unwrap()
+        .into_iter()
+        .map(|x| x.value)
+        .collect()
+}
+
+#[test]
+fn test_query_direct_path() {
+    let game = sample_game();
+    let result = Introspector::query(&game, "players[0].inventory[1].name").unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].value, "shield");
+    assert!(!result[0].has_children);
+    assert_eq!(result[0].path_string(), "players.0.inventory.1.name");
+    assert_eq!(
+        result[0].path[2],
+        IntrospectedElementKey {
+            depth: 2,
+            key: "inventory".to_string(),
+            key_disambiguator: 0,
+        }
+    );
+    assert_eq!(values(&game, "players.1.name"), ["Bob"]);
+    assert!(values(&game, "players[2].name").is_empty());
+    assert!(values(&game, "nonexistent").is_empty());
+}
+
+#[test]
+fn test_query_wildcards() {
+    let game = sample_game();
+    assert_eq!(values(&game, "players[*].name"), ["Ada", "Bob"]);
+    assert_eq!(values(&game, "players.*.inventory.*.name"), ["sword", "shield", "bow"]);
+    assert_eq!(values(&game, "players[*].inventory[*].weight"), ["10", "20", "5"]);
+}
+
+#[test]
+fn test_query_predicates() {
+    let game = sample_game();
+    assert_eq!(values(&game, "players[name=Bob].inventory[0].name"), ["bow"]);
+    assert_eq!(values(&game, "players[*].inventory[weight=20].name"), ["shield"]);
+    assert!(values(&game, "players[name=Cid]").is_empty());
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_query_large_collections() {
+    let numbers: Vec<u32> = (0..20000).collect();
+    let matches = Introspector::query(&numbers, "[*]").unwrap();
+    assert_eq!(matches.len(), savefile::MAX_CHILDREN);
+    assert_eq!(Introspector::query(&numbers, "[9999]").unwrap()[0].value, "9999");
+    assert!(Introspector::query(&numbers, "[10000]").unwrap().is_empty());
+
+    let items: std::collections::BTreeMap<u32, Item> = (0..3000).map(|i| (i, item("stone", i))).collect();
+    let matches = Introspector::query(&items, "[weight=2999].weight").unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].value, "2999");
+}
+
+#[test]
+fn test_query_escapes() {
+    let game = sample_game();
+    assert_eq!(values(&game, "settings.volume\\.music"), ["3"]);
+}
+
+#[test]
+fn test_query_invalid() {
+    let game = sample_game();
+    for query in [
+        "",
+        "players.",
+        "players[",
+        "players[x]",
+        "players]",
+        "a..b",
+        "players[0]x",
+        "a\\",
+    ] {
+        assert_eq!(
+            Introspector::query(&game, query),
+            Err(IntrospectionError::InvalidQuery),
+            "{}",
+            query
+        );
+    }
+}
+
+#[test]
+fn test_query_result_can_be_navigated_to() {
+    let game = sample_game();
+    let result = Introspector::query(&game, "players[name=Bob].inventory[0]").unwrap();
+    let mut introspector = Introspector::new();
+    let mut frames = None;
+    for key in &result[0].path {
+        frames = Some(
+            introspector
+                .do_introspect(&game, IntrospectorNavCommand::ExpandElement(key.clone()))
+                .unwrap(),
+        );
+    }
+    let frames = frames.unwrap();
+    assert_eq!(frames.frames.len(), 5);
+    assert_eq!(frames.frames[4].keyvals[0].value, "bow");
+}
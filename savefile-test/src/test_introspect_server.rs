@@ -0,0 +1,169 @@
+use crate::roundtrip;
+use savefile::introspect_server::{IntrospectClient, IntrospectServer, MAX_KEY_LENGTH, PROTOCOL_VERSION};
+use savefile::prelude::*;
+use savefile::{IntrospectedElementKey, IntrospectorNavCommand};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+
+#[derive(Savefile)]
+pub struct ServerState {
+    tick: u64,
+    players: Vec<String>,
+}
+
+fn expand(key: &str, depth: usize) -> IntrospectorNavCommand {
+    IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+        depth,
+        key: key.to_string(),
+        key_disambiguator: 0,
+    })
+}
+
+#[test]
+fn test_introspect_server_tcp() {
+    let state = Arc::new(Mutex::new(ServerState {
+        tick: 1,
+        players: vec!["alice".to_string(), "bob".to_string()],
+    }));
+    let server = IntrospectServer::bind_tcp("127.0.0.1:0", state.clone()).unwrap();
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+
+    let mut client = IntrospectClient::connect_tcp(addr).unwrap();
+    let result = client.navigate(IntrospectorNavCommand::Nothing).unwrap().unwrap();
+    assert_eq!(result.frames.len(), 1);
+    assert_eq!(result.frames[0].keyvals[0].key.key, "tick");
+    assert_eq!(result.frames[0].keyvals[0].value, "1");
+
+    let result = client.navigate(expand("players", 0)).unwrap().unwrap();
+    assert_eq!(result.frames.len(), 2);
+    assert_eq!(result.frames[1].keyvals[1].value, "bob");
+    assert_eq!(result.total_len(), 4);
+
+    // The state is only locked while a request is processed
+    {
+        let mut state = state.lock().unwrap();
+        state.tick = 2;
+        state.players.push("carol".to_string());
+    }
+    let result = client.navigate(IntrospectorNavCommand::Nothing).unwrap().unwrap();
+    assert_eq!(result.frames[0].keyvals[0].value, "2");
+    assert_eq!(result.frames[1].keyvals[2].value, "carol");
+
+    // Navigation errors are returned, without breaking the connection
+    let err = client.navigate(expand("nonexistent", 0)).unwrap().unwrap_err();
+    assert_eq!(err, IntrospectionError::UnknownKey);
+    let err = client.navigate(IntrospectorNavCommand::Up).unwrap().unwrap_err();
+    assert_eq!(err, IntrospectionError::AlreadyAtTop);
+
+    // Each client has its own navigation state
+    let mut other_client = IntrospectClient::connect_tcp(addr).unwrap();
+    let result = other_client.navigate(IntrospectorNavCommand::Nothing).unwrap().unwrap();
+    assert_eq!(result.frames.len(), 1);
+}
+
+#[test]
+fn test_introspect_server_paging() {
+    let state = Arc::new(Mutex::new((0..1000u32).collect::<Vec<_>>()));
+    let server = IntrospectServer::bind_tcp("127.0.0.1:0", state)
+        .unwrap()
+        .with_child_load_count(10);
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+    let mut client = IntrospectClient::connect_tcp(addr).unwrap();
+    let result = client
+        .navigate(IntrospectorNavCommand::Page { depth: 0, offset: 500 })
+        .unwrap()
+        .unwrap();
+    let frame = &result.frames[0];
+    assert_eq!(frame.offset, 500);
+    assert_eq!(frame.keyvals.len(), 10);
+    assert!(frame.limit_reached);
+    assert_eq!(frame.keyvals[0].value, "500");
+}
+
+#[test]
+fn test_introspect_server_rejects_non_loopback() {
+    let state = Arc::new(Mutex::new(0u32));
+    assert!(IntrospectServer::bind_tcp("0.0.0.0:0", state.clone()).is_err());
+    // Rejected even if binding the first address would succeed
+    let addrs: [SocketAddr; 2] = ["127.0.0.1:0".parse().unwrap(), "0.0.0.0:0".parse().unwrap()];
+    assert!(IntrospectServer::bind_tcp(&addrs[..], state).is_err());
+}
+
+fn send_raw_request(addr: SocketAddr, request: &[u8]) -> bool {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request).unwrap();
+    // The server closes the connection instead of answering
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).is_ok() && response.is_empty()
+}
+
+#[test]
+fn test_introspect_server_rejects_oversized_requests() {
+    let state = Arc::new(Mutex::new(vec![1u32, 2, 3]));
+    let server = IntrospectServer::bind_tcp("127.0.0.1:0", state).unwrap();
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+
+    assert!(send_raw_request(addr, &u32::MAX.to_le_bytes()));
+
+    // A small command, claiming to contain a huge key
+    let mut command = Vec::new();
+    Serializer::save_noschema(&mut command, PROTOCOL_VERSION, &expand("x", 0)).unwrap();
+    let key_length_pos = command.len() - 8 - 1 - 8;
+    command[key_length_pos..key_length_pos + 8].copy_from_slice(&(1u64 << 62).to_le_bytes());
+    let mut request = (command.len() as u32).to_le_bytes().to_vec();
+    request.extend(&command);
+    assert!(send_raw_request(addr, &request));
+
+    // The server is still running
+    let mut client = IntrospectClient::connect_tcp(addr).unwrap();
+    let result = client.navigate(IntrospectorNavCommand::Nothing).unwrap().unwrap();
+    assert_eq!(result.frames[0].keyvals.len(), 3);
+
+    let long_key = "x".repeat(MAX_KEY_LENGTH + 1);
+    assert!(client.navigate(expand(&long_key, 0)).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_introspect_server_unix_socket() {
+    let path = std::env::temp_dir().join(format!("savefile-introspect-test-{}.sock", std::process::id()));
+    _ = std::fs::remove_file(&path);
+    let state = Arc::new(Mutex::new(vec![Some(7u8), None]));
+    IntrospectServer::bind_unix(&path, state).unwrap().spawn();
+    let mut client = IntrospectClient::connect_unix(&path).unwrap();
+    let result = client
+        .navigate(IntrospectorNavCommand::SelectNth {
+            select_depth: 0,
+            select_index: 0,
+        })
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.frames[0].selected, Some(0));
+    assert_eq!(result.frames[0].keyvals[1].value, "None");
+    _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_introspection_protocol_roundtrip() {
+    let commands = vec![
+        expand("players", 3),
+        IntrospectorNavCommand::SelectNth {
+            select_depth: 1,
+            select_index: 2,
+        },
+        IntrospectorNavCommand::Nothing,
+        IntrospectorNavCommand::Up,
+        IntrospectorNavCommand::Page { depth: 2, offset: 100 },
+    ];
+    for command in commands {
+        assert_eq!(roundtrip(command.clone()), command);
+    }
+    assert_eq!(
+        roundtrip(IntrospectionError::NothingSelected),
+        IntrospectionError::NothingSelected
+    );
+}
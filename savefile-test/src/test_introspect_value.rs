@@ -0,0 +1,127 @@
+use savefile::prelude::*;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Savefile)]
+pub struct Sensor {
+    #[savefile_introspect_key]
+    id: u16,
+    reading: f32,
+}
+
+#[derive(Savefile)]
+pub struct Plain {
+    x: u32,
+}
+
+#[derive(Savefile)]
+pub enum Mode {
+    Off,
+    Fixed(u8),
+    Named(#[savefile_introspect_key] String),
+}
+
+#[test]
+fn test_typed_value_primitives() {
+    assert_eq!((-5i8).introspect_typed_value(), IntrospectValue::Int(-5));
+    assert_eq!(i128::MIN.introspect_typed_value(), IntrospectValue::Int(i128::MIN));
+    assert_eq!(42u64.introspect_typed_value(), IntrospectValue::UInt(42));
+    assert_eq!(
+        usize::MAX.introspect_typed_value(),
+        IntrospectValue::UInt(usize::MAX as u128)
+    );
+    assert_eq!(1.5f32.introspect_typed_value(), IntrospectValue::Float(1.5));
+    assert_eq!(true.introspect_typed_value(), IntrospectValue::Bool(true));
+    assert_eq!('x'.introspect_typed_value(), IntrospectValue::Char('x'));
+    assert_eq!(
+        "hello".to_string().introspect_typed_value(),
+        IntrospectValue::Str("hello".to_string())
+    );
+    // The string fallback is still available, and matches the old behavior
+    assert_eq!(42u64.introspect_value(), "42");
+}
+
+#[test]
+fn test_typed_value_wrappers() {
+    assert_eq!(Some(7u32).introspect_typed_value(), IntrospectValue::UInt(7));
+    assert_eq!(None::<u32>.introspect_typed_value(), IntrospectValue::None);
+    assert_eq!(Box::new(-3i32).introspect_typed_value(), IntrospectValue::Int(-3));
+    assert_eq!(
+        std::sync::Arc::new(false).introspect_typed_value(),
+        IntrospectValue::Bool(false)
+    );
+}
+
+#[test]
+fn test_typed_value_collections_have_no_value() {
+    assert_eq!(vec![1u8, 2, 3].introspect_typed_value(), IntrospectValue::None);
+    assert_eq!([1u32; 4].introspect_typed_value(), IntrospectValue::None);
+    assert_eq!((1u8, 2u8).introspect_typed_value(), IntrospectValue::None);
+    let map: HashMap<u32, u32> = HashMap::new();
+    assert_eq!(map.introspect_typed_value(), IntrospectValue::None);
+}
+
+#[test]
+fn test_typed_value_timestamps() {
+    let t = UNIX_EPOCH + Duration::new(1_700_000_000, 250);
+    assert_eq!(
+        t.introspect_typed_value(),
+        IntrospectValue::Timestamp {
+            secs: 1_700_000_000,
+            nanos: 250
+        }
+    );
+    let before = UNIX_EPOCH - Duration::from_millis(1500);
+    assert_eq!(
+        before.introspect_typed_value(),
+        IntrospectValue::Timestamp {
+            secs: -2,
+            nanos: 500_000_000
+        }
+    );
+    let now = SystemTime::now();
+    assert!(matches!(
+        now.introspect_typed_value(),
+        IntrospectValue::Timestamp { .. }
+    ));
+
+    let dt = chrono::DateTime::from_timestamp(1_600_000_000, 123).unwrap();
+    assert_eq!(
+        dt.introspect_typed_value(),
+        IntrospectValue::Timestamp {
+            secs: 1_600_000_000,
+            nanos: 123
+        }
+    );
+}
+
+#[test]
+fn test_typed_value_bytes() {
+    let b = bytes::Bytes::from_static(b"\x00\x01\xff");
+    assert_eq!(b.introspect_typed_value(), IntrospectValue::Bytes(vec![0, 1, 255]));
+}
+
+#[test]
+fn test_typed_value_derived() {
+    let sensor = Sensor { id: 12, reading: 0.5 };
+    assert_eq!(sensor.introspect_typed_value(), IntrospectValue::UInt(12));
+    assert_eq!(sensor.introspect_value(), "12");
+    assert_eq!(sensor.reading, 0.5);
+
+    let plain = Plain { x: 1 };
+    assert_eq!(plain.introspect_typed_value(), IntrospectValue::None);
+    assert_eq!(plain.x, 1);
+
+    assert_eq!(
+        Mode::Off.introspect_typed_value(),
+        IntrospectValue::Str("Mode::Off".to_string())
+    );
+    assert_eq!(
+        Mode::Fixed(3).introspect_typed_value(),
+        IntrospectValue::Str("Mode::Fixed".to_string())
+    );
+    assert_eq!(
+        Mode::Named("auto".to_string()).introspect_typed_value(),
+        IntrospectValue::Str("auto".to_string())
+    );
+}
@@ -0,0 +1,88 @@
+use crate::{assert_roundtrip, roundtrip};
+use nalgebra::{Isometry2, Matrix3, Matrix4, Point2, SMatrix, UnitQuaternion, Vector2, Vector3, Vector4};
+use savefile::prelude::*;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Robot {
+    position: Point2<f64>,
+    velocity: Vector2<f64>,
+    color: Vector4<u8>,
+    orientation: UnitQuaternion<f32>,
+    pose: Isometry2<f64>,
+    inertia: Matrix3<f64>,
+    transform: Matrix4<f32>,
+    jacobian: SMatrix<f32, 2, 3>,
+}
+
+#[test]
+fn test_nalgebra_roundtrip() {
+    assert_roundtrip(Robot {
+        position: Point2::new(1.0, -2.0),
+        velocity: Vector2::new(0.5, 0.25),
+        color: Vector4::new(1, 2, 3, 255),
+        orientation: UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3),
+        pose: Isometry2::new(Vector2::new(3.0, 4.0), 0.7),
+        inertia: Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0),
+        transform: Matrix4::new_scaling(2.0),
+        jacobian: SMatrix::<f32, 2, 3>::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0),
+    });
+}
+
+#[test]
+fn test_nalgebra_packed() {
+    assert!(unsafe { <Point2<f64> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <Vector3<f32> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <Matrix4<f32> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <SMatrix<u16, 3, 5> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <UnitQuaternion<f64> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <Isometry2<f32> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <Vector3<String> as Packed>::repr_c_optimization_safe(0) }.is_false());
+
+    let points: Vec<_> = (0..100).map(|x| Point2::new(x as f32, -x as f32)).collect();
+    assert_roundtrip(points);
+    let strings = Vector2::new("a".to_string(), "b".to_string());
+    assert_roundtrip(strings);
+}
+
+#[test]
+fn test_nalgebra_schema_keeps_shape() {
+    assert_eq!(get_schema::<Vector3<f32>>(0), get_schema::<[f32; 3]>(0));
+    assert_eq!(get_schema::<Matrix3<f32>>(0), get_schema::<[[f32; 3]; 3]>(0));
+    assert_ne!(get_schema::<SMatrix<f32, 2, 3>>(0), get_schema::<SMatrix<f32, 3, 2>>(0));
+}
+
+#[test]
+fn test_glam_roundtrip() {
+    assert_roundtrip(glam::Vec2::new(1.0, 2.0));
+    assert_roundtrip(vec![glam::Vec3::new(1.0, 2.0, 3.0), glam::Vec3::ZERO]);
+    assert_roundtrip(glam::Vec4::new(1.0, 2.0, 3.0, 4.0));
+    assert_roundtrip(glam::Quat::from_rotation_y(0.5));
+    assert_roundtrip(glam::Mat3::from_cols_array(&[
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0,
+    ]));
+    assert_roundtrip(glam::Mat4::from_scale(glam::Vec3::new(1.0, 2.0, 3.0)));
+    assert_roundtrip(glam::DVec2::new(1.0, 2.0));
+    assert_roundtrip(glam::DVec3::new(1.0, 2.0, 3.0));
+    assert_roundtrip(vec![glam::DVec4::new(1.0, 2.0, 3.0, 4.0); 10]);
+    assert!(unsafe { <glam::Vec3 as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <glam::Mat4 as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <glam::DVec4 as Packed>::repr_c_optimization_safe(0) }.is_yes());
+}
+
+#[test]
+fn test_glam_and_nalgebra_are_interchangeable() {
+    let data = save_to_mem(0, &vec![glam::Vec3::new(1.0, 2.0, 3.0)]).unwrap();
+    let loaded: Vec<Vector3<f32>> = load_from_mem(&data, 0).unwrap();
+    assert_eq!(loaded, vec![Vector3::new(1.0, 2.0, 3.0)]);
+
+    let matrix = glam::Mat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    let data = save_to_mem(0, &matrix).unwrap();
+    let loaded: Matrix3<f32> = load_from_mem(&data, 0).unwrap();
+    assert_eq!(loaded[(0, 1)], matrix.col(1).x);
+
+    let quat = roundtrip(glam::Quat::from_rotation_z(1.0));
+    let data = save_to_mem(0, &quat).unwrap();
+    let loaded: UnitQuaternion<f32> = load_from_mem(&data, 0).unwrap();
+    assert_eq!(loaded.w, quat.w);
+    assert_eq!(loaded.k, quat.z);
+}
@@ -0,0 +1,61 @@
+use crate::{assert_roundtrip, load_from_mem, roundtrip, save_to_mem};
+use ndarray::{arr0, Array, Array2, Array3, ArrayD, IxDyn, ShapeBuilder};
+use savefile::prelude::*;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Tensors {
+    weights: Array2<f32>,
+    activations: ArrayD<f64>,
+    labels: Array<String, ndarray::Ix1>,
+}
+
+#[test]
+fn test_ndarray_roundtrip() {
+    assert_roundtrip(Tensors {
+        weights: Array2::from_shape_fn((3, 4), |(r, c)| (r * 10 + c) as f32),
+        activations: ArrayD::from_shape_fn(IxDyn(&[2, 3, 2]), |ix| ix[0] as f64 - ix[1] as f64 * 0.5 + ix[2] as f64),
+        labels: Array::from(vec!["a".to_string(), "b".to_string()]),
+    });
+    assert_roundtrip(arr0(42u32));
+    assert_roundtrip(Array2::<u8>::zeros((0, 5)));
+    assert_roundtrip(ArrayD::<u8>::zeros(IxDyn(&[])));
+}
+
+#[test]
+fn test_ndarray_non_standard_layout() {
+    let array = Array2::from_shape_fn((3, 5), |(r, c)| (r * 100 + c) as u32);
+    let transposed = array.t().to_owned();
+    let fortran = Array2::from_shape_vec((2, 3).f(), vec![1, 2, 3, 4, 5, 6u16]).unwrap();
+    assert!(!fortran.is_standard_layout());
+
+    let loaded = roundtrip(fortran.clone());
+    assert_eq!(loaded, fortran);
+    assert_eq!(roundtrip(transposed.clone()), transposed);
+
+    let mut sliced = Array3::from_shape_fn((4, 4, 4), |(a, b, c)| (a * 16 + b * 4 + c) as i64);
+    sliced.invert_axis(ndarray::Axis(1));
+    assert_eq!(roundtrip(sliced.clone()), sliced);
+}
+
+#[test]
+fn test_ndarray_dimensionality_in_schema() {
+    let array = Array2::<f32>::ones((2, 2));
+    let data = save_to_mem(0, &array).unwrap();
+    assert!(matches!(
+        load_from_mem::<Array3<f32>>(&data, 0),
+        Err(SavefileError::IncompatibleSchema { .. })
+    ));
+    assert!(load_from_mem::<ArrayD<f32>>(&data, 0).is_err());
+    assert_eq!(load_from_mem::<Array2<f32>>(&data, 0).unwrap(), array);
+}
+
+#[test]
+fn test_ndarray_introspect() {
+    let array = Array2::from_shape_fn((2, 3), |(r, c)| r * 3 + c);
+    assert_eq!(array.introspect_value(), "ndarray[2, 3]");
+    assert_eq!(array.introspect_len(), 6);
+    let child = array.introspect_child(4).unwrap();
+    assert_eq!(child.key(), "[1, 1]");
+    assert_eq!(child.val().introspect_value(), "4");
+    assert!(array.introspect_child(6).is_none());
+}
@@ -20,8 +20,8 @@ struct Inner {
 #[test]
 #[cfg(debug_assertions)] //This test only works in debug builds
 fn test_misaligned1() {
-    assert_eq!(unsafe { Inner::repr_c_optimization_safe(0).is_yes() }, false);
-    assert_eq!(unsafe { CorrectlyAligned::repr_c_optimization_safe(0).is_yes() }, true);
+    assert!(!unsafe { Inner::repr_c_optimization_safe(0).is_yes() });
+    assert!(unsafe { CorrectlyAligned::repr_c_optimization_safe(0).is_yes() });
 }
 
 #[test]
@@ -40,8 +40,8 @@ struct Inner2 {
 #[test]
 #[cfg(debug_assertions)] //This test only works in debug builds
 fn test_misaligned2() {
-    assert_eq!(unsafe { Inner2::repr_c_optimization_safe(0).is_yes() }, false);
-    assert_eq!(unsafe { CorrectlyAligned::repr_c_optimization_safe(0).is_yes() }, true);
+    assert!(!unsafe { Inner2::repr_c_optimization_safe(0).is_yes() });
+    assert!(unsafe { CorrectlyAligned::repr_c_optimization_safe(0).is_yes() });
 }
 
 #[test]
@@ -63,19 +63,13 @@ struct UnnamedFieldsStruct(usize);
 
 #[test]
 fn test_various_types_for_reprc() {
-    assert_eq!(unsafe { <() as Packed>::repr_c_optimization_safe(0).is_yes() }, true);
-    assert_eq!(unsafe { <u8>::repr_c_optimization_safe(0) }.is_yes(), true);
+    assert!(unsafe { <() as Packed>::repr_c_optimization_safe(0).is_yes() });
+    assert!(unsafe { <u8>::repr_c_optimization_safe(0) }.is_yes());
 
-    assert_eq!(unsafe { <MyUnitStruct>::repr_c_optimization_safe(0) }.is_yes(), true);
-    assert_eq!(
-        unsafe { UnnamedFieldsStruct::repr_c_optimization_safe(0) }.is_yes(),
-        false
-    ); //usize is 32 bit on 32 bit platforms.
+    assert!(unsafe { <MyUnitStruct>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(!unsafe { UnnamedFieldsStruct::repr_c_optimization_safe(0) }.is_yes()); //usize is 32 bit on 32 bit platforms.
 
-    assert_eq!(unsafe { <(u32, u32)>::repr_c_optimization_safe(0) }.is_yes(), true);
-    assert_eq!(unsafe { <(u32, u8)>::repr_c_optimization_safe(0) }.is_yes(), false);
-    assert_eq!(
-        unsafe { <(u32, u8, u8, u16)>::repr_c_optimization_safe(0) }.is_yes(),
-        true
-    );
+    assert!(unsafe { <(u32, u32)>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(!unsafe { <(u32, u8)>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <(u32, u8, u8, u16)>::repr_c_optimization_safe(0) }.is_yes());
 }
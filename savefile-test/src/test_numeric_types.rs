@@ -0,0 +1,105 @@
+use crate::{assert_roundtrip, roundtrip};
+use half::{bf16, f16};
+use num_bigint::{BigInt, BigUint};
+use ordered_float::{NotNan, OrderedFloat};
+use rust_decimal::Decimal;
+use savefile::prelude::*;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Ledger {
+    balance: Decimal,
+    fees: Vec<Decimal>,
+    supply: BigUint,
+    delta: BigInt,
+}
+
+#[test]
+fn test_decimal_and_bigint_roundtrip() {
+    assert_roundtrip(Ledger {
+        balance: Decimal::from_str("-1234567.891").unwrap(),
+        fees: vec![
+            Decimal::ZERO,
+            Decimal::MAX,
+            Decimal::MIN,
+            Decimal::from_str("0.0000001").unwrap(),
+        ],
+        supply: BigUint::from_str("340282366920938463463374607431768211456000").unwrap(),
+        delta: BigInt::from_str("-98765432109876543210987654321").unwrap(),
+    });
+    assert_roundtrip(BigInt::from(0));
+    assert_roundtrip(BigInt::from(-1));
+    assert_roundtrip(BigUint::from(0u32));
+}
+
+#[test]
+fn test_decimal_keeps_scale() {
+    let loaded = roundtrip(Decimal::from_str("1.500").unwrap());
+    assert_eq!(loaded.scale(), 3);
+    assert_eq!(loaded.introspect_value(), "1.500");
+}
+
+#[test]
+fn test_decimal_rejects_invalid_scale() {
+    let mut buf = Vec::new();
+    Serializer::bare_serialize(&mut buf, 0, &(1i128, 29u8)).unwrap();
+    assert!(Deserializer::bare_deserialize::<Decimal>(&mut &buf[..], 0).is_err());
+}
+
+#[test]
+fn test_bigint_introspect() {
+    let value = BigInt::from_str("-123456789012345678901234567890").unwrap();
+    assert_eq!(value.introspect_value(), "-123456789012345678901234567890");
+}
+
+#[test]
+fn test_bigint_is_not_biguint() {
+    let data = save_to_mem(0, &BigUint::from(5u32)).unwrap();
+    assert!(load_from_mem::<BigInt>(&data, 0).is_err());
+}
+
+#[test]
+fn test_half_floats() {
+    let values = vec![f16::from_f32(1.5), f16::MAX, f16::NEG_INFINITY];
+    assert_roundtrip(values);
+    assert_roundtrip(vec![bf16::from_f32(-2.25), bf16::MIN_POSITIVE]);
+    assert!(unsafe { <f16 as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <bf16 as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert_eq!(f16::from_f32(0.5).introspect_value(), "0.5");
+
+    // f16 and bf16 have the same size, but different formats
+    let data = save_to_mem(0, &f16::ONE).unwrap();
+    assert!(load_from_mem::<bf16>(&data, 0).is_err());
+    assert!(load_from_mem::<u16>(&data, 0).is_err());
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Weights {
+    ordered: Vec<OrderedFloat<f32>>,
+    not_nan: NotNan<f64>,
+}
+
+#[test]
+fn test_ordered_float() {
+    assert_roundtrip(Weights {
+        ordered: vec![OrderedFloat(1.0), OrderedFloat(-0.5)],
+        not_nan: NotNan::new(3.25).unwrap(),
+    });
+    assert!(unsafe { <OrderedFloat<f32> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <NotNan<f32> as Packed>::repr_c_optimization_safe(0) }.is_false());
+    assert_eq!(get_schema::<OrderedFloat<f64>>(0), get_schema::<f64>(0));
+    assert_eq!(NotNan::new(2.5f32).unwrap().introspect_value(), "2.5");
+    assert_eq!(OrderedFloat(f64::NAN).introspect_value(), "NaN");
+
+    // NaN is fine for OrderedFloat
+    assert!(roundtrip(OrderedFloat(f64::NAN)).0.is_nan());
+}
+
+#[test]
+fn test_not_nan_rejects_nan() {
+    let data = save_to_mem(0, &f64::NAN).unwrap();
+    assert!(matches!(
+        load_from_mem::<NotNan<f64>>(&data, 0),
+        Err(SavefileError::ValidationFailed { .. })
+    ));
+}
@@ -0,0 +1,192 @@
+use crate::{assert_roundtrip, assert_roundtrip_to_new_version};
+use savefile::prelude::*;
+
+mod other_crate {
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Timespan {
+        pub secs: u64,
+        pub nanos: u32,
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Rgb(pub u8, pub u8, pub u8);
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum Shape {
+        Circle { radius: f32 },
+        Rect(f32, f32),
+        Empty,
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Labeled {
+        pub label: String,
+        pub value: u32,
+    }
+}
+
+use other_crate::{Labeled, Rgb, Shape, Timespan};
+
+#[derive(Savefile)]
+#[savefile_remote = "other_crate::Timespan"]
+struct TimespanDef {
+    secs: u64,
+    nanos: u32,
+}
+
+#[derive(Savefile)]
+#[savefile_remote = "other_crate::Rgb"]
+struct RgbDef(u8, u8, u8);
+
+#[derive(Savefile)]
+#[savefile_remote = "other_crate::Shape"]
+enum ShapeDef {
+    Circle { radius: f32 },
+    Rect(f32, f32),
+    Empty,
+}
+
+#[derive(Savefile)]
+#[savefile_remote = "std::ops::Range<T>"]
+struct RangeDef<T: Serialize + Deserialize + Introspect> {
+    start: T,
+    end: T,
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Drawing {
+    shapes: Vec<Remote<Shape, ShapeDef>>,
+    colors: Vec<Remote<Rgb, RgbDef>>,
+    duration: Remote<Timespan, TimespanDef>,
+    frames: Remote<std::ops::Range<u32>, RangeDef<u32>>,
+}
+
+fn sample_drawing() -> Drawing {
+    Drawing {
+        shapes: vec![
+            Shape::Circle { radius: 2.5 }.into(),
+            Shape::Rect(1.0, 2.0).into(),
+            Shape::Empty.into(),
+        ],
+        colors: vec![Rgb(1, 2, 3).into(), Rgb(255, 0, 128).into()],
+        duration: Timespan { secs: 42, nanos: 17 }.into(),
+        frames: (10..20).into(),
+    }
+}
+
+#[test]
+fn test_remote_roundtrip() {
+    assert_roundtrip(sample_drawing());
+}
+
+#[test]
+fn test_remote_schema() {
+    let schema = get_schema::<Remote<Timespan, TimespanDef>>(0);
+    let Schema::Struct(s) = schema else {
+        panic!("Expected struct schema, got {:?}", schema);
+    };
+    assert_eq!(s.dbg_name, "Timespan");
+    let names: Vec<_> = s.fields.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, ["secs", "nanos"]);
+
+    let schema = get_schema::<Remote<Shape, ShapeDef>>(0);
+    let Schema::Enum(e) = schema else {
+        panic!("Expected enum schema, got {:?}", schema);
+    };
+    assert_eq!(e.dbg_name, "Shape");
+    assert_eq!(e.variants.len(), 3);
+    assert_eq!(e.variants[1].fields.len(), 2);
+
+    let schema = get_schema::<Remote<Rgb, RgbDef>>(0);
+    let Schema::Struct(s) = schema else {
+        panic!("Expected struct schema, got {:?}", schema);
+    };
+    let names: Vec<_> = s.fields.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, ["0", "1", "2"]);
+}
+
+#[test]
+fn test_remote_is_not_packed() {
+    assert!(unsafe { <Remote<Rgb, RgbDef> as Packed>::repr_c_optimization_safe(0) }.is_false());
+}
+
+#[test]
+fn test_remote_introspect() {
+    let drawing = sample_drawing();
+    assert_eq!(drawing.duration.introspect_value(), "Timespan");
+    assert_eq!(drawing.duration.introspect_len(), 2);
+    let child = drawing.duration.introspect_child(1).unwrap();
+    assert_eq!(child.key(), "nanos");
+    assert_eq!(child.val().introspect_value(), "17");
+    assert!(drawing.duration.introspect_child(2).is_none());
+
+    assert_eq!(drawing.shapes[0].introspect_value(), "Shape::Circle");
+    assert_eq!(drawing.shapes[1].introspect_len(), 2);
+    assert_eq!(drawing.shapes[1].introspect_child(1).unwrap().key(), "1");
+    assert_eq!(drawing.shapes[2].introspect_len(), 0);
+}
+
+#[derive(Savefile)]
+#[savefile_remote = "other_crate::Labeled"]
+struct LabeledDef {
+    #[savefile_introspect_key]
+    label: String,
+    value: u32,
+}
+
+#[test]
+fn test_remote_introspect_key() {
+    let item: Remote<Labeled, LabeledDef> = Labeled {
+        label: "first".to_string(),
+        value: 3,
+    }
+    .into();
+    assert_eq!(item.introspect_value(), "first");
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct LabeledV0 {
+    label: String,
+    old_value: String,
+}
+
+#[derive(Savefile)]
+#[savefile_remote = "other_crate::Labeled"]
+struct LabeledDefV1 {
+    label: String,
+    #[savefile_versions = "..0"]
+    old_value: Removed<String>,
+    #[savefile_versions = "1.."]
+    #[savefile_default_val = "7"]
+    value: u32,
+}
+
+#[test]
+fn test_remote_versioning() {
+    let loaded = assert_roundtrip_to_new_version(
+        LabeledV0 {
+            label: "x".to_string(),
+            old_value: "gone".to_string(),
+        },
+        0,
+        Remote::<Labeled, LabeledDefV1>::new(Labeled {
+            label: "x".to_string(),
+            value: 7,
+        }),
+        1,
+    );
+    assert_eq!(loaded.value, 7);
+
+    let mut data = Vec::new();
+    Serializer::bare_serialize(
+        &mut data,
+        1,
+        &Remote::<Labeled, LabeledDefV1>::new(Labeled {
+            label: "y".to_string(),
+            value: 3,
+        }),
+    )
+    .unwrap();
+    let back: Remote<Labeled, LabeledDefV1> = Deserializer::bare_deserialize(&mut &data[..], 1).unwrap();
+    assert_eq!(back.into_inner().value, 3);
+}
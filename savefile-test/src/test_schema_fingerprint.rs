@@ -0,0 +1,133 @@
+use savefile::prelude::*;
+
+mod narrow {
+    use savefile::prelude::*;
+
+    #[derive(Savefile, Debug, PartialEq)]
+    pub struct Config {
+        pub id: u32,
+        pub name: String,
+    }
+}
+
+mod wide {
+    use savefile::prelude::*;
+
+    #[derive(Savefile, Debug, PartialEq)]
+    pub struct Config {
+        pub id: u64,
+        pub name: String,
+    }
+}
+
+#[derive(Savefile, Debug, PartialEq)]
+pub struct Renamed {
+    pub other_id: u32,
+    pub other_name: String,
+}
+
+#[derive(Savefile, Debug, PartialEq)]
+pub enum Shape {
+    Circle(f32),
+    #[savefile_versions = "1.."]
+    Square {
+        side: f32,
+    },
+}
+
+#[derive(Savefile, Debug, PartialEq)]
+pub struct Generic<T> {
+    pub value: T,
+}
+
+#[test]
+fn test_fingerprint_is_stable() {
+    // The fingerprint is part of the file format, and must never change
+    assert_eq!(
+        Schema::Primitive(SchemaPrimitive::schema_u32).fingerprint(),
+        0x0880_9460_36ab_1be9_5aa0_7330_552a_3950
+    );
+}
+
+#[test]
+fn test_fingerprint_ignores_names() {
+    assert_eq!(
+        get_schema::<narrow::Config>(0).fingerprint(),
+        get_schema::<Renamed>(0).fingerprint()
+    );
+    assert_ne!(
+        get_schema::<narrow::Config>(0).fingerprint(),
+        get_schema::<wide::Config>(0).fingerprint()
+    );
+    assert_eq!(Schema::Undefined.fingerprint(), 0);
+}
+
+#[test]
+fn test_cached_fingerprint_matches_schema() {
+    for version in 0..2 {
+        let expected = get_schema::<Shape>(version).fingerprint();
+        assert_eq!(Shape::schema_fingerprint(version), expected);
+        assert_eq!(Shape::schema_fingerprint(version), expected);
+    }
+    assert_ne!(Shape::schema_fingerprint(0), Shape::schema_fingerprint(1));
+    assert_ne!(
+        Generic::<u32>::schema_fingerprint(0),
+        Generic::<u64>::schema_fingerprint(0)
+    );
+}
+
+#[test]
+fn test_load_with_fingerprint_mismatch_falls_back_to_schema() {
+    let data = save_to_mem(
+        0,
+        &narrow::Config {
+            id: 42,
+            name: "x".to_string(),
+        },
+    )
+    .unwrap();
+    let loaded: wide::Config = load_from_mem(&data, 0).unwrap();
+    assert_eq!(
+        loaded,
+        wide::Config {
+            id: 42,
+            name: "x".to_string()
+        }
+    );
+
+    // A corrupt fingerprint in the header just makes the load take the slow path
+    let mut corrupted = data.clone();
+    corrupted[16] ^= 0xff;
+    let loaded: narrow::Config = load_from_mem(&corrupted, 0).unwrap();
+    assert_eq!(loaded.id, 42);
+}
+
+#[test]
+fn test_load_file_without_fingerprint() {
+    // Files written by savefile lib version 2 have neither fingerprint nor schema size
+    let mut data = Vec::new();
+    data.extend_from_slice(b"savefile\0");
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.push(0);
+    get_schema::<narrow::Config>(0)
+        .serialize(&mut Serializer::<Vec<u8>>::new_raw(&mut data, 2))
+        .unwrap();
+    Serializer::bare_serialize(
+        &mut data,
+        0,
+        &narrow::Config {
+            id: 7,
+            name: "old".to_string(),
+        },
+    )
+    .unwrap();
+    let loaded: narrow::Config = load_from_mem(&data, 0).unwrap();
+    assert_eq!(
+        loaded,
+        narrow::Config {
+            id: 7,
+            name: "old".to_string()
+        }
+    );
+}
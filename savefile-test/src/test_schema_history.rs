@@ -0,0 +1,103 @@
+use savefile::prelude::*;
+use std::path::PathBuf;
+
+mod before {
+    use savefile::prelude::*;
+
+    #[derive(Savefile)]
+    pub struct Record {
+        pub id: u32,
+        #[savefile_versions = "1.."]
+        pub name: String,
+    }
+}
+
+mod after {
+    use savefile::prelude::*;
+
+    // An accidental change: 'id' was widened without adding a new version
+    #[derive(Savefile)]
+    pub struct Record {
+        pub id: u64,
+        #[savefile_versions = "1.."]
+        pub name: String,
+    }
+}
+
+#[derive(Savefile)]
+pub struct Wrapper<T> {
+    inner: T,
+}
+
+fn history_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("savefile-schema-history-{}-{}", name, std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_schema_history_records_and_verifies() {
+    let dir = history_dir("records");
+    verify_schema_history::<before::Record>(&dir, 1).unwrap();
+    assert!(dir.join("savefile_Record_0.schema").exists());
+    assert!(dir.join("savefile_Record_1.schema").exists());
+    // Unchanged schemas pass
+    verify_schema_history::<before::Record>(&dir, 1).unwrap();
+    // Adding a version records it, without affecting older versions
+    verify_schema_history::<before::Record>(&dir, 2).unwrap();
+    assert!(dir.join("savefile_Record_2.schema").exists());
+
+    verify_schema_history::<Wrapper<String>>(&dir, 0).unwrap();
+    assert!(dir.join("savefile_Wrapper_String_0.schema").exists());
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_schema_history_detects_change() {
+    let dir = history_dir("change");
+    verify_named_schema_history::<before::Record>(&dir, "Record", 1).unwrap();
+    let err = verify_named_schema_history::<after::Record>(&dir, "Record", 1).unwrap_err();
+    let SavefileError::IncompatibleSchema { message } = err else {
+        panic!("Unexpected error {:?}", err);
+    };
+    assert!(message.starts_with("The schema of version 0 of "), "{}", message);
+    assert!(message.contains("savefile_Record_0.schema"), "{}", message);
+    assert!(message.contains("- ") && message.contains("schema_u32"), "{}", message);
+    assert!(message.contains("+ ") && message.contains("schema_u64"), "{}", message);
+    let changed: Vec<&str> = message
+        .lines()
+        .filter(|line| line.starts_with('-') || line.starts_with('+'))
+        .collect();
+    assert!(
+        changed
+            .iter()
+            .any(|line| line.starts_with('-') && line.contains("schema_u32")),
+        "{}",
+        message
+    );
+    assert!(
+        changed
+            .iter()
+            .any(|line| line.starts_with('+') && line.contains("schema_u64")),
+        "{}",
+        message
+    );
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_schema_history_detects_name_collision() {
+    let dir = history_dir("collision");
+    verify_schema_history::<before::Record>(&dir, 1).unwrap();
+    let err = verify_schema_history::<after::Record>(&dir, 1).unwrap_err();
+    let SavefileError::GeneralError { msg } = err else {
+        panic!("Unexpected error {:?}", err);
+    };
+    assert!(msg.contains("savefile_Record_0.schema"), "{}", msg);
+    assert!(
+        msg.contains(std::any::type_name::<before::Record>()) && msg.contains(std::any::type_name::<after::Record>()),
+        "{}",
+        msg
+    );
+    _ = std::fs::remove_dir_all(&dir);
+}
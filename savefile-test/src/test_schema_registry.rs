@@ -0,0 +1,156 @@
+use savefile::prelude::*;
+use savefile::{DirectorySchemaRegistry, FileSchemaRegistry, MemorySchemaRegistry};
+use std::path::PathBuf;
+
+mod narrow {
+    use savefile::prelude::*;
+
+    #[derive(Savefile, Debug, PartialEq)]
+    pub struct Sample {
+        pub sensor: String,
+        pub reading: u32,
+        pub flags: Vec<bool>,
+    }
+}
+
+mod wide {
+    use savefile::prelude::*;
+
+    #[derive(Savefile, Debug, PartialEq)]
+    pub struct Sample {
+        pub sensor: String,
+        pub reading: u64,
+        pub flags: Vec<bool>,
+    }
+}
+
+fn narrow_sample() -> narrow::Sample {
+    narrow::Sample {
+        sensor: "t1".to_string(),
+        reading: 17,
+        flags: vec![true],
+    }
+}
+
+fn wide_sample() -> wide::Sample {
+    wide::Sample {
+        sensor: "t1".to_string(),
+        reading: 17,
+        flags: vec![true],
+    }
+}
+
+#[cfg(not(miri))]
+fn registry_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("savefile-schema-registry-{}-{}", name, std::process::id()));
+    _ = std::fs::remove_dir_all(&path);
+    _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_memory_registry() {
+    let mut registry = MemorySchemaRegistry::new();
+    let mut data = Vec::new();
+    save_with_schema_ref(&mut data, 0, &narrow_sample(), &mut registry).unwrap();
+    assert!(data.len() < save_to_mem(0, &narrow_sample()).unwrap().len());
+    assert_eq!(registry.schemas().count(), 1);
+
+    // An identical in-memory schema does not need the registry at all
+    let loaded: narrow::Sample = load_from_mem(&data, 0).unwrap();
+    assert_eq!(loaded, narrow_sample());
+
+    let loaded: wide::Sample = load_with_registry(&mut &data[..], 0, &registry).unwrap();
+    assert_eq!(loaded, wide_sample());
+}
+
+#[test]
+fn test_different_schema_needs_registry() {
+    let mut registry = MemorySchemaRegistry::new();
+    let mut data = Vec::new();
+    save_with_schema_ref(&mut data, 0, &narrow_sample(), &mut registry).unwrap();
+
+    let err = load_from_mem::<wide::Sample>(&data, 0).unwrap_err();
+    assert!(format!("{:?}", err).contains("load_with_registry"), "{:?}", err);
+
+    let err = load_with_registry::<wide::Sample>(&mut &data[..], 0, &MemorySchemaRegistry::new()).unwrap_err();
+    assert!(format!("{:?}", err).contains("was not found"), "{:?}", err);
+
+    // Schema differences are still detected
+    let err = load_with_registry::<Vec<u8>>(&mut &data[..], 0, &registry).unwrap_err();
+    assert!(matches!(err, SavefileError::IncompatibleSchema { .. }), "{:?}", err);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_directory_registry() {
+    let dir = registry_path("dir");
+    let mut data = Vec::new();
+    save_with_schema_ref(&mut data, 0, &narrow_sample(), &mut DirectorySchemaRegistry::new(&dir)).unwrap();
+    let fingerprint = get_schema::<narrow::Sample>(0).fingerprint();
+    assert!(dir.join(format!("{:032x}.schema", fingerprint)).exists());
+
+    let loaded: wide::Sample = load_with_registry(&mut &data[..], 0, &DirectorySchemaRegistry::new(&dir)).unwrap();
+    assert_eq!(loaded, wide_sample());
+    _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_file_registry() {
+    let path = registry_path("file");
+    let mut data = Vec::new();
+    {
+        let mut registry = FileSchemaRegistry::open(&path).unwrap();
+        save_with_schema_ref(&mut data, 0, &narrow_sample(), &mut registry).unwrap();
+        save_with_schema_ref(&mut Vec::new(), 0, &42u32, &mut registry).unwrap();
+    }
+
+    let registry = FileSchemaRegistry::open(&path).unwrap();
+    assert!(registry.contains_schema(get_schema::<u32>(0).fingerprint()).unwrap());
+    let loaded: wide::Sample = load_with_registry(&mut &data[..], 0, &registry).unwrap();
+    assert_eq!(loaded, wide_sample());
+    _ = std::fs::remove_file(&path);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_file_registry_keeps_schemas_added_by_other_instances() {
+    let path = registry_path("file-merge");
+    let mut first = FileSchemaRegistry::open(&path).unwrap();
+    let mut second = FileSchemaRegistry::open(&path).unwrap();
+    first.add_schema(&get_schema::<u32>(0)).unwrap();
+    second.add_schema(&get_schema::<String>(0)).unwrap();
+
+    let registry = FileSchemaRegistry::open(&path).unwrap();
+    assert!(registry.contains_schema(get_schema::<u32>(0).fingerprint()).unwrap());
+    assert!(registry.contains_schema(get_schema::<String>(0).fingerprint()).unwrap());
+    _ = std::fs::remove_file(&path);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_directory_registry_from_many_threads() {
+    let dir = registry_path("dir-threads");
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let dir = dir.clone();
+            std::thread::spawn(move || {
+                let mut registry = DirectorySchemaRegistry::new(&dir);
+                for _ in 0..10 {
+                    registry.add_schema(&get_schema::<narrow::Sample>(0)).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    let registry = DirectorySchemaRegistry::new(&dir);
+    let fingerprint = get_schema::<narrow::Sample>(0).fingerprint();
+    assert_eq!(
+        registry.get_schema(fingerprint).unwrap(),
+        Some(get_schema::<narrow::Sample>(0))
+    );
+    _ = std::fs::remove_dir_all(&dir);
+}
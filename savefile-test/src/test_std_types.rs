@@ -0,0 +1,117 @@
+use crate::{assert_roundtrip, roundtrip};
+use savefile::prelude::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::LinkedList;
+use std::ffi::{CString, OsString};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+use std::num::{NonZeroI32, NonZeroU128, NonZeroU16, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping};
+use std::ops::Bound;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Savefile)]
+struct Limits {
+    small: NonZeroU8,
+    medium: NonZeroU16,
+    large: NonZeroU64,
+    huge: NonZeroU128,
+    count: NonZeroUsize,
+    signed: NonZeroI32,
+    optional: Option<NonZeroU64>,
+}
+
+#[test]
+fn test_nonzero_roundtrip() {
+    assert_roundtrip(Limits {
+        small: NonZeroU8::new(1).unwrap(),
+        medium: NonZeroU16::new(1000).unwrap(),
+        large: NonZeroU64::new(u64::MAX).unwrap(),
+        huge: NonZeroU128::new(3).unwrap(),
+        count: NonZeroUsize::new(17).unwrap(),
+        signed: NonZeroI32::new(-5).unwrap(),
+        optional: NonZeroU64::new(9),
+    });
+}
+
+#[test]
+fn test_nonzero_rejects_zero() {
+    let data = save_to_mem(0, &0u32).unwrap();
+    assert!(load_from_mem::<std::num::NonZeroU32>(&data, 0).is_err());
+    let data = save_to_mem(0, &7u32).unwrap();
+    assert_eq!(load_from_mem::<std::num::NonZeroU32>(&data, 0).unwrap().get(), 7);
+}
+
+#[test]
+fn test_nonzero_is_not_packed() {
+    assert!(unsafe { <NonZeroU64 as Packed>::repr_c_optimization_safe(0) }.is_false());
+}
+
+#[test]
+fn test_transparent_wrappers() {
+    assert_roundtrip(Wrapping(42u32));
+    assert_roundtrip(Saturating(-3i16));
+    assert_roundtrip(Reverse("text".to_string()));
+    assert_roundtrip(vec![Wrapping(1u64), Wrapping(u64::MAX)]);
+    assert!(unsafe { <Wrapping<u32> as Packed>::repr_c_optimization_safe(0) }.is_yes());
+    assert!(unsafe { <Reverse<String> as Packed>::repr_c_optimization_safe(0) }.is_false());
+    assert_eq!(get_schema::<Wrapping<u32>>(0), get_schema::<u32>(0));
+}
+
+#[test]
+fn test_ranges_and_bounds() {
+    assert_roundtrip(1u32..=7);
+    assert_roundtrip(vec![Bound::Included(3u8), Bound::Excluded(4), Bound::Unbounded]);
+    assert_roundtrip((Bound::Excluded("a".to_string()), Bound::<String>::Unbounded));
+}
+
+#[test]
+fn test_ordering() {
+    assert_roundtrip(vec![Ordering::Less, Ordering::Equal, Ordering::Greater]);
+    assert_eq!(Ordering::Greater.introspect_value(), "Greater");
+}
+
+#[test]
+fn test_linked_list() {
+    let list: LinkedList<String> = ["a", "b", "c"].iter().map(|x| x.to_string()).collect();
+    assert_eq!(list.introspect_len(), 3);
+    assert_eq!(list.introspect_child(2).unwrap().val().introspect_value(), "c");
+    assert_roundtrip(list);
+    assert_eq!(
+        get_schema::<LinkedList<u32>>(0),
+        get_schema::<std::collections::VecDeque<u32>>(0)
+    );
+}
+
+#[test]
+fn test_string_types() {
+    assert_roundtrip(OsString::from("some/path"));
+    assert_roundtrip(CString::new("hello").unwrap());
+    assert_roundtrip::<Box<str>>("boxed".into());
+    assert_roundtrip::<Rc<str>>("shared".into());
+
+    // Box<str> and String have the same schema, and can be loaded as each other.
+    let data = save_to_mem(0, &"converted".to_string()).unwrap();
+    let loaded: Box<str> = load_from_mem(&data, 0).unwrap();
+    assert_eq!(&*loaded, "converted");
+}
+
+#[test]
+fn test_cstring_rejects_nul() {
+    let data = save_to_mem(0, &vec![b'a', 0u8, b'b']).unwrap();
+    assert!(load_from_mem::<CString>(&data, 0).is_err());
+    let data = save_to_mem(0, &vec![b'a', b'b']).unwrap();
+    assert_eq!(load_from_mem::<CString>(&data, 0).unwrap().as_bytes(), b"ab");
+}
+
+#[test]
+fn test_net_types() {
+    assert_roundtrip(Ipv4Addr::new(192, 168, 0, 1));
+    assert_roundtrip(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    assert_roundtrip(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080));
+    let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 5, 7);
+    let loaded = roundtrip(v6);
+    assert_eq!(loaded, v6);
+    assert_eq!(loaded.flowinfo(), 5);
+    assert_eq!(loaded.scope_id(), 7);
+    assert_eq!(Ipv4Addr::new(10, 0, 0, 1).introspect_value(), "10.0.0.1");
+    assert_eq!(v6.introspect_value(), "[::1%7]:443");
+}
@@ -372,3 +372,145 @@ fn simple_vertest_b() {
     let _ver1: FastVersionB1 =
         assert_roundtrip_to_new_version(FastVersionB0 { a: 2, b: 3 }, 0, FastVersionB1 { a: 2, b: 3, c: 0 }, 1);
 }
+
+#[derive(Debug, PartialEq, Savefile)]
+enum NarrowEnum {
+    A,
+    B(u8, f32),
+}
+#[derive(Debug, PartialEq, Savefile)]
+enum WideEnum {
+    A,
+    B(u32, f64),
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct NarrowStruct {
+    a: u8,
+    b: u32,
+    c: i16,
+    d: f32,
+    e: Vec<u16>,
+    f: Option<i32>,
+    g: [u16; 2],
+    h: NarrowEnum,
+    i: String,
+}
+#[derive(Debug, PartialEq, Savefile)]
+struct WideStruct {
+    a: u16,
+    b: u64,
+    c: i64,
+    d: f64,
+    e: std::collections::VecDeque<u128>,
+    f: Option<i128>,
+    g: [u32; 2],
+    h: WideEnum,
+    i: String,
+}
+
+fn narrow_sample() -> NarrowStruct {
+    NarrowStruct {
+        a: 255,
+        b: u32::MAX,
+        c: -32768,
+        d: 1.5,
+        e: vec![1, 2, 65535],
+        f: Some(-7),
+        g: [3, 4],
+        h: NarrowEnum::B(42, -0.25),
+        i: "hello".to_string(),
+    }
+}
+
+#[test]
+fn test_automatic_widening() {
+    use crate::assert_roundtrip_to_new_version;
+    assert_roundtrip_to_new_version(
+        narrow_sample(),
+        0,
+        WideStruct {
+            a: 255,
+            b: u32::MAX as u64,
+            c: -32768,
+            d: 1.5,
+            e: [1, 2, 65535].into_iter().collect(),
+            f: Some(-7),
+            g: [3, 4],
+            h: WideEnum::B(42, -0.25),
+            i: "hello".to_string(),
+        },
+        0,
+    );
+    assert_roundtrip_to_new_version(NarrowEnum::A, 0, WideEnum::A, 0);
+}
+
+#[test]
+fn test_automatic_widening_large_vec() {
+    let narrow: Vec<u16> = (0..100_000u32).map(|x| x as u16).collect();
+    let data = save_to_mem(0, &narrow).unwrap();
+    let wide: Vec<u32> = load_from_mem(&data, 0).unwrap();
+    assert!(wide.iter().zip(narrow.iter()).all(|(w, n)| *w == *n as u32));
+    assert_eq!(wide.len(), narrow.len());
+
+    // Errors while converting are reported as they are
+    let truncated = &data[..data.len() - 1];
+    let Err(SavefileError::IOError { io_error }) = load_from_mem::<Vec<u32>>(truncated, 0) else {
+        panic!("Truncated data must not load");
+    };
+    assert_eq!(io_error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_automatic_widening_strict() {
+    let data = save_to_mem(0, &narrow_sample()).unwrap();
+    let result = load_strict::<WideStruct>(&mut &data[..], 0);
+    assert!(matches!(result, Err(SavefileError::IncompatibleSchema { .. })));
+}
+
+#[test]
+fn test_no_automatic_narrowing() {
+    let data = save_to_mem(0, &WideEnum::B(1, 2.0)).unwrap();
+    let result = load_from_mem::<NarrowEnum>(&data, 0);
+    assert!(matches!(result, Err(SavefileError::IncompatibleSchema { .. })));
+}
+
+#[test]
+fn test_no_automatic_sign_change() {
+    let data = save_to_mem(0, &1u8).unwrap();
+    let result = load_from_mem::<i16>(&data, 0);
+    assert!(matches!(result, Err(SavefileError::IncompatibleSchema { .. })));
+}
+
+#[derive(Debug, PartialEq, Savefile)]
+struct NarrowTree {
+    value: u32,
+    children: Vec<NarrowTree>,
+}
+#[derive(Debug, PartialEq, Savefile)]
+struct WideTree {
+    value: u64,
+    children: Vec<WideTree>,
+}
+
+#[test]
+fn test_no_automatic_widening_of_recursive_types() {
+    let tree = NarrowTree {
+        value: 1,
+        children: vec![NarrowTree {
+            value: 2,
+            children: vec![],
+        }],
+    };
+    let data = save_to_mem(0, &tree).unwrap();
+    assert_eq!(load_from_mem::<NarrowTree>(&data, 0).unwrap(), tree);
+
+    let Err(SavefileError::IncompatibleSchema { message }) = load_from_mem::<WideTree>(&data, 0) else {
+        panic!("Recursive types must not be widened");
+    };
+    assert!(
+        message.contains("not supported for data containing recursive types"),
+        "{}",
+        message
+    );
+}
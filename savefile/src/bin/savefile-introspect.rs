@@ -0,0 +1,137 @@
+//! Interactive client for `savefile::introspect_server`.
+//!
+//! Usage: `savefile-introspect <ADDRESS>`, where ADDRESS is either a TCP address like
+//! `127.0.0.1:7777`, or the path of a Unix domain socket.
+
+use savefile::introspect_server::IntrospectClient;
+use savefile::{IntrospectedElementKey, IntrospectionResult, IntrospectorNavCommand, SavefileError};
+use std::io::{BufRead, Write};
+use std::net::SocketAddr;
+
+const HELP: &str = "\
+Commands:
+  ls              Show the current tree
+  cd <key> [n]    Expand the child with the given key, at the deepest level.
+                  If several children have the same key, n selects which one.
+  sel <index>     Expand the child with the given index, at the deepest level
+  up, ..          Go up one level
+  page <offset>   Show children starting at the given index, at the deepest level
+  help            Show this text
+  quit            Exit";
+
+fn connect(address: &str) -> Result<IntrospectClient, SavefileError> {
+    if let Ok(addr) = address.parse::<SocketAddr>() {
+        return IntrospectClient::connect_tcp(addr);
+    }
+    #[cfg(unix)]
+    {
+        IntrospectClient::connect_unix(address)
+    }
+    #[cfg(not(unix))]
+    {
+        IntrospectClient::connect_tcp(address)
+    }
+}
+
+fn parse_command(line: &str, deepest: usize) -> Result<Option<IntrospectorNavCommand>, String> {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return Ok(Some(IntrospectorNavCommand::Nothing));
+    };
+    let rest: Vec<&str> = words.collect();
+    let parse_number = |word: Option<&&str>| -> Result<usize, String> {
+        let word = word.ok_or_else(|| format!("'{}' needs a numeric argument", command))?;
+        word.parse().map_err(|_| format!("'{}' is not a number", word))
+    };
+    Ok(Some(match command {
+        "ls" => IntrospectorNavCommand::Nothing,
+        "cd" => {
+            let key = rest.first().ok_or("'cd' needs a key")?;
+            let key_disambiguator = if rest.len() > 1 { parse_number(rest.get(1))? } else { 0 };
+            IntrospectorNavCommand::ExpandElement(IntrospectedElementKey {
+                depth: deepest,
+                key: key.to_string(),
+                key_disambiguator,
+            })
+        }
+        "sel" => IntrospectorNavCommand::SelectNth {
+            select_depth: deepest,
+            select_index: parse_number(rest.first())?,
+        },
+        "up" | ".." => IntrospectorNavCommand::Up,
+        "page" => IntrospectorNavCommand::Page {
+            depth: deepest,
+            offset: parse_number(rest.first())?,
+        },
+        "help" => {
+            println!("{}", HELP);
+            return Ok(None);
+        }
+        "quit" | "exit" => std::process::exit(0),
+        other => return Err(format!("Unknown command '{}', try 'help'", other)),
+    }))
+}
+
+fn print_result(result: &IntrospectionResult) {
+    print!("{}", result);
+    if let Some(frame) = result.frames.last() {
+        if frame.offset > 0 || frame.limit_reached {
+            println!(
+                "(children {}..{}{})",
+                frame.offset,
+                frame.offset + frame.keyvals.len(),
+                if frame.limit_reached {
+                    ", use 'page' to see more"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+}
+
+fn main() {
+    let Some(address) = std::env::args().nth(1) else {
+        eprintln!("Usage: savefile-introspect <ADDRESS>");
+        eprintln!("ADDRESS is a TCP address like 127.0.0.1:7777, or a Unix domain socket path");
+        std::process::exit(1);
+    };
+    let mut client = match connect(&address) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Failed to connect to {}: {:?}", address, err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut deepest = 0;
+    let stdin = std::io::stdin();
+    let mut line = String::new();
+    let mut command = Some(IntrospectorNavCommand::Nothing);
+    loop {
+        if let Some(command) = command.take() {
+            match client.navigate(command) {
+                Ok(Ok(result)) => {
+                    deepest = result.frames.len().saturating_sub(1);
+                    print_result(&result);
+                }
+                Ok(Err(err)) => println!("Error: {:?}", err),
+                Err(err) => {
+                    eprintln!("Connection failed: {:?}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        print!("> ");
+        _ = std::io::stdout().flush();
+        line.clear();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        match parse_command(line.trim(), deepest) {
+            Ok(parsed) => command = parsed,
+            Err(err) => println!("{}", err),
+        }
+    }
+}
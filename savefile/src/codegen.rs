@@ -0,0 +1,1251 @@
+//! Generation of source code from schemas.
+//!
+//! This makes it possible to read old files, even if the type definitions used to
+//! write them no longer exist. The schema stored in a file contains enough information
+//! to recreate equivalent type definitions, or a reader for the data in another language.
+
+use crate::{Field, Schema, SchemaEnum, SchemaPrimitive, SchemaStruct};
+use std::fmt::Write;
+
+/// Options for [schema_to_rust].
+#[derive(Debug, Clone)]
+pub struct RustCodegenOptions {
+    /// The version of the data described by the schema. Enum variants which do not exist in
+    /// this version are generated as placeholders, only present in later versions.
+    /// The generated types should be loaded using this version.
+    pub version: u32,
+    /// The name of the type alias generated for the schema, if it is not a struct or enum.
+    pub root_name: String,
+    /// Traits to derive for all generated types, in addition to `Savefile`.
+    pub derives: Vec<String>,
+}
+
+impl Default for RustCodegenOptions {
+    fn default() -> Self {
+        RustCodegenOptions {
+            version: 0,
+            root_name: "Root".to_string(),
+            derives: vec!["Debug".to_string()],
+        }
+    }
+}
+
+/// Generate Rust type definitions which can load data saved with the given schema.
+///
+/// Structs and enums are named after their `dbg_name`, and get one `#[derive(Savefile)]`
+/// type definition each. Types with the same name but different schemas (such as different
+/// instantiations of a generic type) get a numeric suffix. If the schema is not a struct or
+/// enum, a type alias named [RustCodegenOptions::root_name] is generated for it.
+///
+/// Some notes:
+///  * Since the memory format of `Box<T>` is identical to that of `T`, boxes are not visible
+///    in schemas. Types which contain themselves are given a `Box` where needed.
+///  * Maps are generated as `HashMap` if the key is a primitive, and as `Vec<(K, V)>` otherwise.
+///  * Primitives such as dates and decimals are generated using the types of the crates
+///    savefile supports for them, which need the corresponding savefile features.
+///  * Schemas which can't be represented, such as [Schema::Custom], generate a `compile_error!`.
+pub fn schema_to_rust(schema: &Schema, options: &RustCodegenOptions) -> String {
+    let mut generator = Generator::new(RustLanguage { options });
+    let root_type = match schema {
+        Schema::Struct(_) | Schema::Enum(_) => {
+            generator.named_type(schema, false);
+            None
+        }
+        _ => Some(generator.type_expr(schema, false)),
+    };
+
+    let mut output = String::new();
+    output.push_str("// Generated by savefile::codegen::schema_to_rust\n");
+    output.push_str("use savefile::prelude::*;\n");
+    if let Some(root_type) = root_type {
+        _ = write!(
+            output,
+            "\npub type {} = {};\n",
+            rust_identifier(&options.root_name),
+            root_type
+        );
+    }
+    for definition in generator.definitions {
+        output.push('\n');
+        output.push_str(&definition);
+    }
+    output
+}
+
+/// Generate a self-contained Python module which can read files saved with the given schema.
+///
+/// The module has no dependencies outside the Python standard library. It provides
+/// `load_file(path)` and `loads(data)`, which check the file header and the schema
+/// fingerprint, and decode the payload. Structs become dataclasses, and each enum
+/// becomes a base class with one dataclass per variant, named `Enum_Variant`.
+/// `Result` values are decoded as `Ok` or `Err`, and maps as dicts.
+///
+/// If numpy is installed, vectors and arrays of numbers are decoded as numpy arrays,
+/// and vectors of structs containing only numbers (such as `Packed` structs) as numpy
+/// structured arrays. Otherwise, these are decoded as lists.
+///
+/// Only files with the same schema fingerprint as the given schema can be read. Files written
+/// by savefile format versions before 3 have no schema fingerprint, so their embedded schema is
+/// skipped without being checked. Such files must be read using `loads(data, embedded_schema=False)`
+/// if they were saved without a schema.
+/// Dates and durations are decoded with the microsecond precision of Python's `datetime`.
+pub fn schema_to_python(schema: &Schema) -> String {
+    let mut generator = Generator::new(PythonLanguage);
+    let root = generator.type_expr(schema, false);
+
+    let mut output = String::new();
+    output.push_str(PYTHON_PRELUDE);
+    _ = writeln!(output, "\nSCHEMA_FINGERPRINT = 0x{:032x}\n", schema.fingerprint());
+    output.push_str(PYTHON_RUNTIME);
+    for definition in generator.definitions {
+        output.push_str("\n\n");
+        output.push_str(&definition);
+    }
+    _ = write!(output, "\n\ndef _read_root(r):\n    return {}\n", root.read);
+    output
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RecursionPoint {
+    /// An item of a collection. These are always recursion points.
+    CollectionItem,
+    /// A struct or enum, which may be the content of an (invisible) Box.
+    Named,
+}
+
+struct GeneratedType {
+    dbg_name: String,
+    fingerprint: u128,
+    name: String,
+}
+
+/// The parts of code generation which differ between target languages.
+///
+/// Each method returns an expression for a value of the given kind. For Rust, this
+/// is a type, for Python, the code which reads the value.
+trait Language {
+    type Expr;
+    /// The name of the public function using this language, for error messages
+    const GENERATOR: &'static str;
+    fn identifier(&self, name: &str) -> String;
+    fn primitive(&self, primitive: &SchemaPrimitive) -> Self::Expr;
+    fn vector(&self, item_schema: &Schema, item: Self::Expr) -> Self::Expr;
+    fn map(&self, key_schema: &Schema, key: Self::Expr, value: Self::Expr) -> Self::Expr;
+    fn array(&self, item_schema: &Schema, item: Self::Expr, count: usize) -> Self::Expr;
+    fn option(&self, item: Self::Expr) -> Self::Expr;
+    fn tuple(&self, items: Vec<Self::Expr>) -> Self::Expr;
+    fn result(&self, ok: Self::Expr, err: Self::Expr) -> Self::Expr;
+    fn std_duration(&self) -> Self::Expr;
+    fn zero_size(&self) -> Self::Expr;
+    fn boxed(&self, item: Self::Expr) -> Self::Expr;
+    fn utc_timestamp(&self) -> Self::Expr;
+    fn unsupported(&self, message: &str) -> Self::Expr;
+    /// A generated struct or enum
+    fn named(&self, name: &str) -> Self::Expr;
+    /// A generated type, from within itself. If 'direct' is true, there is no
+    /// indirection between the type and this reference.
+    fn recursive_reference(&self, name: &str, direct: bool) -> Self::Expr;
+    /// A [Schema::Recursion], with the given target
+    fn recursion(&self, target: Self::Expr) -> Self::Expr;
+    /// 'fields' has one expression for each field of the struct
+    fn struct_definition(&self, name: &str, schema_struct: &SchemaStruct, fields: Vec<Self::Expr>) -> String;
+    /// 'variants' has the field expressions of each variant of the enum
+    fn enum_definition(&self, name: &str, schema_enum: &SchemaEnum, variants: Vec<Vec<Self::Expr>>) -> String;
+}
+
+struct Generator<'s, L> {
+    language: L,
+    types: Vec<GeneratedType>,
+    definitions: Vec<String>,
+    /// Indices into 'types', for the types currently being generated
+    generating: Vec<usize>,
+    /// The schema nodes which may have been recursion points, when the schema was created.
+    /// See [crate::WithSchemaContext::possible_recursion].
+    recursion_points: Vec<(RecursionPoint, &'s Schema)>,
+}
+
+impl<'s, L: Language> Generator<'s, L> {
+    fn new(language: L) -> Self {
+        Generator {
+            language,
+            types: Vec::new(),
+            definitions: Vec::new(),
+            generating: Vec::new(),
+            recursion_points: Vec::new(),
+        }
+    }
+
+    /// Return an expression for the given schema. If 'direct' is true, the value is
+    /// not stored behind any indirection, so types which contain themselves must be boxed.
+    fn type_expr(&mut self, schema: &'s Schema, direct: bool) -> L::Expr {
+        match schema {
+            Schema::Struct(schema_struct) => {
+                if let Some(fields) = tuple_fields(schema_struct) {
+                    let items = fields.iter().map(|field| self.type_expr(&field.value, true)).collect();
+                    return self.language.tuple(items);
+                }
+                if schema_struct.dbg_name == "Duration"
+                    && schema_struct.fields.len() == 1
+                    && schema_struct.fields[0].name == "Duration"
+                    && *schema_struct.fields[0].value == Schema::Primitive(SchemaPrimitive::schema_u128)
+                {
+                    return self.language.std_duration();
+                }
+                self.named_type(schema, direct)
+            }
+            Schema::Enum(schema_enum) => {
+                if let Some((ok, err)) = result_types(schema_enum) {
+                    let ok = self.type_expr(ok, direct);
+                    let err = self.type_expr(err, direct);
+                    return self.language.result(ok, err);
+                }
+                self.named_type(schema, direct)
+            }
+            Schema::Primitive(primitive) => self.language.primitive(primitive),
+            Schema::Vector(item, _) => {
+                if let Schema::Struct(pair) = &**item {
+                    if pair.dbg_name == "KeyValuePair" && pair.fields.len() == 2 {
+                        let key = self.collection_item(&pair.fields[0].value);
+                        let value = self.collection_item(&pair.fields[1].value);
+                        return self.language.map(&pair.fields[0].value, key, value);
+                    }
+                }
+                let item_expr = self.collection_item(item);
+                self.language.vector(item, item_expr)
+            }
+            Schema::Array(array) => {
+                let item = self.item(&array.item_type, direct);
+                self.language.array(&array.item_type, item, array.count)
+            }
+            Schema::SchemaOption(item) => {
+                let item = self.type_expr(item, direct);
+                self.language.option(item)
+            }
+            Schema::ZeroSize => self.language.zero_size(),
+            Schema::Boxed(item) => {
+                let item = self.type_expr(item, false);
+                self.language.boxed(item)
+            }
+            Schema::UtcTimestamp => self.language.utc_timestamp(),
+            Schema::Recursion(depth) => self.recursion(*depth),
+            other => self.language.unsupported(&format!(
+                "{} does not support {} schemas",
+                L::GENERATOR,
+                other.top_level_description()
+            )),
+        }
+    }
+
+    fn collection_item(&mut self, item: &'s Schema) -> L::Expr {
+        self.item(item, false)
+    }
+
+    /// The item of a collection or array. These are recursion points, unless the item
+    /// is itself a recursion (the type was already on the stack).
+    fn item(&mut self, item: &'s Schema, direct: bool) -> L::Expr {
+        if let Schema::Recursion(depth) = item {
+            return self.recursion(*depth);
+        }
+        self.recursion_points.push((RecursionPoint::CollectionItem, item));
+        let ret = self.type_expr(item, direct);
+        self.recursion_points.pop();
+        ret
+    }
+
+    /// Resolve a [Schema::Recursion].
+    ///
+    /// Only the items of collections are certain to be recursion points. Boxes are not
+    /// visible in the schema, so named types are only considered if there are too few
+    /// collection items for the given depth.
+    fn recursion(&mut self, depth: usize) -> L::Expr {
+        let collection_items: Vec<&'s Schema> = self
+            .recursion_points
+            .iter()
+            .filter(|(kind, _)| *kind == RecursionPoint::CollectionItem)
+            .map(|(_, schema)| *schema)
+            .collect();
+        let target = if depth > 0 && depth <= collection_items.len() {
+            Some(collection_items[collection_items.len() - depth])
+        } else if depth > 0 && depth <= self.recursion_points.len() {
+            Some(self.recursion_points[self.recursion_points.len() - depth].1)
+        } else {
+            None
+        };
+        match target {
+            Some(target) => {
+                let target = self.type_expr(target, false);
+                self.language.recursion(target)
+            }
+            None => self.language.unsupported(&format!(
+                "{} could not resolve recursion of depth {}",
+                L::GENERATOR,
+                depth
+            )),
+        }
+    }
+
+    /// Return an expression for the type generated for a struct or enum, generating it if needed.
+    fn named_type(&mut self, schema: &'s Schema, direct: bool) -> L::Expr {
+        let dbg_name = match schema {
+            Schema::Struct(schema_struct) => &schema_struct.dbg_name,
+            Schema::Enum(schema_enum) => &schema_enum.dbg_name,
+            _ => unreachable!(),
+        };
+        let fingerprint = schema.fingerprint();
+
+        // A type which contains itself. Schemas of recursive types contain one copy of
+        // the type within itself, which ends with a Schema::Recursion.
+        if let Some(&index) = self.generating.iter().find(|index| {
+            let generated = &self.types[**index];
+            generated.dbg_name == *dbg_name && (generated.fingerprint == fingerprint || contains_recursion(schema))
+        }) {
+            return self.language.recursive_reference(&self.types[index].name, direct);
+        }
+        if let Some(generated) = self
+            .types
+            .iter()
+            .find(|generated| generated.dbg_name == *dbg_name && generated.fingerprint == fingerprint)
+        {
+            return self.language.named(&generated.name);
+        }
+
+        let base_name = self.language.identifier(dbg_name);
+        let mut name = base_name.clone();
+        let mut suffix = 2;
+        while self.types.iter().any(|generated| generated.name == name) {
+            name = format!("{}{}", base_name, suffix);
+            suffix += 1;
+        }
+        self.types.push(GeneratedType {
+            dbg_name: dbg_name.clone(),
+            fingerprint,
+            name: name.clone(),
+        });
+        let definition_index = self.definitions.len();
+        self.definitions.push(String::new());
+        self.generating.push(self.types.len() - 1);
+        // Named types in direct positions may be the content of a Box, which is a recursion point
+        if direct {
+            self.recursion_points.push((RecursionPoint::Named, schema));
+        }
+
+        let definition = match schema {
+            Schema::Struct(schema_struct) => {
+                let fields = self.fields(&schema_struct.fields);
+                self.language.struct_definition(&name, schema_struct, fields)
+            }
+            Schema::Enum(schema_enum) => {
+                let variants = schema_enum
+                    .variants
+                    .iter()
+                    .map(|variant| self.fields(&variant.fields))
+                    .collect();
+                self.language.enum_definition(&name, schema_enum, variants)
+            }
+            _ => unreachable!(),
+        };
+        if direct {
+            self.recursion_points.pop();
+        }
+        self.generating.pop();
+        self.definitions[definition_index] = definition;
+        self.language.named(&name)
+    }
+
+    fn fields(&mut self, fields: &'s [Field]) -> Vec<L::Expr> {
+        fields.iter().map(|field| self.type_expr(&field.value, true)).collect()
+    }
+}
+
+struct RustLanguage<'a> {
+    options: &'a RustCodegenOptions,
+}
+
+impl RustLanguage<'_> {
+    fn derive_line(&self) -> String {
+        let mut derives = vec!["Savefile".to_string()];
+        derives.extend(self.options.derives.iter().cloned());
+        format!("#[derive({})]\n", derives.join(", "))
+    }
+}
+
+impl Language for RustLanguage<'_> {
+    type Expr = String;
+    const GENERATOR: &'static str = "schema_to_rust";
+
+    fn identifier(&self, name: &str) -> String {
+        rust_identifier(name)
+    }
+    fn primitive(&self, primitive: &SchemaPrimitive) -> String {
+        primitive_type(primitive).to_string()
+    }
+    fn vector(&self, _item_schema: &Schema, item: String) -> String {
+        format!("Vec<{}>", item)
+    }
+    fn map(&self, key_schema: &Schema, key: String, value: String) -> String {
+        if is_hashable(key_schema) {
+            format!("std::collections::HashMap<{}, {}>", key, value)
+        } else {
+            format!("Vec<({}, {})>", key, value)
+        }
+    }
+    fn array(&self, _item_schema: &Schema, item: String, count: usize) -> String {
+        format!("[{}; {}]", item, count)
+    }
+    fn option(&self, item: String) -> String {
+        format!("Option<{}>", item)
+    }
+    fn tuple(&self, items: Vec<String>) -> String {
+        if items.len() == 1 {
+            format!("({},)", items[0])
+        } else {
+            format!("({})", items.join(", "))
+        }
+    }
+    fn result(&self, ok: String, err: String) -> String {
+        format!("Result<{}, {}>", ok, err)
+    }
+    fn std_duration(&self) -> String {
+        "std::time::Duration".to_string()
+    }
+    fn zero_size(&self) -> String {
+        "()".to_string()
+    }
+    fn boxed(&self, item: String) -> String {
+        format!("Box<{}>", item)
+    }
+    fn utc_timestamp(&self) -> String {
+        "chrono::DateTime<chrono::Utc>".to_string()
+    }
+    fn unsupported(&self, message: &str) -> String {
+        format!("compile_error!({:?})", message)
+    }
+    fn named(&self, name: &str) -> String {
+        name.to_string()
+    }
+    fn recursive_reference(&self, name: &str, direct: bool) -> String {
+        if direct {
+            format!("Box<{}>", name)
+        } else {
+            name.to_string()
+        }
+    }
+    fn recursion(&self, target: String) -> String {
+        format!("Box<{}>", target)
+    }
+
+    fn struct_definition(&self, name: &str, schema_struct: &SchemaStruct, fields: Vec<String>) -> String {
+        let mut definition = self.derive_line();
+        if is_tuple_like(&schema_struct.fields) && !schema_struct.fields.is_empty() {
+            let fields: Vec<String> = fields.iter().map(|field| format!("pub {}", field)).collect();
+            _ = writeln!(definition, "pub struct {}({});", name, fields.join(", "));
+        } else {
+            _ = writeln!(definition, "pub struct {} {{", name);
+            for (field, field_type) in schema_struct.fields.iter().zip(fields) {
+                _ = writeln!(definition, "    pub {}: {},", rust_identifier(&field.name), field_type);
+            }
+            definition.push_str("}\n");
+        }
+        definition
+    }
+
+    fn enum_definition(&self, name: &str, schema_enum: &SchemaEnum, variants: Vec<Vec<String>>) -> String {
+        let mut definition = self.derive_line();
+        let variant_count = schema_enum
+            .variants
+            .iter()
+            .map(|variant| variant.discriminant as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let default_discriminant_size = if variant_count <= 256 {
+            1
+        } else if variant_count <= 65536 {
+            2
+        } else {
+            4
+        };
+        if schema_enum.has_explicit_repr || schema_enum.discriminant_size != default_discriminant_size {
+            let repr = match schema_enum.discriminant_size {
+                1 => "u8",
+                2 => "u16",
+                _ => "u32",
+            };
+            _ = writeln!(definition, "#[repr({})]", repr);
+        }
+        _ = writeln!(definition, "pub enum {} {{", name);
+        for discriminant in 0..variant_count as u32 {
+            let Some((variant, fields)) = schema_enum
+                .variants
+                .iter()
+                .zip(&variants)
+                .find(|(variant, _)| variant.discriminant == discriminant)
+            else {
+                // A variant which did not exist in this version
+                _ = writeln!(
+                    definition,
+                    "    #[savefile_versions = \"{}..\"]\n    Unused{},",
+                    self.options.version.saturating_add(1),
+                    discriminant
+                );
+                continue;
+            };
+            let name = rust_identifier(&variant.name);
+            if variant.fields.is_empty() {
+                _ = writeln!(definition, "    {},", name);
+            } else if is_tuple_like(&variant.fields) {
+                _ = writeln!(definition, "    {}({}),", name, fields.join(", "));
+            } else {
+                _ = writeln!(definition, "    {} {{", name);
+                for (field, field_type) in variant.fields.iter().zip(fields) {
+                    _ = writeln!(definition, "        {}: {},", rust_identifier(&field.name), field_type);
+                }
+                definition.push_str("    },\n");
+            }
+        }
+        definition.push_str("}\n");
+        definition
+    }
+}
+
+/// A Python expression which reads a value, and the type hint for the value
+struct PythonExpr {
+    read: String,
+    hint: String,
+}
+
+impl PythonExpr {
+    fn new(read: impl Into<String>, hint: impl Into<String>) -> PythonExpr {
+        PythonExpr {
+            read: read.into(),
+            hint: hint.into(),
+        }
+    }
+}
+
+struct PythonLanguage;
+
+impl PythonLanguage {
+    /// Arguments for `_Reader.items`, which read numbers and structs containing only
+    /// numbers in one go, and the type hint of the resulting sequence.
+    fn items_args(&self, item_schema: &Schema, item: &PythonExpr) -> (String, String) {
+        let sequence_hint = format!("typing.Sequence[{}]", item.hint);
+        if let Schema::Primitive(primitive) = item_schema {
+            if let Some((dtype, format)) = python_number_format(primitive) {
+                return (format!("None, \"{}\", \"{}\"", dtype, format), sequence_hint);
+            }
+        }
+        if let Some(dtype) = self.packed_struct_dtype(item_schema) {
+            return (format!("lambda: {}, {}", item.read, dtype), sequence_hint);
+        }
+        (format!("lambda: {}", item.read), format!("typing.List[{}]", item.hint))
+    }
+
+    /// A numpy structured dtype for structs whose fields are all numbers
+    fn packed_struct_dtype(&self, schema: &Schema) -> Option<String> {
+        let Schema::Struct(schema_struct) = schema else {
+            return None;
+        };
+        if schema_struct.fields.is_empty() || tuple_fields(schema_struct).is_some() {
+            return None;
+        }
+        let mut dtype = Vec::new();
+        for field in &schema_struct.fields {
+            let Schema::Primitive(primitive) = &*field.value else {
+                return None;
+            };
+            let (field_dtype, _) = python_number_format(primitive)?;
+            dtype.push(format!("(\"{}\", \"{}\")", self.identifier(&field.name), field_dtype));
+        }
+        Some(format!("[{}]", dtype.join(", ")))
+    }
+
+    fn dataclass(&self, name: &str, base: Option<&str>, fields: &[Field], exprs: &[PythonExpr]) -> String {
+        let mut definition = "@dataclasses.dataclass\n".to_string();
+        match base {
+            Some(base) => _ = writeln!(definition, "class {}({}):", name, base),
+            None => _ = writeln!(definition, "class {}:", name),
+        }
+        if fields.is_empty() {
+            definition.push_str("    pass\n");
+        }
+        for (field, expr) in fields.iter().zip(exprs) {
+            _ = writeln!(definition, "    {}: {}", self.identifier(&field.name), expr.hint);
+        }
+        definition
+    }
+}
+
+/// Call a generated constructor with the given field values, indented by 'indent' spaces
+fn python_constructor(name: &str, exprs: &[PythonExpr], indent: usize) -> String {
+    if exprs.is_empty() {
+        return format!("{}()", name);
+    }
+    let mut call = format!("{}(\n", name);
+    for expr in exprs {
+        _ = writeln!(call, "{:indent$}    {},", "", expr.read, indent = indent);
+    }
+    _ = write!(call, "{:indent$})", "", indent = indent);
+    call
+}
+
+impl Language for PythonLanguage {
+    type Expr = PythonExpr;
+    const GENERATOR: &'static str = "schema_to_python";
+
+    fn identifier(&self, name: &str) -> String {
+        python_identifier(name)
+    }
+    fn primitive(&self, primitive: &SchemaPrimitive) -> PythonExpr {
+        let (method, hint) = python_primitive(primitive);
+        PythonExpr::new(format!("r.{}()", method), hint)
+    }
+    fn vector(&self, item_schema: &Schema, item: PythonExpr) -> PythonExpr {
+        let (args, hint) = self.items_args(item_schema, &item);
+        PythonExpr::new(format!("r.vec({})", args), hint)
+    }
+    fn map(&self, key_schema: &Schema, key: PythonExpr, value: PythonExpr) -> PythonExpr {
+        let hashable = is_hashable(key_schema);
+        PythonExpr::new(
+            format!(
+                "r.map(lambda: {}, lambda: {}, {})",
+                key.read,
+                value.read,
+                if hashable { "True" } else { "False" }
+            ),
+            if hashable {
+                format!("typing.Dict[{}, {}]", key.hint, value.hint)
+            } else {
+                format!("typing.List[typing.Tuple[{}, {}]]", key.hint, value.hint)
+            },
+        )
+    }
+    fn array(&self, item_schema: &Schema, item: PythonExpr, count: usize) -> PythonExpr {
+        let (args, hint) = self.items_args(item_schema, &item);
+        PythonExpr::new(format!("r.items({}, {})", count, args), hint)
+    }
+    fn option(&self, item: PythonExpr) -> PythonExpr {
+        PythonExpr::new(
+            format!("({} if r.bool() else None)", item.read),
+            format!("typing.Optional[{}]", item.hint),
+        )
+    }
+    fn tuple(&self, items: Vec<PythonExpr>) -> PythonExpr {
+        let reads: Vec<&str> = items.iter().map(|item| item.read.as_str()).collect();
+        let hints: Vec<&str> = items.iter().map(|item| item.hint.as_str()).collect();
+        PythonExpr::new(
+            if items.len() == 1 {
+                format!("({},)", reads[0])
+            } else {
+                format!("({})", reads.join(", "))
+            },
+            if items.is_empty() {
+                "typing.Tuple[()]".to_string()
+            } else {
+                format!("typing.Tuple[{}]", hints.join(", "))
+            },
+        )
+    }
+    fn result(&self, ok: PythonExpr, err: PythonExpr) -> PythonExpr {
+        PythonExpr::new(
+            format!("(Ok({}) if r.bool() else Err({}))", ok.read, err.read),
+            "typing.Union[Ok, Err]",
+        )
+    }
+    fn std_duration(&self) -> PythonExpr {
+        PythonExpr::new("r.std_duration()", "datetime.timedelta")
+    }
+    fn zero_size(&self) -> PythonExpr {
+        PythonExpr::new("None", "None")
+    }
+    fn boxed(&self, item: PythonExpr) -> PythonExpr {
+        item
+    }
+    fn utc_timestamp(&self) -> PythonExpr {
+        PythonExpr::new("r.utc_timestamp()", "datetime.datetime")
+    }
+    fn unsupported(&self, message: &str) -> PythonExpr {
+        PythonExpr::new(format!("_unsupported({})", python_string(message)), "typing.Any")
+    }
+    fn named(&self, name: &str) -> PythonExpr {
+        PythonExpr::new(format!("_read_{}(r)", name), name)
+    }
+    fn recursive_reference(&self, name: &str, _direct: bool) -> PythonExpr {
+        self.named(name)
+    }
+    fn recursion(&self, target: PythonExpr) -> PythonExpr {
+        target
+    }
+
+    fn struct_definition(&self, name: &str, schema_struct: &SchemaStruct, fields: Vec<PythonExpr>) -> String {
+        let mut definition = self.dataclass(name, None, &schema_struct.fields, &fields);
+        _ = write!(
+            definition,
+            "\n\ndef _read_{}(r):\n    return {}\n",
+            name,
+            python_constructor(name, &fields, 4)
+        );
+        definition
+    }
+
+    fn enum_definition(&self, name: &str, schema_enum: &SchemaEnum, variants: Vec<Vec<PythonExpr>>) -> String {
+        let mut definition = format!(
+            "class {}:\n    \"\"\"Base class of the variants of the enum {}\"\"\"\n",
+            name, name
+        );
+        let mut reader = format!(
+            "def _read_{}(r):\n    discriminant = r.u{}()\n",
+            name,
+            schema_enum.discriminant_size as usize * 8
+        );
+        for (variant, fields) in schema_enum.variants.iter().zip(&variants) {
+            let variant_name = format!("{}_{}", name, self.identifier(&variant.name));
+            _ = write!(
+                definition,
+                "\n\n{}",
+                self.dataclass(&variant_name, Some(name), &variant.fields, fields)
+            );
+            _ = write!(
+                reader,
+                "    if discriminant == {}:\n        return {}\n",
+                variant.discriminant,
+                python_constructor(&variant_name, fields, 8)
+            );
+        }
+        _ = writeln!(
+            reader,
+            "    raise SavefileError(\"Invalid discriminant %d for enum {}\" % discriminant)",
+            name
+        );
+        _ = write!(definition, "\n\n{}", reader);
+        definition
+    }
+}
+
+/// Fields named 0, 1, 2 ... are the fields of a tuple struct or tuple variant
+fn is_tuple_like(fields: &[Field]) -> bool {
+    fields
+        .iter()
+        .enumerate()
+        .all(|(index, field)| field.name == index.to_string())
+}
+
+/// If the struct is one of the tuples created by [Schema::new_tuple1] etc., return its fields
+fn tuple_fields(schema_struct: &SchemaStruct) -> Option<&[Field]> {
+    let count: usize = schema_struct.dbg_name.strip_suffix("-Tuple")?.parse().ok()?;
+    if count == schema_struct.fields.len() && is_tuple_like(&schema_struct.fields) {
+        Some(&schema_struct.fields)
+    } else {
+        None
+    }
+}
+
+/// If the enum is the schema of a `Result` (see [crate::get_result_schema]), return the
+/// schemas of the ok and error values. Note that the schema of `Result` has always had
+/// the discriminant 0 for both variants.
+fn result_types(schema_enum: &SchemaEnum) -> Option<(&Schema, &Schema)> {
+    match &schema_enum.variants[..] {
+        [ok, err]
+            if schema_enum.dbg_name == "Result"
+                && ok.name == "Ok"
+                && err.name == "Err"
+                && ok.discriminant == 0
+                && err.discriminant <= 1
+                && ok.fields.len() == 1
+                && err.fields.len() == 1 =>
+        {
+            Some((&ok.fields[0].value, &err.fields[0].value))
+        }
+        _ => None,
+    }
+}
+
+fn contains_recursion(schema: &Schema) -> bool {
+    match schema {
+        Schema::Recursion(_) => true,
+        Schema::Struct(schema_struct) => schema_struct
+            .fields
+            .iter()
+            .any(|field| contains_recursion(&field.value)),
+        Schema::Enum(schema_enum) => schema_enum
+            .variants
+            .iter()
+            .any(|variant| variant.fields.iter().any(|field| contains_recursion(&field.value))),
+        Schema::Vector(item, _)
+        | Schema::SchemaOption(item)
+        | Schema::Boxed(item)
+        | Schema::Slice(item)
+        | Schema::Reference(item) => contains_recursion(item),
+        Schema::Array(array) => contains_recursion(&array.item_type),
+        _ => false,
+    }
+}
+
+fn is_hashable(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Primitive(
+            SchemaPrimitive::schema_i8
+                | SchemaPrimitive::schema_u8
+                | SchemaPrimitive::schema_i16
+                | SchemaPrimitive::schema_u16
+                | SchemaPrimitive::schema_i32
+                | SchemaPrimitive::schema_u32
+                | SchemaPrimitive::schema_i64
+                | SchemaPrimitive::schema_u64
+                | SchemaPrimitive::schema_i128
+                | SchemaPrimitive::schema_u128
+                | SchemaPrimitive::schema_string(_)
+                | SchemaPrimitive::schema_bool
+                | SchemaPrimitive::schema_char
+        )
+    )
+}
+
+fn primitive_type(primitive: &SchemaPrimitive) -> &'static str {
+    match primitive {
+        SchemaPrimitive::schema_i8 => "i8",
+        SchemaPrimitive::schema_u8 => "u8",
+        SchemaPrimitive::schema_i16 => "i16",
+        SchemaPrimitive::schema_u16 => "u16",
+        SchemaPrimitive::schema_i32 => "i32",
+        SchemaPrimitive::schema_u32 => "u32",
+        SchemaPrimitive::schema_i64 => "i64",
+        SchemaPrimitive::schema_u64 => "u64",
+        SchemaPrimitive::schema_string(_) => "String",
+        SchemaPrimitive::schema_f32 => "f32",
+        SchemaPrimitive::schema_f64 => "f64",
+        SchemaPrimitive::schema_bool => "bool",
+        SchemaPrimitive::schema_canary1 => "savefile::Canary1",
+        SchemaPrimitive::schema_u128 => "u128",
+        SchemaPrimitive::schema_i128 => "i128",
+        SchemaPrimitive::schema_char => "char",
+        SchemaPrimitive::schema_date => "chrono::NaiveDate",
+        SchemaPrimitive::schema_time => "chrono::NaiveTime",
+        SchemaPrimitive::schema_datetime => "chrono::NaiveDateTime",
+        SchemaPrimitive::schema_datetime_offset => "chrono::DateTime<chrono::FixedOffset>",
+        SchemaPrimitive::schema_duration => "chrono::Duration",
+        SchemaPrimitive::schema_f16 => "half::f16",
+        SchemaPrimitive::schema_bf16 => "half::bf16",
+        SchemaPrimitive::schema_decimal => "rust_decimal::Decimal",
+        SchemaPrimitive::schema_bigint => "num_bigint::BigInt",
+        SchemaPrimitive::schema_biguint => "num_bigint::BigUint",
+        SchemaPrimitive::schema_uuid => "uuid::Uuid",
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct", "trait",
+    "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Turn an arbitrary name into a valid Rust identifier
+fn rust_identifier(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if matches!(identifier.as_str(), "self" | "Self" | "super" | "crate" | "_") {
+        identifier.push('_');
+    } else if RUST_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.insert_str(0, "r#");
+    }
+    identifier
+}
+
+/// The numpy dtype and `struct` module format of numbers, as stored by savefile
+fn python_number_format(primitive: &SchemaPrimitive) -> Option<(&'static str, &'static str)> {
+    Some(match primitive {
+        SchemaPrimitive::schema_i8 => ("<i1", "b"),
+        SchemaPrimitive::schema_u8 => ("<u1", "B"),
+        SchemaPrimitive::schema_i16 => ("<i2", "h"),
+        SchemaPrimitive::schema_u16 => ("<u2", "H"),
+        SchemaPrimitive::schema_i32 => ("<i4", "i"),
+        SchemaPrimitive::schema_u32 => ("<u4", "I"),
+        SchemaPrimitive::schema_i64 => ("<i8", "q"),
+        SchemaPrimitive::schema_u64 => ("<u8", "Q"),
+        SchemaPrimitive::schema_f16 => ("<f2", "e"),
+        SchemaPrimitive::schema_f32 => ("<f4", "f"),
+        SchemaPrimitive::schema_f64 => ("<f8", "d"),
+        SchemaPrimitive::schema_bool => ("?", "?"),
+        _ => return None,
+    })
+}
+
+/// The `_Reader` method which reads the primitive, and its type hint
+fn python_primitive(primitive: &SchemaPrimitive) -> (&'static str, &'static str) {
+    match primitive {
+        SchemaPrimitive::schema_i8 => ("i8", "int"),
+        SchemaPrimitive::schema_u8 => ("u8", "int"),
+        SchemaPrimitive::schema_i16 => ("i16", "int"),
+        SchemaPrimitive::schema_u16 => ("u16", "int"),
+        SchemaPrimitive::schema_i32 => ("i32", "int"),
+        SchemaPrimitive::schema_u32 => ("u32", "int"),
+        SchemaPrimitive::schema_i64 => ("i64", "int"),
+        SchemaPrimitive::schema_u64 => ("u64", "int"),
+        SchemaPrimitive::schema_string(_) => ("string", "str"),
+        SchemaPrimitive::schema_f32 => ("f32", "float"),
+        SchemaPrimitive::schema_f64 => ("f64", "float"),
+        SchemaPrimitive::schema_bool => ("bool", "bool"),
+        SchemaPrimitive::schema_canary1 => ("canary1", "None"),
+        SchemaPrimitive::schema_u128 => ("u128", "int"),
+        SchemaPrimitive::schema_i128 => ("i128", "int"),
+        SchemaPrimitive::schema_char => ("char", "str"),
+        SchemaPrimitive::schema_date => ("date", "datetime.date"),
+        SchemaPrimitive::schema_time => ("time", "datetime.time"),
+        SchemaPrimitive::schema_datetime => ("datetime", "datetime.datetime"),
+        SchemaPrimitive::schema_datetime_offset => ("datetime_offset", "datetime.datetime"),
+        SchemaPrimitive::schema_duration => ("duration", "datetime.timedelta"),
+        SchemaPrimitive::schema_f16 => ("f16", "float"),
+        SchemaPrimitive::schema_bf16 => ("bf16", "float"),
+        SchemaPrimitive::schema_decimal => ("decimal", "decimal.Decimal"),
+        SchemaPrimitive::schema_bigint => ("bigint", "int"),
+        SchemaPrimitive::schema_biguint => ("biguint", "int"),
+        SchemaPrimitive::schema_uuid => ("uuid", "uuid.UUID"),
+    }
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Turn an arbitrary name into a valid Python identifier
+fn python_identifier(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if PYTHON_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Quote a string as a Python string literal
+fn python_string(text: &str) -> String {
+    let mut literal = "\"".to_string();
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            ' '..='~' => literal.push(c),
+            _ => _ = write!(literal, "\\U{:08x}", c as u32),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+const PYTHON_PRELUDE: &str = r#"# Generated by savefile::codegen::schema_to_python
+"""Reader for files saved by savefile.
+
+Use load_file(path) or loads(data) to read a file. The file must have been saved
+with the schema this module was generated from. This is checked using the schema
+fingerprint, except for files written by savefile format versions before 3, which
+don't have one.
+
+If numpy is installed, vectors and arrays of numbers are decoded as numpy arrays,
+and vectors of structs containing only numbers as numpy structured arrays.
+"""
+
+from __future__ import annotations
+
+import bz2
+import dataclasses
+import datetime
+import decimal
+import struct
+import typing
+import uuid
+
+try:
+    import numpy
+except ImportError:
+    numpy = None
+"#;
+
+const PYTHON_RUNTIME: &str = r#"# The newest savefile file format this module can read
+MAX_LIB_VERSION = 3
+
+
+class SavefileError(Exception):
+    """The file could not be decoded"""
+
+
+@dataclasses.dataclass
+class Ok:
+    value: typing.Any
+
+
+@dataclasses.dataclass
+class Err:
+    value: typing.Any
+
+
+def load_file(path, embedded_schema=True):
+    """Read a file saved by savefile"""
+    with open(path, "rb") as file:
+        return loads(file.read(), embedded_schema)
+
+
+def loads(data, embedded_schema=True):
+    """Decode the contents of a file saved by savefile.
+
+    Set embedded_schema to False for files saved without a schema, such as by save_noschema.
+    This is only needed for files older than savefile format version 3, newer files record
+    whether they have a schema.
+    """
+    try:
+        return _loads(data, embedded_schema)
+    except struct.error as err:
+        raise SavefileError("Unexpected end of file") from err
+
+
+def _loads(data, embedded_schema):
+    r = _Reader(data)
+    if r.read(9) != b"savefile\0":
+        raise SavefileError("Not a savefile file")
+    lib_version = r.u16()
+    if lib_version > MAX_LIB_VERSION:
+        raise SavefileError("File has savefile format version %d, newer than %d" % (lib_version, MAX_LIB_VERSION))
+    r.u32()  # The data version. Different versions have different schemas.
+    flags = r.u8()
+    if lib_version >= 3:
+        embedded_schema = flags & 8 != 0
+    compressed = flags & 1 != 0
+    schema_by_reference = flags & 2 != 0
+    if (embedded_schema or schema_by_reference) and lib_version >= 3:
+        fingerprint = r.u128()
+        if fingerprint != SCHEMA_FINGERPRINT:
+            raise SavefileError(
+                "File has schema fingerprint %032x, expected %032x" % (fingerprint, SCHEMA_FINGERPRINT)
+            )
+    if flags & 4 != 0:
+        r.read(r.u64())  # Metadata, see save_with_metadata
+    if compressed:
+        r = _Reader(bz2.decompress(r.data[r.pos:]))
+    if embedded_schema and not schema_by_reference:
+        if lib_version >= 3:
+            r.read(r.u64())
+        else:
+            _skip_schema(r, lib_version)
+    return _read_root(r)
+
+
+def _skip_schema(r, lib_version):
+    """Skip a schema in a file older than savefile format version 3, which has no schema length."""
+    tag = r.u8()
+    if tag == 1:  # Struct
+        r.read(r.u64())
+        count = r.u64()
+        if lib_version > 0:
+            _skip_option_u64(r)
+            _skip_option_u64(r)
+        for _ in range(count):
+            _skip_field(r, lib_version)
+    elif tag == 2:  # Enum
+        r.read(r.u64())
+        for _ in range(r.u64()):
+            r.read(r.u64())
+            r.read(4 if lib_version >= 3 else 1)
+            for _ in range(r.u64()):
+                _skip_field(r, lib_version)
+        if lib_version > 0:
+            r.read(2)
+            _skip_option_u64(r)
+            _skip_option_u64(r)
+    elif tag == 3:  # Primitive, strings also have a memory layout
+        if r.u8() == 9 and lib_version > 0:
+            r.u8()
+    elif tag == 4:  # Vector
+        _skip_schema(r, lib_version)
+        if lib_version > 0:
+            r.u8()
+    elif tag in (5, 6, 13, 17, 19, 20):
+        pass
+    elif tag in (7, 12, 14):
+        _skip_schema(r, lib_version)
+    elif tag == 8:  # Array
+        r.u64()
+        _skip_schema(r, lib_version)
+    elif tag == 9:  # Custom
+        r.read(r.u64())
+    elif tag == 16:  # Recursion
+        r.u64()
+    else:
+        raise SavefileError("Unsupported schema variant %d" % tag)
+
+
+def _skip_field(r, lib_version):
+    r.read(r.u64())
+    _skip_schema(r, lib_version)
+    if lib_version > 0:
+        _skip_option_u64(r)
+
+
+def _skip_option_u64(r):
+    if r.u8() != 0:
+        r.u64()
+
+
+def _unsupported(message):
+    raise SavefileError(message)
+
+
+_EPOCH = datetime.datetime(1970, 1, 1)
+_UTC_EPOCH = datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def _timedelta(seconds, nanos):
+    # Python datetimes have microsecond precision
+    return datetime.timedelta(seconds=seconds, microseconds=min(nanos // 1000, 999999))
+
+
+class _Reader:
+    _formats = {format: struct.Struct("<" + format) for format in "bBhHiIqQefd"}
+
+    def __init__(self, data):
+        self.data = memoryview(data)
+        self.pos = 0
+
+    def read(self, count):
+        end = self.pos + count
+        if end > len(self.data):
+            raise SavefileError("Unexpected end of file")
+        ret = self.data[self.pos:end]
+        self.pos = end
+        return ret
+
+    def _unpack(self, format):
+        packer = self._formats[format]
+        ret = packer.unpack_from(self.data, self.pos)[0]
+        self.pos += packer.size
+        return ret
+
+    def i8(self):
+        return self._unpack("b")
+
+    def u8(self):
+        return self._unpack("B")
+
+    def i16(self):
+        return self._unpack("h")
+
+    def u16(self):
+        return self._unpack("H")
+
+    def i32(self):
+        return self._unpack("i")
+
+    def u32(self):
+        return self._unpack("I")
+
+    def i64(self):
+        return self._unpack("q")
+
+    def u64(self):
+        return self._unpack("Q")
+
+    def i128(self):
+        return int.from_bytes(self.read(16), "little", signed=True)
+
+    def u128(self):
+        return int.from_bytes(self.read(16), "little")
+
+    def f16(self):
+        return self._unpack("e")
+
+    def bf16(self):
+        return struct.unpack("<f", struct.pack("<I", self.u16() << 16))[0]
+
+    def f32(self):
+        return self._unpack("f")
+
+    def f64(self):
+        return self._unpack("d")
+
+    def bool(self):
+        return self.u8() != 0
+
+    def char(self):
+        return chr(self.u32())
+
+    def string(self):
+        return str(self.read(self.u64()), "utf-8")
+
+    def canary1(self):
+        if self.u32() != 0x47566843:
+            raise SavefileError("Corrupt file, invalid canary")
+        return None
+
+    def date(self):
+        return _EPOCH.date() + datetime.timedelta(days=self.i32())
+
+    def time(self):
+        seconds = self.u32()
+        return (_EPOCH + _timedelta(seconds, self.u32())).time()
+
+    def datetime(self):
+        seconds = self.i64()
+        return _EPOCH + _timedelta(seconds, self.u32())
+
+    def datetime_offset(self):
+        seconds = self.i64()
+        utc = _UTC_EPOCH + _timedelta(seconds, self.u32())
+        return utc.astimezone(datetime.timezone(datetime.timedelta(seconds=self.i32())))
+
+    def duration(self):
+        seconds = self.i64()
+        return _timedelta(seconds, self.u32())
+
+    def std_duration(self):
+        return datetime.timedelta(microseconds=self.u128() // 1000)
+
+    def utc_timestamp(self):
+        return _UTC_EPOCH + datetime.timedelta(microseconds=self.i64() // 1000)
+
+    def decimal(self):
+        mantissa = self.i128()
+        return decimal.Decimal("%de-%d" % (mantissa, self.u8()))
+
+    def bigint(self):
+        return int.from_bytes(self.read(self.u64()), "little", signed=True)
+
+    def biguint(self):
+        return int.from_bytes(self.read(self.u64()), "little")
+
+    def uuid(self):
+        return uuid.UUID(bytes=bytes(self.read(16)))
+
+    def items(self, count, read_item, dtype=None, format=None):
+        """Read 'count' items. Items with a numpy dtype or struct format are read in one go."""
+        if dtype is not None and numpy is not None:
+            dtype = numpy.dtype(dtype)
+            return numpy.frombuffer(self.read(dtype.itemsize * count), dtype=dtype).copy()
+        if format is not None:
+            packer = struct.Struct("<%d%s" % (count, format))
+            ret = list(packer.unpack_from(self.data, self.pos))
+            self.pos += packer.size
+            return ret
+        return [read_item() for _ in range(count)]
+
+    def vec(self, read_item, dtype=None, format=None):
+        return self.items(self.u64(), read_item, dtype, format)
+
+    def map(self, read_key, read_value, hashable):
+        pairs = [(read_key(), read_value()) for _ in range(self.u64())]
+        return dict(pairs) if hashable else pairs
+"#;
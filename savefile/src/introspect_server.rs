@@ -0,0 +1,562 @@
+//! A server which makes the live state of a running program available for introspection,
+//! and a client for connecting to it.
+//!
+//! The server listens on a local TCP port or a Unix domain socket. Each connected client
+//! gets its own [Introspector], and sends [IntrospectorNavCommand]s. For each command, the
+//! server locks the root object, runs the command and sends back the resulting
+//! [IntrospectionResult] (or [IntrospectionError]). The lock is only held while a single
+//! command is being processed.
+//!
+//! Messages are encoded using savefile. Each command is preceded by its length, as a
+//! little endian u32, and may be at most [MAX_REQUEST_SIZE] bytes. The `savefile-introspect`
+//! binary is an interactive client.
+//!
+//! This module requires the `introspect-server` feature.
+//!
+//! The server does not authenticate clients. It only accepts loopback TCP addresses, but
+//! any local user can connect to it.
+//!
+//! ```no_run
+//! use savefile::introspect_server::IntrospectServer;
+//! use std::sync::{Arc, Mutex};
+//!
+//! let state = Arc::new(Mutex::new(vec![1u32, 2, 3]));
+//! let server = IntrospectServer::bind_tcp("127.0.0.1:7777", state.clone()).unwrap();
+//! server.spawn();
+//! // Keep using 'state' as normal. Connect using 'savefile-introspect 127.0.0.1:7777'
+//! ```
+
+use crate::{
+    Deserialize, Deserializer, Field, Introspect, IntrospectedElement, IntrospectedElementKey, IntrospectionError,
+    IntrospectionFrame, IntrospectionResult, Introspector, IntrospectorNavCommand, Packed, SavefileError, Schema,
+    SchemaEnum, SchemaPrimitive, SchemaStruct, Serialize, Serializer, Variant, VecOrStringLayout, WithSchema,
+    WithSchemaContext,
+};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Version of the protocol between [IntrospectServer] and [IntrospectClient].
+/// It is checked for every message.
+pub const PROTOCOL_VERSION: u32 = 0;
+
+/// The largest command, in bytes, accepted by an [IntrospectServer]. Clients sending
+/// larger commands are disconnected.
+pub const MAX_REQUEST_SIZE: usize = 16 * 1024;
+
+/// The longest key, in bytes, accepted in an [IntrospectorNavCommand::ExpandElement] command
+pub const MAX_KEY_LENGTH: usize = 4096;
+
+/// The object being introspected by an [IntrospectServer]
+pub type IntrospectRoot = Arc<Mutex<dyn Introspect + Send>>;
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn try_clone(&self) -> std::io::Result<Stream> {
+        match self {
+            Stream::Tcp(stream) => Ok(Stream::Tcp(stream.try_clone()?)),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Ok(Stream::Unix(stream.try_clone()?)),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Serves an introspectable object to [IntrospectClient]s.
+///
+/// Each client is handled by a separate thread.
+pub struct IntrospectServer {
+    listener: Listener,
+    root: IntrospectRoot,
+    child_load_count: usize,
+}
+
+impl IntrospectServer {
+    /// Listen for clients on the given TCP address, which must be a loopback address
+    /// (like 127.0.0.1). Use port 0 to get any free port, see [IntrospectServer::local_addr].
+    pub fn bind_tcp(addr: impl ToSocketAddrs, root: IntrospectRoot) -> Result<IntrospectServer, SavefileError> {
+        // Check before binding, so that the port is never open on other interfaces
+        let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+        if let Some(addr) = addrs.iter().find(|addr| !addr.ip().is_loopback()) {
+            return Err(SavefileError::GeneralError {
+                msg: format!(
+                    "The introspection server only listens on loopback addresses, not {}",
+                    addr
+                ),
+            });
+        }
+        let listener = TcpListener::bind(&addrs[..])?;
+        Ok(IntrospectServer {
+            listener: Listener::Tcp(listener),
+            root,
+            child_load_count: usize::MAX,
+        })
+    }
+
+    /// Listen for clients on a Unix domain socket at the given path.
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl AsRef<Path>, root: IntrospectRoot) -> Result<IntrospectServer, SavefileError> {
+        Ok(IntrospectServer {
+            listener: Listener::Unix(UnixListener::bind(path)?),
+            root,
+            child_load_count: usize::MAX,
+        })
+    }
+
+    /// Limit the number of children returned per level. See [Introspector::new_with].
+    /// Clients can use [IntrospectorNavCommand::Page] to see the rest.
+    pub fn with_child_load_count(mut self, child_load_count: usize) -> IntrospectServer {
+        self.child_load_count = child_load_count;
+        self
+    }
+
+    /// The address the server is listening on, if it is a TCP server.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    /// Accept clients, until an error occurs. This blocks the calling thread.
+    pub fn run(self) -> Result<(), SavefileError> {
+        loop {
+            let stream = match &self.listener {
+                Listener::Tcp(listener) => Stream::Tcp(listener.accept()?.0),
+                #[cfg(unix)]
+                Listener::Unix(listener) => Stream::Unix(listener.accept()?.0),
+            };
+            let root = self.root.clone();
+            let introspector = Introspector::new_with(self.child_load_count);
+            std::thread::spawn(move || {
+                // Errors affect only this client, and there is nobody to report them to.
+                _ = serve_client(stream, root, introspector);
+            });
+        }
+    }
+
+    /// Run the server in a background thread
+    pub fn spawn(self) -> JoinHandle<Result<(), SavefileError>> {
+        std::thread::spawn(move || self.run())
+    }
+}
+
+fn is_disconnect(err: &SavefileError) -> bool {
+    match err {
+        SavefileError::IOError { io_error } => matches!(
+            io_error.kind(),
+            ErrorKind::UnexpectedEof | ErrorKind::ConnectionReset | ErrorKind::BrokenPipe
+        ),
+        SavefileError::ShortRead => true,
+        _ => false,
+    }
+}
+
+fn read_request(reader: &mut impl Read) -> Result<IntrospectorNavCommand, SavefileError> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_REQUEST_SIZE {
+        return Err(SavefileError::GeneralError {
+            msg: format!("Introspection command too large: {} bytes", len),
+        });
+    }
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+    Deserializer::load_noschema(&mut &buf[..], PROTOCOL_VERSION)
+}
+
+fn write_request(writer: &mut impl Write, command: &IntrospectorNavCommand) -> Result<(), SavefileError> {
+    let mut buf = Vec::new();
+    Serializer::save_noschema(&mut buf, PROTOCOL_VERSION, command)?;
+    if buf.len() > MAX_REQUEST_SIZE {
+        return Err(SavefileError::GeneralError {
+            msg: format!("Introspection command too large: {} bytes", buf.len()),
+        });
+    }
+    writer.write_all(&(buf.len() as u32).to_le_bytes())?;
+    writer.write_all(&buf)?;
+    Ok(())
+}
+
+fn serve_client(stream: Stream, root: IntrospectRoot, mut introspector: Introspector) -> Result<(), SavefileError> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+    loop {
+        let command = match read_request(&mut reader) {
+            Ok(command) => command,
+            Err(err) if is_disconnect(&err) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        let result = {
+            // A poisoned lock still contains state worth looking at
+            let root = root.lock().unwrap_or_else(|err| err.into_inner());
+            introspector.do_introspect(&*root, command)
+        };
+        Serializer::save_noschema(&mut writer, PROTOCOL_VERSION, &result)?;
+        writer.flush()?;
+    }
+}
+
+/// A connection to an [IntrospectServer]
+pub struct IntrospectClient {
+    reader: BufReader<Stream>,
+    writer: BufWriter<Stream>,
+}
+
+impl IntrospectClient {
+    /// Connect to a server listening on the given TCP address
+    pub fn connect_tcp(addr: impl ToSocketAddrs) -> Result<IntrospectClient, SavefileError> {
+        Self::new(Stream::Tcp(TcpStream::connect(addr)?))
+    }
+
+    /// Connect to a server listening on the Unix domain socket at the given path
+    #[cfg(unix)]
+    pub fn connect_unix(path: impl AsRef<Path>) -> Result<IntrospectClient, SavefileError> {
+        Self::new(Stream::Unix(UnixStream::connect(path)?))
+    }
+
+    fn new(stream: Stream) -> Result<IntrospectClient, SavefileError> {
+        Ok(IntrospectClient {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    /// Send a navigation command to the server, and wait for the result.
+    ///
+    /// The outer error is for communication failures, the inner error is the
+    /// result of the navigation.
+    pub fn navigate(
+        &mut self,
+        command: IntrospectorNavCommand,
+    ) -> Result<Result<IntrospectionResult, IntrospectionError>, SavefileError> {
+        write_request(&mut self.writer, &command)?;
+        self.writer.flush()?;
+        Deserializer::load_noschema(&mut self.reader, PROTOCOL_VERSION)
+    }
+}
+
+fn struct_schema(name: &str, fields: Vec<(&str, Schema)>) -> Schema {
+    Schema::Struct(SchemaStruct {
+        dbg_name: name.to_string(),
+        size: None,
+        alignment: None,
+        fields: fields
+            .into_iter()
+            .map(|(name, schema)| Field::new(name.to_string(), Box::new(schema)))
+            .collect(),
+    })
+}
+
+fn enum_schema(name: &str, variants: Vec<(&str, Vec<(&str, Schema)>)>) -> Schema {
+    Schema::Enum(SchemaEnum {
+        dbg_name: name.to_string(),
+        size: None,
+        alignment: None,
+        variants: variants
+            .into_iter()
+            .enumerate()
+            .map(|(discriminant, (name, fields))| Variant {
+                name: name.to_string(),
+                discriminant: discriminant as u32,
+                fields: fields
+                    .into_iter()
+                    .map(|(name, schema)| Field::new(name.to_string(), Box::new(schema)))
+                    .collect(),
+            })
+            .collect(),
+        discriminant_size: 1,
+        has_explicit_repr: false,
+    })
+}
+
+fn string_schema() -> Schema {
+    Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+}
+
+impl WithSchema for IntrospectedElementKey {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        struct_schema(
+            "IntrospectedElementKey",
+            vec![
+                ("depth", Schema::Primitive(SchemaPrimitive::schema_u64)),
+                ("key", string_schema()),
+                ("key_disambiguator", Schema::Primitive(SchemaPrimitive::schema_u64)),
+            ],
+        )
+    }
+}
+impl Packed for IntrospectedElementKey {}
+impl Serialize for IntrospectedElementKey {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.depth)?;
+        serializer.write_string(&self.key)?;
+        serializer.write_usize(self.key_disambiguator)
+    }
+}
+impl Deserialize for IntrospectedElementKey {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let depth = deserializer.read_usize()?;
+        // Checked before reading, since the length comes from an untrusted client
+        let key_length = deserializer.read_usize()?;
+        if key_length > MAX_KEY_LENGTH {
+            return Err(SavefileError::GeneralError {
+                msg: format!("Introspection key too long: {} bytes", key_length),
+            });
+        }
+        let key = String::from_utf8(deserializer.read_bytes(key_length)?)?;
+        Ok(IntrospectedElementKey {
+            depth,
+            key,
+            key_disambiguator: deserializer.read_usize()?,
+        })
+    }
+}
+
+impl WithSchema for IntrospectedElement {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        struct_schema(
+            "IntrospectedElement",
+            vec![
+                ("key", IntrospectedElementKey::schema(version, context)),
+                ("value", string_schema()),
+                ("has_children", Schema::Primitive(SchemaPrimitive::schema_bool)),
+                ("selected", Schema::Primitive(SchemaPrimitive::schema_bool)),
+            ],
+        )
+    }
+}
+impl Packed for IntrospectedElement {}
+impl Serialize for IntrospectedElement {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.key.serialize(serializer)?;
+        serializer.write_string(&self.value)?;
+        serializer.write_bool(self.has_children)?;
+        serializer.write_bool(self.selected)
+    }
+}
+impl Deserialize for IntrospectedElement {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(IntrospectedElement {
+            key: IntrospectedElementKey::deserialize(deserializer)?,
+            value: deserializer.read_string()?,
+            has_children: deserializer.read_bool()?,
+            selected: deserializer.read_bool()?,
+        })
+    }
+}
+
+impl WithSchema for IntrospectionFrame {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        struct_schema(
+            "IntrospectionFrame",
+            vec![
+                ("selected", Option::<usize>::schema(version, context)),
+                ("keyvals", Vec::<IntrospectedElement>::schema(version, context)),
+                ("offset", Schema::Primitive(SchemaPrimitive::schema_u64)),
+                ("limit_reached", Schema::Primitive(SchemaPrimitive::schema_bool)),
+            ],
+        )
+    }
+}
+impl Packed for IntrospectionFrame {}
+impl Serialize for IntrospectionFrame {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.selected.serialize(serializer)?;
+        self.keyvals.serialize(serializer)?;
+        serializer.write_usize(self.offset)?;
+        serializer.write_bool(self.limit_reached)
+    }
+}
+impl Deserialize for IntrospectionFrame {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(IntrospectionFrame {
+            selected: Option::deserialize(deserializer)?,
+            keyvals: Vec::deserialize(deserializer)?,
+            offset: deserializer.read_usize()?,
+            limit_reached: deserializer.read_bool()?,
+        })
+    }
+}
+
+impl WithSchema for IntrospectionResult {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        struct_schema(
+            "IntrospectionResult",
+            vec![("frames", Vec::<IntrospectionFrame>::schema(version, context))],
+        )
+    }
+}
+impl Packed for IntrospectionResult {}
+impl Serialize for IntrospectionResult {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.frames.serialize(serializer)
+    }
+}
+impl Deserialize for IntrospectionResult {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let frames: Vec<IntrospectionFrame> = Vec::deserialize(deserializer)?;
+        let cached_total_len = frames.iter().map(|frame| frame.keyvals.len()).sum();
+        Ok(IntrospectionResult {
+            frames,
+            cached_total_len,
+        })
+    }
+}
+
+impl WithSchema for IntrospectorNavCommand {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        let u64_schema = || Schema::Primitive(SchemaPrimitive::schema_u64);
+        enum_schema(
+            "IntrospectorNavCommand",
+            vec![
+                (
+                    "ExpandElement",
+                    vec![("0", IntrospectedElementKey::schema(version, context))],
+                ),
+                (
+                    "SelectNth",
+                    vec![("select_depth", u64_schema()), ("select_index", u64_schema())],
+                ),
+                ("Nothing", vec![]),
+                ("Up", vec![]),
+                ("Page", vec![("depth", u64_schema()), ("offset", u64_schema())]),
+            ],
+        )
+    }
+}
+impl Packed for IntrospectorNavCommand {}
+impl Serialize for IntrospectorNavCommand {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        match self {
+            IntrospectorNavCommand::ExpandElement(key) => {
+                serializer.write_u8(0)?;
+                key.serialize(serializer)
+            }
+            IntrospectorNavCommand::SelectNth {
+                select_depth,
+                select_index,
+            } => {
+                serializer.write_u8(1)?;
+                serializer.write_usize(*select_depth)?;
+                serializer.write_usize(*select_index)
+            }
+            IntrospectorNavCommand::Nothing => serializer.write_u8(2),
+            IntrospectorNavCommand::Up => serializer.write_u8(3),
+            IntrospectorNavCommand::Page { depth, offset } => {
+                serializer.write_u8(4)?;
+                serializer.write_usize(*depth)?;
+                serializer.write_usize(*offset)
+            }
+        }
+    }
+}
+impl Deserialize for IntrospectorNavCommand {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(match deserializer.read_u8()? {
+            0 => IntrospectorNavCommand::ExpandElement(IntrospectedElementKey::deserialize(deserializer)?),
+            1 => IntrospectorNavCommand::SelectNth {
+                select_depth: deserializer.read_usize()?,
+                select_index: deserializer.read_usize()?,
+            },
+            2 => IntrospectorNavCommand::Nothing,
+            3 => IntrospectorNavCommand::Up,
+            4 => IntrospectorNavCommand::Page {
+                depth: deserializer.read_usize()?,
+                offset: deserializer.read_usize()?,
+            },
+            discriminant => {
+                return Err(SavefileError::GeneralError {
+                    msg: format!("Corrupt IntrospectorNavCommand, discriminant {}", discriminant),
+                })
+            }
+        })
+    }
+}
+
+const INTROSPECTION_ERRORS: [(&str, IntrospectionError); 9] = [
+    ("BadDepth", IntrospectionError::BadDepth),
+    ("UnknownKey", IntrospectionError::UnknownKey),
+    ("NoChildren", IntrospectionError::NoChildren),
+    ("IndexOutOfRange", IntrospectionError::IndexOutOfRange),
+    ("AlreadyAtTop", IntrospectionError::AlreadyAtTop),
+    ("ReadOnly", IntrospectionError::ReadOnly),
+    ("InvalidValue", IntrospectionError::InvalidValue),
+    ("NothingSelected", IntrospectionError::NothingSelected),
+    ("InvalidQuery", IntrospectionError::InvalidQuery),
+];
+
+impl WithSchema for IntrospectionError {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        enum_schema(
+            "IntrospectionError",
+            INTROSPECTION_ERRORS.iter().map(|(name, _)| (*name, vec![])).collect(),
+        )
+    }
+}
+impl Packed for IntrospectionError {}
+impl Serialize for IntrospectionError {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let discriminant = INTROSPECTION_ERRORS
+            .iter()
+            .position(|(_, err)| err == self)
+            .expect("all IntrospectionError variants are listed in INTROSPECTION_ERRORS");
+        serializer.write_u8(discriminant as u8)
+    }
+}
+impl Deserialize for IntrospectionError {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let discriminant = deserializer.read_u8()?;
+        match INTROSPECTION_ERRORS.get(discriminant as usize) {
+            Some((_, err)) => Ok(*err),
+            None => Err(SavefileError::GeneralError {
+                msg: format!("Corrupt IntrospectionError, discriminant {}", discriminant),
+            }),
+        }
+    }
+}
@@ -413,6 +413,89 @@ struct Racecar {
 Note that in this case we don't need to tell Savefile how the deserialized u8 is to be converted
 to an u16.
 
+Simple lossless widenings like this one are in fact handled automatically, even without the
+savefile_versions_as attribute and without increasing the version number. When the file schema
+has a narrower type than the in-memory schema, `load` converts unsigned integers to wider unsigned
+integers, signed integers to wider signed integers and f32 to f64. `Vec<T>` and `VecDeque<T>` have the
+same on-disk format, and can be freely exchanged. Use `load_strict` or `load_file_strict` to
+instead get an error whenever the schemas differ.
+
+## The savefile_remote attribute
+
+Types from other crates can't implement the savefile traits, unless the crate in question
+supports savefile. To still be able to save such types, declare a local 'mirror' type with the same
+fields, and annotate it with the path of the foreign type:
+
+```
+# use savefile::prelude::*;
+mod other_crate {
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+#[derive(Savefile)]
+#[savefile_remote="other_crate::Point"]
+struct PointDef {
+    x: i32,
+    #[savefile_versions="1.."]
+    y: i32,
+}
+
+#[derive(Savefile)]
+struct Shape {
+    corners: Vec<Remote<other_crate::Point, PointDef>>,
+}
+# fn main() {}
+```
+
+The mirror type itself does not implement the savefile traits. Instead, the derive implements
+[RemoteMirror] for it, and the foreign type is saved by wrapping it in [Remote]. All the regular
+field attributes, like savefile_versions, can be used on the mirror. The fields of the foreign
+type must be visible from the mirror. Fields which are only present in old versions can
+use the `Removed` type, and do not need to exist in the foreign type.
+
+## The savefile_post_load and savefile_pre_save attributes
+
+Fields with the savefile_ignore attribute often hold data derived from the other fields,
+like caches or lookup tables. These can be rebuilt automatically after loading, using
+the `savefile_post_load` attribute:
+
+```
+# use savefile::prelude::*;
+# use std::collections::HashMap;
+#[derive(Savefile)]
+#[savefile_post_load="rebuild_index"]
+struct Phonebook {
+    names: Vec<String>,
+    #[savefile_ignore]
+    #[savefile_introspect_ignore]
+    index: HashMap<String, usize>,
+}
+
+impl Phonebook {
+    fn rebuild_index(&mut self) -> Result<(), SavefileError> {
+        self.index.clear();
+        for (i, name) in self.names.iter().enumerate() {
+            if self.index.insert(name.clone(), i).is_some() {
+                return Err(SavefileError::ValidationFailed { msg: format!("Duplicate name {}", name) });
+            }
+        }
+        Ok(())
+    }
+}
+# fn main() {}
+```
+
+The function is given a mutable reference to the freshly loaded value. It can return `()`,
+or a `Result` (see [HookResult]), in which case an error makes the load fail.
+Similarly, the `savefile_pre_save` attribute gives a function which is called with a shared
+reference before each value is saved. A single identifier is taken to be a method of the type,
+otherwise the path to a free function can be given.
+
+Types with either of these attributes never use the `Packed` optimization.
+
 
 
 # Speeding things up
@@ -764,6 +847,10 @@ generated [crate::Introspect::introspect_value] return the string representation
 This can be useful, to have the primary key (name) of an object more prominently visible in the
 introspection output.
 
+Values are also available in typed form, using [crate::Introspect::introspect_typed_value]. This
+returns an [crate::IntrospectValue], so that numbers, timestamps and byte strings can be handled
+without parsing strings. Types without a value of their own, like collections, return `IntrospectValue::None`.
+
 Example:
 
 ````rust
@@ -850,6 +937,51 @@ Every time [crate::Introspector::do_introspect] is called, a [crate::Introspecto
 which can traverse the tree downward or upward. In the example in the previous chapter,
 SelectNth is used to select the 2nd children at the 0th level in the tree.
 
+Large collections can be browsed by creating the Introspector using [crate::Introspector::new_with],
+to limit the number of children loaded per level, and then using [crate::IntrospectorNavCommand::Page]
+to load children starting at any index.
+
+[crate::Introspector::query] can be used to find elements deep inside an object directly,
+using a path like `players[name=Bob].inventory[*].name`.
+
+[crate::introspect_diff] compares two introspectable objects, and lists every element which
+was changed, added or removed.
+
+[crate::introspect_to_json] and [crate::introspect_to_text] write the whole introspection tree
+of an object as JSON or as indented text, for example to attach to a bug report.
+
+The `introspect_server` module, enabled by the `introspect-server` feature, can serve the state
+of a running program over a local socket, so that it can be browsed using the `savefile-introspect`
+command line tool.
+
+## Modifying values
+
+The trait [crate::IntrospectMut] allows modifying an introspected object. It is implemented
+by `#[derive(Savefile)]`, and for primitives, strings, `Option`, `Box`, `Vec`, `VecDeque` and arrays.
+Enums can be set to one of their unit variants, given by name.
+
+[crate::Introspector::set_selected_value] sets the value of the element most recently selected
+using the Introspector, by parsing a string.
+
+```
+extern crate savefile;
+extern crate savefile_derive;
+use savefile::prelude::*;
+use savefile_derive::Savefile;
+
+#[derive(Savefile)]
+struct Settings {
+    volume: u8,
+}
+
+let mut settings = Settings { volume: 3 };
+let mut introspector = Introspector::new();
+introspector.do_introspect(&settings,
+    IntrospectorNavCommand::SelectNth{select_depth:0, select_index: 0}).unwrap();
+introspector.set_selected_value(&mut settings, "11").unwrap();
+assert_eq!(settings.volume, 11);
+```
+
 
 # Troubleshooting
 
@@ -870,6 +1002,10 @@ through simple copying of bytes.
 /// The prelude contains all definitions thought to be needed by typical users of the library
 pub mod prelude;
 
+pub mod codegen;
+#[cfg(feature = "introspect-server")]
+pub mod introspect_server;
+
 #[cfg(feature = "serde_derive")]
 extern crate serde;
 #[cfg(feature = "serde_derive")]
@@ -906,6 +1042,7 @@ use std::sync::atomic::{
 
 pub use ::byteorder::LittleEndian;
 use std::collections::BinaryHeap;
+use std::collections::LinkedList;
 use std::collections::VecDeque;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
@@ -942,7 +1079,12 @@ pub extern crate savefile_derive;
 /// There is an ambition that savefiles created by earlier versions
 /// will be possible to open using later versions. The other way
 /// around is not supported.
-pub const CURRENT_SAVEFILE_LIB_VERSION: u16 = 2;
+///
+/// Version 3 stores enum variant discriminants as u32 instead of u8, and the fingerprint
+/// of the schema in the header, with the size of the schema before it. The header also
+/// records whether the file has a schema, and may contain user metadata
+/// (see [crate::save_with_metadata]).
+pub const CURRENT_SAVEFILE_LIB_VERSION: u16 = 3;
 
 /// This object represents an error in deserializing or serializing
 /// an item.
@@ -1034,6 +1176,18 @@ pub enum SavefileError {
     },
     /// A timestamp was encountered which is out of range for the Savefile timestamp type
     TimestampOutOfRange,
+    /// A date, time or duration was encountered which is out of range for the
+    /// chrono or time type being saved or loaded.
+    DateTimeOutOfRange {
+        /// The name of the type
+        type_name: &'static str,
+    },
+    /// Loaded data was rejected by a savefile_post_load hook, or data to be saved
+    /// was rejected by a savefile_pre_save hook.
+    ValidationFailed {
+        /// Descriptive message
+        msg: String,
+    },
 }
 impl From<Utf8Error> for SavefileError {
     fn from(value: Utf8Error) -> Self {
@@ -1109,6 +1263,12 @@ impl Display for SavefileError {
             SavefileError::TimestampOutOfRange => {
                 write!(f, "A timestamp value outside the range 1677-09-21T00:12:43.145224192 .. 2262-04-11T23:47:16.854775807 was encountered.")
             }
+            SavefileError::DateTimeOutOfRange { type_name } => {
+                write!(f, "A value outside the range of {} was encountered.", type_name)
+            }
+            SavefileError::ValidationFailed { msg } => {
+                write!(f, "Validation failed: {}", msg)
+            }
         }
     }
 }
@@ -1488,6 +1648,165 @@ impl Deserialize for SocketAddr {
     }
 }
 
+impl WithSchema for Ipv4Addr {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "Ipv4Addr".to_string(),
+            vec![Field::new(
+                "bits".to_string(),
+                Box::new(Schema::Primitive(SchemaPrimitive::schema_u32)),
+            )],
+        ))
+    }
+}
+impl Packed for Ipv4Addr {}
+impl Serialize for Ipv4Addr {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u32(self.to_bits())
+    }
+}
+impl Deserialize for Ipv4Addr {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Ipv4Addr::from_bits(deserializer.read_u32()?))
+    }
+}
+impl Introspect for Ipv4Addr {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+impl WithSchema for Ipv6Addr {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "Ipv6Addr".to_string(),
+            vec![Field::new(
+                "bits".to_string(),
+                Box::new(Schema::Primitive(SchemaPrimitive::schema_u128)),
+            )],
+        ))
+    }
+}
+impl Packed for Ipv6Addr {}
+impl Serialize for Ipv6Addr {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u128(self.to_bits())
+    }
+}
+impl Deserialize for Ipv6Addr {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Ipv6Addr::from_bits(deserializer.read_u128()?))
+    }
+}
+impl Introspect for Ipv6Addr {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+// The field order is the same as for the variants of SocketAddr
+impl WithSchema for SocketAddrV4 {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "SocketAddrV4".to_string(),
+            vec![
+                Field::new(
+                    "port".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u16)),
+                ),
+                Field::new(
+                    "ip".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u32)),
+                ),
+            ],
+        ))
+    }
+}
+impl Packed for SocketAddrV4 {}
+impl Serialize for SocketAddrV4 {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u16(self.port())?;
+        serializer.write_u32(self.ip().to_bits())
+    }
+}
+impl Deserialize for SocketAddrV4 {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let port = deserializer.read_u16()?;
+        let ip = deserializer.read_u32()?;
+        Ok(SocketAddrV4::new(Ipv4Addr::from_bits(ip), port))
+    }
+}
+impl Introspect for SocketAddrV4 {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+impl WithSchema for SocketAddrV6 {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "SocketAddrV6".to_string(),
+            vec![
+                Field::new(
+                    "port".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u16)),
+                ),
+                Field::new(
+                    "ip".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u128)),
+                ),
+                Field::new(
+                    "flowinfo".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u32)),
+                ),
+                Field::new(
+                    "scope_id".to_string(),
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u32)),
+                ),
+            ],
+        ))
+    }
+}
+impl Packed for SocketAddrV6 {}
+impl Serialize for SocketAddrV6 {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u16(self.port())?;
+        serializer.write_u128(self.ip().to_bits())?;
+        serializer.write_u32(self.flowinfo())?;
+        serializer.write_u32(self.scope_id())
+    }
+}
+impl Deserialize for SocketAddrV6 {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let port = deserializer.read_u16()?;
+        let ip = deserializer.read_u128()?;
+        let flowinfo = deserializer.read_u32()?;
+        let scope_id = deserializer.read_u32()?;
+        Ok(SocketAddrV6::new(Ipv6Addr::from_bits(ip), port, flowinfo, scope_id))
+    }
+}
+impl Introspect for SocketAddrV6 {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
 impl WithSchema for PathBuf {
     fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
         Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
@@ -1543,6 +1862,14 @@ impl<'a, T: 'a + Introspect + ToOwned + ?Sized> Introspect for Cow<'a, T> {
         (**self).introspect_value()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        (**self).introspect_typed_value()
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        (**self).introspect_kind()
+    }
+
     fn introspect_child<'b>(&'b self, index: usize) -> Option<Box<dyn IntrospectItem<'b> + 'b>> {
         (**self).introspect_child(index)
     }
@@ -2100,15 +2427,24 @@ impl<'a, W: Write + 'a> Serializer<'a, W> {
             writer,
             version,
             data,
-            Some(T::schema(version, &mut WithSchemaContext::new())),
+            FileSchema::Embedded(T::schema(version, &mut WithSchemaContext::new())),
             with_compression,
             None,
+            None,
         )?)
     }
     /// Creata a new serializer.
     /// Don't use this function directly, use the [crate::save_noschema] function instead.
     pub fn save_noschema<T: Serialize>(writer: &mut W, version: u32, data: &T) -> Result<(), SavefileError> {
-        Ok(Self::save_impl(writer, version, data, None, false, None)?)
+        Ok(Self::save_impl(
+            writer,
+            version,
+            data,
+            FileSchema::None,
+            false,
+            None,
+            None,
+        )?)
     }
 
     #[doc(hidden)]
@@ -2122,8 +2458,9 @@ impl<'a, W: Write + 'a> Serializer<'a, W> {
             writer,
             version,
             data,
-            None,
+            FileSchema::None,
             false,
+            None,
             Some(lib_version_override),
         )?)
     }
@@ -2141,32 +2478,47 @@ impl<'a, W: Write + 'a> Serializer<'a, W> {
         writer: &mut W,
         version: u32,
         data: &T,
-        with_schema: Option<Schema>,
+        with_schema: FileSchema,
         with_compression: bool,
+        metadata: Option<&[u8]>,
         lib_version_override: Option<u16>,
     ) -> Result<(), SavefileError> {
+        let lib_version = lib_version_override.unwrap_or(CURRENT_SAVEFILE_LIB_VERSION);
         let header = "savefile\0".to_string().into_bytes();
 
         writer.write_all(&header)?; //9
 
-        writer.write_u16::<LittleEndian>(
-            lib_version_override.unwrap_or(CURRENT_SAVEFILE_LIB_VERSION), /*savefile format version*/
-        )?;
+        writer.write_u16::<LittleEndian>(lib_version /*savefile format version*/)?;
         writer.write_u32::<LittleEndian>(version)?;
         // 9 + 2 + 4 = 15
+        let schema_by_reference = matches!(with_schema, FileSchema::Reference(_));
+        let schema_embedded = matches!(with_schema, FileSchema::Embedded(_)) && lib_version >= 3;
+        writer.write_u8(
+            with_compression as u8
+                | (schema_by_reference as u8) << 1
+                | (metadata.is_some() as u8) << 2
+                | (schema_embedded as u8) << 3,
+        )?; //15 + 1 = 16
+        match &with_schema {
+            FileSchema::Embedded(schema) if lib_version >= 3 => {
+                writer.write_u128::<LittleEndian>(schema.fingerprint())?;
+            }
+            FileSchema::Reference(fingerprint) => {
+                writer.write_u128::<LittleEndian>(*fingerprint)?;
+            }
+            _ => {}
+        }
+        if let Some(metadata) = metadata {
+            writer.write_u64::<LittleEndian>(metadata.len() as u64)?;
+            writer.write_all(metadata)?;
+        }
         {
             if with_compression {
-                writer.write_u8(1)?; //15 + 1 = 16
-
                 #[cfg(feature = "bzip2")]
                 {
                     let mut compressed_writer = bzip2::write::BzEncoder::new(writer, Compression::best());
-                    if let Some(schema) = with_schema {
-                        let mut schema_serializer = Serializer::<bzip2::write::BzEncoder<W>>::new_raw(
-                            &mut compressed_writer,
-                            lib_version_override.unwrap_or(CURRENT_SAVEFILE_LIB_VERSION) as u32,
-                        );
-                        schema.serialize(&mut schema_serializer)?;
+                    if let FileSchema::Embedded(schema) = with_schema {
+                        write_file_schema(&mut compressed_writer, &schema, lib_version)?;
                     }
 
                     let mut serializer = Serializer {
@@ -2182,13 +2534,8 @@ impl<'a, W: Write + 'a> Serializer<'a, W> {
                     return Err(SavefileError::CompressionSupportNotCompiledIn);
                 }
             } else {
-                writer.write_u8(0)?;
-                if let Some(schema) = with_schema {
-                    let mut schema_serializer = Serializer::<W>::new_raw(
-                        writer,
-                        lib_version_override.unwrap_or(CURRENT_SAVEFILE_LIB_VERSION) as u32,
-                    );
-                    schema.serialize(&mut schema_serializer)?;
+                if let FileSchema::Embedded(schema) = with_schema {
+                    write_file_schema(writer, &schema, lib_version)?;
                 }
 
                 let mut serializer = Serializer {
@@ -2354,7 +2701,28 @@ impl<TR: Read> Deserializer<'_, TR> {
         Deserializer::<_>::load_impl::<T>(
             reader,
             version,
-            Some(|version| T::schema(version, &mut WithSchemaContext::new())),
+            Some((
+                |version| T::schema(version, &mut WithSchemaContext::new()),
+                T::schema_fingerprint,
+            )),
+            None,
+            true,
+        )
+    }
+
+    /// Deserialize an object of type T from the given reader.
+    /// Don't use this method directly, use the [crate::load_strict] function
+    /// instead.
+    pub fn load_strict<T: WithSchema + Deserialize>(reader: &mut TR, version: u32) -> Result<T, SavefileError> {
+        Deserializer::<_>::load_impl::<T>(
+            reader,
+            version,
+            Some((
+                |version| T::schema(version, &mut WithSchemaContext::new()),
+                T::schema_fingerprint,
+            )),
+            None,
+            false,
         )
     }
 
@@ -2362,8 +2730,8 @@ impl<TR: Read> Deserializer<'_, TR> {
     /// Don't use this method directly, use the [crate::load_noschema] function
     /// instead.
     pub fn load_noschema<T: Deserialize>(reader: &mut TR, version: u32) -> Result<T, SavefileError> {
-        let dummy: Option<fn(u32) -> Schema> = None;
-        Deserializer::<TR>::load_impl::<T>(reader, version, dummy)
+        let no_schema = None::<(fn(u32) -> Schema, fn(u32) -> u128)>;
+        Deserializer::<TR>::load_impl::<T>(reader, version, no_schema, None, false)
     }
 
     /// Deserialize data which was serialized using 'bare_serialize'
@@ -2380,7 +2748,9 @@ impl<TR: Read> Deserializer<'_, TR> {
     fn load_impl<T: Deserialize>(
         reader: &mut TR,
         version: u32,
-        expected_schema: Option<impl FnOnce(u32) -> Schema>,
+        expected_schema: Option<(impl FnOnce(u32) -> Schema, impl FnOnce(u32) -> u128)>,
+        registry: Option<&dyn SchemaRegistry>,
+        allow_widening: bool,
     ) -> Result<T, SavefileError> {
         let mut head: [u8; 9] = [0u8; 9];
         reader.read_exact(&mut head)?;
@@ -2407,60 +2777,271 @@ impl<TR: Read> Deserializer<'_, TR> {
                 ),
             });
         }
-        let with_compression = reader.read_u8()? != 0;
+        let flags = reader.read_u8()?;
+        check_header_flags(flags, savefile_lib_version)?;
+        let with_compression = flags & 1 != 0;
+        let schema_by_reference = flags & 2 != 0;
+        let with_metadata = flags & 4 != 0;
+        // Files older than version 3 don't say whether they have a schema
+        let schema_embedded = flags & 8 != 0;
+        if savefile_lib_version >= 3 && expected_schema.is_some() && !schema_embedded && !schema_by_reference {
+            return Err(SavefileError::GeneralError {
+                msg: "The file has no schema, since it was saved without one. Load it using load_noschema.".into(),
+            });
+        }
+        let file_fingerprint = if schema_embedded || schema_by_reference {
+            Some(reader.read_u128::<LittleEndian>()?)
+        } else {
+            None
+        };
+        if with_metadata {
+            let metadata_size = reader.read_u64::<LittleEndian>()?;
+            skip_exact(reader, metadata_size)?;
+        }
+
+        let mut expected_schema = expected_schema;
+        let mut widening = None;
+        if let (true, Some(file_fingerprint)) = (schema_by_reference, file_fingerprint) {
+            if let Some((memory_schema, memory_fingerprint)) = expected_schema.take() {
+                widening = verify_referenced_file_schema(
+                    registry,
+                    file_ver,
+                    file_fingerprint,
+                    memory_schema,
+                    memory_fingerprint,
+                    allow_widening,
+                )?;
+            }
+        }
 
         if with_compression {
             #[cfg(feature = "bzip2")]
             {
                 let mut compressed_reader = bzip2::read::BzDecoder::new(reader);
-                if let Some(memory_schema) = expected_schema {
-                    let mut schema_deserializer = new_schema_deserializer(&mut compressed_reader, savefile_lib_version);
-                    let memory_schema = memory_schema(file_ver);
-                    let file_schema = Schema::deserialize(&mut schema_deserializer)?;
-
-                    if let Some(err) = diff_schema(&memory_schema, &file_schema, ".".to_string(), false) {
-                        return Err(SavefileError::IncompatibleSchema {
-                            message: format!(
-                                "Saved schema differs from in-memory schema for version {}. Error: {}",
-                                file_ver, err
-                            ),
-                        });
-                    }
+                if let Some((memory_schema, memory_fingerprint)) = expected_schema {
+                    widening = read_and_verify_file_schema(
+                        &mut compressed_reader,
+                        savefile_lib_version,
+                        file_ver,
+                        file_fingerprint,
+                        memory_schema,
+                        memory_fingerprint,
+                        allow_widening,
+                    )?;
+                } else if schema_embedded {
+                    skip_file_schema(&mut compressed_reader)?;
                 }
-                let mut deserializer = Deserializer {
-                    reader: &mut compressed_reader,
-                    file_version: file_ver,
-                    ephemeral_state: HashMap::new(),
-                };
-                Ok(T::deserialize(&mut deserializer)?)
+                deserialize_payload(&mut compressed_reader, file_ver, widening)
             }
             #[cfg(not(feature = "bzip2"))]
             {
                 return Err(SavefileError::CompressionSupportNotCompiledIn);
             }
         } else {
-            if let Some(memory_schema) = expected_schema {
-                let mut schema_deserializer = new_schema_deserializer(reader, savefile_lib_version);
-                let memory_schema = memory_schema(file_ver);
-                let file_schema = Schema::deserialize(&mut schema_deserializer)?;
-
-                if let Some(err) = diff_schema(&memory_schema, &file_schema, ".".to_string(), false) {
-                    return Err(SavefileError::IncompatibleSchema {
-                        message: format!(
-                            "Saved schema differs from in-memory schema for version {}. Error: {}",
-                            file_ver, err
-                        ),
-                    });
+            if let Some((memory_schema, memory_fingerprint)) = expected_schema {
+                widening = read_and_verify_file_schema(
+                    reader,
+                    savefile_lib_version,
+                    file_ver,
+                    file_fingerprint,
+                    memory_schema,
+                    memory_fingerprint,
+                    allow_widening,
+                )?;
+            } else if schema_embedded {
+                skip_file_schema(reader)?;
+            }
+            deserialize_payload(reader, file_ver, widening)
+        }
+    }
+}
+
+/// Check that the flags of a file header are consistent with each other and
+/// with the savefile format version of the file.
+fn check_header_flags(flags: u8, savefile_lib_version: u16) -> Result<(), SavefileError> {
+    let schema_by_reference = flags & 2 != 0;
+    let with_metadata = flags & 4 != 0;
+    let schema_embedded = flags & 8 != 0;
+    if ((schema_by_reference || with_metadata || schema_embedded) && savefile_lib_version < 3)
+        || (schema_embedded && schema_by_reference)
+    {
+        return Err(SavefileError::GeneralError {
+            msg: "Corrupt savefile header.".into(),
+        });
+    }
+    Ok(())
+}
+
+/// How the schema of the data is stored in a file.
+enum FileSchema {
+    /// No schema is stored (used by the `_noschema` functions).
+    None,
+    /// The schema is stored in the file.
+    Embedded(Schema),
+    /// Only the fingerprint of the schema is stored in the file. The schema itself
+    /// is kept in a [SchemaRegistry].
+    Reference(u128),
+}
+
+/// Check that a file which refers to its schema by fingerprint can be loaded using the
+/// in-memory schema. The schema of the file is only looked up in the registry if its
+/// fingerprint differs from that of the in-memory schema.
+fn verify_referenced_file_schema(
+    registry: Option<&dyn SchemaRegistry>,
+    file_ver: u32,
+    file_fingerprint: u128,
+    memory_schema: impl FnOnce(u32) -> Schema,
+    memory_fingerprint: impl FnOnce(u32) -> u128,
+    allow_widening: bool,
+) -> Result<Option<(Schema, Schema)>, SavefileError> {
+    if file_fingerprint != 0 && file_fingerprint == memory_fingerprint(file_ver) {
+        return Ok(None);
+    }
+    let Some(registry) = registry else {
+        return Err(SavefileError::GeneralError {
+            msg: format!(
+                "The file refers to its schema by fingerprint ({:032x}), and must be loaded using load_with_registry.",
+                file_fingerprint
+            ),
+        });
+    };
+    let Some(file_schema) = registry.get_schema(file_fingerprint)? else {
+        return Err(SavefileError::GeneralError {
+            msg: format!(
+                "The schema with fingerprint {:032x} was not found in the schema registry.",
+                file_fingerprint
+            ),
+        });
+    };
+    verify_file_schema(memory_schema(file_ver), file_schema, file_ver, allow_widening)
+}
+
+/// Read the schema of a file, written by [write_file_schema]
+fn read_file_schema(reader: &mut impl Read, lib_version: u16) -> Result<Schema, SavefileError> {
+    if lib_version >= 3 {
+        reader.read_u64::<LittleEndian>()?;
+    }
+    Schema::deserialize(&mut new_schema_deserializer(reader, lib_version))
+}
+
+/// Skip the schema of a file with savefile format version 3 or later,
+/// when loading it without checking the schema.
+fn skip_file_schema(reader: &mut impl Read) -> Result<(), SavefileError> {
+    let schema_size = reader.read_u64::<LittleEndian>()?;
+    skip_exact(reader, schema_size)
+}
+
+/// Skip 'count' bytes of the reader
+fn skip_exact(reader: &mut impl Read, count: u64) -> Result<(), SavefileError> {
+    let skipped = std::io::copy(&mut reader.take(count), &mut std::io::sink())?;
+    if skipped != count {
+        return Err(SavefileError::IOError {
+            io_error: ErrorKind::UnexpectedEof.into(),
+        });
+    }
+    Ok(())
+}
+
+/// Write the schema of a file.
+/// Since savefile lib version 3, the schema is preceded by its size in bytes,
+/// so that loaders which find a matching fingerprint in the header can skip it.
+fn write_file_schema(writer: &mut impl Write, schema: &Schema, lib_version: u16) -> Result<(), SavefileError> {
+    if lib_version >= 3 {
+        let mut schema_bytes = Vec::new();
+        schema.serialize(&mut Serializer {
+            writer: &mut schema_bytes,
+            file_version: lib_version as u32,
+        })?;
+        writer.write_u64::<LittleEndian>(schema_bytes.len() as u64)?;
+        writer.write_all(&schema_bytes)?;
+    } else {
+        schema.serialize(&mut Serializer {
+            writer,
+            file_version: lib_version as u32,
+        })?;
+    }
+    Ok(())
+}
+
+/// Read the schema of a file, and check that the file can be loaded using the in-memory schema.
+///
+/// If the fingerprint from the file header is equal to that of the in-memory schema,
+/// the schema in the file is skipped, and the in-memory schema is never created.
+fn read_and_verify_file_schema(
+    reader: &mut impl Read,
+    savefile_lib_version: u16,
+    file_ver: u32,
+    file_fingerprint: Option<u128>,
+    memory_schema: impl FnOnce(u32) -> Schema,
+    memory_fingerprint: impl FnOnce(u32) -> u128,
+    allow_widening: bool,
+) -> Result<Option<(Schema, Schema)>, SavefileError> {
+    if let Some(file_fingerprint) = file_fingerprint {
+        let schema_size = reader.read_u64::<LittleEndian>()?;
+        if file_fingerprint != 0 && file_fingerprint == memory_fingerprint(file_ver) {
+            skip_exact(reader, schema_size)?;
+            return Ok(None);
+        }
+    }
+    let mut schema_deserializer = new_schema_deserializer(reader, savefile_lib_version);
+    let file_schema = Schema::deserialize(&mut schema_deserializer)?;
+    verify_file_schema(memory_schema(file_ver), file_schema, file_ver, allow_widening)
+}
+
+/// Check that data saved with 'file_schema' can be loaded as 'memory_schema'.
+/// Returns the two schemas if the data must first be converted, because
+/// some primitives in the file need to be widened.
+fn verify_file_schema(
+    memory_schema: Schema,
+    file_schema: Schema,
+    file_ver: u32,
+    allow_widening: bool,
+) -> Result<Option<(Schema, Schema)>, SavefileError> {
+    if let Some(mut err) = diff_schema(&memory_schema, &file_schema, ".".to_string(), false) {
+        if allow_widening {
+            match diff_schema_widening(&memory_schema, &file_schema, ".".to_string()) {
+                None => return Ok(Some((memory_schema, file_schema))),
+                Some(widening_err) if widening_err != err => {
+                    err = format!("{}. The data cannot be converted: {}", err, widening_err);
                 }
+                Some(_) => {}
             }
-            let mut deserializer = Deserializer {
-                reader,
-                file_version: file_ver,
-                ephemeral_state: HashMap::new(),
-            };
-            Ok(T::deserialize(&mut deserializer)?)
         }
+        return Err(SavefileError::IncompatibleSchema {
+            message: format!(
+                "Saved schema differs from in-memory schema for version {}. Error: {}",
+                file_ver, err
+            ),
+        });
+    }
+    Ok(None)
+}
+
+fn deserialize_payload<T: Deserialize>(
+    reader: &mut impl Read,
+    file_ver: u32,
+    widening: Option<(Schema, Schema)>,
+) -> Result<T, SavefileError> {
+    if let Some((memory_schema, file_schema)) = widening {
+        let mut converter = WideningReader::new(&memory_schema, &file_schema, reader, file_ver);
+        let mut deserializer = Deserializer {
+            reader: &mut converter,
+            file_version: file_ver,
+            ephemeral_state: HashMap::new(),
+        };
+        let result = T::deserialize(&mut deserializer);
+        // Report the actual problem, not the io error it was reported as
+        if let Some(err) = converter.error.take() {
+            return Err(err);
+        }
+        return result;
     }
+    let mut deserializer = Deserializer {
+        reader,
+        file_version: file_ver,
+        ephemeral_state: HashMap::new(),
+    };
+    T::deserialize(&mut deserializer)
 }
 
 /// Create a Deserializer.
@@ -2483,6 +3064,16 @@ pub fn load<T: WithSchema + Deserialize>(reader: &mut impl Read, version: u32) -
     Deserializer::<_>::load::<T>(reader, version)
 }
 
+/// Like [crate::load], but does not accept any differences between the schema
+/// in the file and the in-memory schema.
+///
+/// [crate::load] automatically converts primitives which have been changed
+/// to a wider type, such as u32 to u64. This function instead fails with
+/// [SavefileError::IncompatibleSchema] in that case.
+pub fn load_strict<T: WithSchema + Deserialize>(reader: &mut impl Read, version: u32) -> Result<T, SavefileError> {
+    Deserializer::<_>::load_strict::<T>(reader, version)
+}
+
 /// Deserialize an instance of type T from the given u8 slice .
 ///
 /// The current type of T in memory must be equal to `version`.
@@ -2564,6 +3155,16 @@ pub fn load_file<T: WithSchema + Deserialize, P: AsRef<Path>>(filepath: P, versi
     Deserializer::load::<T>(&mut f, version)
 }
 
+/// Like [crate::load_strict] , except it deserializes from the given file in the filesystem.
+/// This is a pure convenience function.
+pub fn load_file_strict<T: WithSchema + Deserialize, P: AsRef<Path>>(
+    filepath: P,
+    version: u32,
+) -> Result<T, SavefileError> {
+    let mut f = BufReader::new(File::open(filepath)?);
+    Deserializer::load_strict::<T>(&mut f, version)
+}
+
 /// Like [crate::save] , except it opens a file on the filesystem and writes
 /// the data to it. This is a pure convenience function.
 pub fn save_file<T: WithSchema + Serialize, P: AsRef<Path>>(
@@ -2595,21 +3196,550 @@ pub fn save_file_noschema<T: Serialize, P: AsRef<Path>>(
     Serializer::save_noschema::<T>(&mut f, version, data)
 }
 
-/// Context object used to keep track of recursion.
+/// Write the given `data` to the `writer`, with a reference to its schema instead of
+/// the schema itself.
 ///
-/// Datastructures which cannot contain recursion do not need to concern themselves with
-/// this. Recursive data structures in rust require the use of Box, Vec, Arc, or similar.
-/// The most common of these datatypes from std are supported by savefile, and will guard
-/// against recursion in a well-defined way.
-/// As a user of Savefile, you only need to use this if you are implementing Savefile for
-/// container or smart-pointer type.
-pub struct WithSchemaContext {
-    seen_types: HashMap<TypeId, usize /*depth*/>,
+/// Only the [Schema::fingerprint] of the schema is written to the file. The schema itself
+/// is added to the `registry`, unless it is already present. This makes sense when saving a
+/// large number of small objects, where the schema could otherwise be larger than the data.
+///
+/// The data must be loaded using [crate::load_with_registry].
+pub fn save_with_schema_ref<T: WithSchema + Serialize>(
+    writer: &mut impl Write,
+    version: u32,
+    data: &T,
+    registry: &mut impl SchemaRegistry,
+) -> Result<(), SavefileError> {
+    let fingerprint = T::schema_fingerprint(version);
+    if fingerprint == 0 {
+        return Err(SavefileError::GeneralError {
+            msg: "The schema of this type contains Schema::Undefined, and cannot be referenced.".into(),
+        });
+    }
+    if !registry.contains_schema(fingerprint)? {
+        registry.add_schema(&T::schema(version, &mut WithSchemaContext::new()))?;
+    }
+    Serializer::save_impl(
+        writer,
+        version,
+        data,
+        FileSchema::Reference(fingerprint),
+        false,
+        None,
+        None,
+    )
 }
 
-impl WithSchemaContext {
-    /// Create a new empty WithSchemaContext.
-    /// This is useful for calling ::schema at the top-level.
+/// Deserialize an instance of type T from the given `reader`, which must have been
+/// written by [crate::save_with_schema_ref].
+///
+/// If the schema referenced by the file is not the in-memory schema, it is looked up in
+/// the `registry`, and checked in the same way as by [crate::load].
+/// Files with an embedded schema can also be loaded using this function.
+pub fn load_with_registry<T: WithSchema + Deserialize>(
+    reader: &mut impl Read,
+    version: u32,
+    registry: &impl SchemaRegistry,
+) -> Result<T, SavefileError> {
+    Deserializer::<_>::load_impl::<T>(
+        reader,
+        version,
+        Some((
+            |version| T::schema(version, &mut WithSchemaContext::new()),
+            T::schema_fingerprint,
+        )),
+        Some(registry),
+        true,
+    )
+}
+
+/// Write the given `data` to the `writer`, together with some metadata.
+///
+/// The metadata can be of any type, such as a `BTreeMap<String, Vec<u8>>`, and is saved
+/// using the same `version` as the data. It is stored uncompressed before the data, so that
+/// [crate::read_info] can return it without reading the rest of the file. Files with
+/// metadata can be loaded by all the regular load functions, which skip the metadata.
+pub fn save_with_metadata<T: WithSchema + Serialize, M: WithSchema + Serialize>(
+    writer: &mut impl Write,
+    version: u32,
+    data: &T,
+    metadata: &M,
+) -> Result<(), SavefileError> {
+    let metadata = save_to_mem(version, metadata)?;
+    Serializer::save_impl(
+        writer,
+        version,
+        data,
+        FileSchema::Embedded(T::schema(version, &mut WithSchemaContext::new())),
+        false,
+        Some(&metadata),
+        None,
+    )
+}
+
+/// Like [crate::save_with_metadata], except it opens a file on the filesystem and writes
+/// the data to it. This is a pure convenience function.
+pub fn save_file_with_metadata<T: WithSchema + Serialize, M: WithSchema + Serialize, P: AsRef<Path>>(
+    filepath: P,
+    version: u32,
+    data: &T,
+    metadata: &M,
+) -> Result<(), SavefileError> {
+    let mut f = BufWriter::new(File::create(filepath)?);
+    save_with_metadata(&mut f, version, data, metadata)
+}
+
+/// Information about a file, read from its header by [crate::read_info].
+#[derive(Debug, Clone)]
+pub struct FileInfo {
+    /// The savefile format version the file was written with.
+    /// See [CURRENT_SAVEFILE_LIB_VERSION].
+    pub lib_version: u16,
+    /// The version of the data in the file.
+    pub data_version: u32,
+    /// True if the data in the file is compressed.
+    pub compressed: bool,
+    /// The schema of the data in the file. None if the file was saved without a schema,
+    /// or only refers to its schema (see [crate::save_with_schema_ref]).
+    pub schema: Option<Schema>,
+    /// The metadata saved by [crate::save_with_metadata], if any. The metadata is stored
+    /// as a complete savefile, which can be decoded using [FileInfo::load_metadata].
+    pub metadata: Option<Vec<u8>>,
+}
+
+impl FileInfo {
+    /// Decode the metadata of the file, if it has any.
+    ///
+    /// The current version of the metadata type in memory must be `version`.
+    pub fn load_metadata<M: WithSchema + Deserialize>(&self, version: u32) -> Result<Option<M>, SavefileError> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| load_from_mem(metadata, version))
+            .transpose()
+    }
+}
+
+/// Read the header, metadata and schema of a file from the given `reader`, without
+/// reading the data itself.
+///
+/// Files written by savefile format versions before 3 do not record whether they
+/// contain a schema. They are assumed to have one, as written by [crate::save].
+pub fn read_info(reader: &mut impl Read) -> Result<FileInfo, SavefileError> {
+    let mut head: [u8; 9] = [0u8; 9];
+    reader.read_exact(&mut head)?;
+    if head[..] != b"savefile\0"[..] {
+        return Err(SavefileError::GeneralError {
+            msg: "File is not in new savefile-format.".into(),
+        });
+    }
+    let lib_version = reader.read_u16::<LittleEndian>()?;
+    if lib_version > CURRENT_SAVEFILE_LIB_VERSION {
+        return Err(SavefileError::GeneralError {
+            msg: "This file has been created by a future, incompatible version of the savefile crate.".into(),
+        });
+    }
+    let data_version = reader.read_u32::<LittleEndian>()?;
+    let flags = reader.read_u8()?;
+    check_header_flags(flags, lib_version)?;
+    let compressed = flags & 1 != 0;
+    let schema_by_reference = flags & 2 != 0;
+    let with_metadata = flags & 4 != 0;
+    let schema_embedded = lib_version < 3 || flags & 8 != 0;
+    if lib_version >= 3 && (schema_embedded || schema_by_reference) {
+        reader.read_u128::<LittleEndian>()?;
+    }
+
+    let metadata = if with_metadata {
+        let metadata_size = reader.read_u64::<LittleEndian>()?;
+        let mut metadata = Vec::new();
+        reader.take(metadata_size).read_to_end(&mut metadata)?;
+        if metadata.len() as u64 != metadata_size {
+            return Err(SavefileError::IOError {
+                io_error: ErrorKind::UnexpectedEof.into(),
+            });
+        }
+        Some(metadata)
+    } else {
+        None
+    };
+
+    let schema = if !schema_embedded {
+        None
+    } else if compressed {
+        #[cfg(feature = "bzip2")]
+        {
+            Some(read_file_schema(&mut bzip2::read::BzDecoder::new(reader), lib_version)?)
+        }
+        #[cfg(not(feature = "bzip2"))]
+        {
+            return Err(SavefileError::CompressionSupportNotCompiledIn);
+        }
+    } else {
+        Some(read_file_schema(reader, lib_version)?)
+    };
+
+    Ok(FileInfo {
+        lib_version,
+        data_version,
+        compressed,
+        schema,
+        metadata,
+    })
+}
+
+/// Like [crate::read_info], except it reads from the given file in the filesystem.
+/// This is a pure convenience function.
+pub fn read_file_info<P: AsRef<Path>>(filepath: P) -> Result<FileInfo, SavefileError> {
+    let mut f = BufReader::new(File::open(filepath)?);
+    read_info(&mut f)
+}
+
+/// A store of schemas, indexed by their [Schema::fingerprint].
+///
+/// This is used by [crate::save_with_schema_ref] and [crate::load_with_registry], to
+/// avoid storing the schema in each file. See [MemorySchemaRegistry],
+/// [DirectorySchemaRegistry] and [FileSchemaRegistry].
+pub trait SchemaRegistry {
+    /// Return the schema with the given fingerprint, or None if it is not in the registry.
+    fn get_schema(&self, fingerprint: u128) -> Result<Option<Schema>, SavefileError>;
+    /// Add the given schema to the registry. Adding a schema which is already present
+    /// must succeed.
+    fn add_schema(&mut self, schema: &Schema) -> Result<(), SavefileError>;
+    /// Return true if the registry has a schema with the given fingerprint.
+    fn contains_schema(&self, fingerprint: u128) -> Result<bool, SavefileError> {
+        Ok(self.get_schema(fingerprint)?.is_some())
+    }
+}
+
+/// A [SchemaRegistry] which keeps the schemas in memory.
+#[derive(Debug, Default, Clone)]
+pub struct MemorySchemaRegistry {
+    schemas: HashMap<u128, Schema>,
+}
+
+impl MemorySchemaRegistry {
+    /// Create a new, empty, registry.
+    pub fn new() -> MemorySchemaRegistry {
+        MemorySchemaRegistry::default()
+    }
+    /// Iterate over all schemas in the registry.
+    pub fn schemas(&self) -> impl Iterator<Item = &Schema> {
+        self.schemas.values()
+    }
+}
+
+impl SchemaRegistry for MemorySchemaRegistry {
+    fn get_schema(&self, fingerprint: u128) -> Result<Option<Schema>, SavefileError> {
+        Ok(self.schemas.get(&fingerprint).cloned())
+    }
+    fn add_schema(&mut self, schema: &Schema) -> Result<(), SavefileError> {
+        self.schemas
+            .entry(schema.fingerprint())
+            .or_insert_with(|| schema.clone());
+        Ok(())
+    }
+    fn contains_schema(&self, fingerprint: u128) -> Result<bool, SavefileError> {
+        Ok(self.schemas.contains_key(&fingerprint))
+    }
+}
+
+/// A path next to 'path', for writing a temporary file which is then renamed to 'path'.
+/// The name is unique within the process, so that threads don't overwrite each other's files.
+fn temp_file_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let mut temp_path = path.to_path_buf().into_os_string();
+    temp_path.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    temp_path.into()
+}
+
+/// A [SchemaRegistry] which stores each schema in a separate file in a directory.
+///
+/// The files are named after the fingerprint of the schema, with the extension `.schema`.
+/// Several processes can safely add schemas to the same directory.
+#[derive(Debug, Clone)]
+pub struct DirectorySchemaRegistry {
+    dir: PathBuf,
+}
+
+impl DirectorySchemaRegistry {
+    /// Create a registry using the given directory. The directory is created when
+    /// the first schema is added.
+    pub fn new(dir: impl Into<PathBuf>) -> DirectorySchemaRegistry {
+        DirectorySchemaRegistry { dir: dir.into() }
+    }
+    fn schema_path(&self, fingerprint: u128) -> PathBuf {
+        self.dir.join(format!("{:032x}.schema", fingerprint))
+    }
+}
+
+impl SchemaRegistry for DirectorySchemaRegistry {
+    fn get_schema(&self, fingerprint: u128) -> Result<Option<Schema>, SavefileError> {
+        let path = self.schema_path(fingerprint);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(load_file_noschema(&path, CURRENT_SAVEFILE_LIB_VERSION as u32)?))
+    }
+    fn add_schema(&mut self, schema: &Schema) -> Result<(), SavefileError> {
+        let path = self.schema_path(schema.fingerprint());
+        if path.exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.dir)?;
+        // Write to a temporary file first, so that other processes never see a partial schema
+        let temp_path = temp_file_path(&path);
+        save_file_noschema(&temp_path, CURRENT_SAVEFILE_LIB_VERSION as u32, schema)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+    fn contains_schema(&self, fingerprint: u128) -> Result<bool, SavefileError> {
+        Ok(self.schema_path(fingerprint).exists())
+    }
+}
+
+/// A [SchemaRegistry] which stores all schemas in a single file.
+///
+/// All schemas are read when the registry is opened. Each time a new schema is added, the file
+/// is read again, and rewritten with the schemas from the file and the registry.
+///
+/// Only one process (and only one registry instance) may add schemas to the file at a time.
+/// There is no locking, so if two writers add schemas concurrently, the schemas added by one of
+/// them may be lost, and files saved with references to those schemas can then not be loaded.
+/// Use [DirectorySchemaRegistry] if several processes need to add schemas.
+#[derive(Debug, Clone)]
+pub struct FileSchemaRegistry {
+    path: PathBuf,
+    schemas: MemorySchemaRegistry,
+}
+
+impl FileSchemaRegistry {
+    /// Open the registry stored in the given file. If the file does not exist, the registry
+    /// is empty, and the file is created when the first schema is added.
+    pub fn open(path: impl Into<PathBuf>) -> Result<FileSchemaRegistry, SavefileError> {
+        let path = path.into();
+        let mut registry = FileSchemaRegistry {
+            path,
+            schemas: MemorySchemaRegistry::new(),
+        };
+        registry.read_file()?;
+        Ok(registry)
+    }
+
+    /// Add the schemas in the file to this registry
+    fn read_file(&mut self) -> Result<(), SavefileError> {
+        if self.path.exists() {
+            let stored: Vec<Schema> = load_file_noschema(&self.path, CURRENT_SAVEFILE_LIB_VERSION as u32)?;
+            for schema in stored {
+                self.schemas.schemas.entry(schema.fingerprint()).or_insert(schema);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SchemaRegistry for FileSchemaRegistry {
+    fn get_schema(&self, fingerprint: u128) -> Result<Option<Schema>, SavefileError> {
+        self.schemas.get_schema(fingerprint)
+    }
+    fn add_schema(&mut self, schema: &Schema) -> Result<(), SavefileError> {
+        if self.schemas.contains_schema(schema.fingerprint())? {
+            return Ok(());
+        }
+        // Keep schemas added to the file since it was read
+        self.read_file()?;
+        self.schemas.add_schema(schema)?;
+        let mut all: Vec<Schema> = self.schemas.schemas().cloned().collect();
+        all.sort_by_key(|schema| schema.fingerprint());
+        // Write to a temporary file first, so that the registry is never left partially written
+        let temp_path = temp_file_path(&self.path);
+        save_file_noschema(&temp_path, CURRENT_SAVEFILE_LIB_VERSION as u32, &all)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+    fn contains_schema(&self, fingerprint: u128) -> Result<bool, SavefileError> {
+        self.schemas.contains_schema(fingerprint)
+    }
+}
+
+/// Verify that the schemas of old versions of a type have not changed.
+///
+/// For each version from 0 to `latest_version`, the schema of `T` is compared to the one
+/// recorded in `dir`. Versions which have not been recorded yet are written to `dir`, one file
+/// per version, named after the type. These files should be checked in to version control.
+///
+/// This is meant to be called from a test, to detect accidental changes to the format of
+/// persisted types. Changes which only affect the memory layout are not reported.
+/// If the schema of any recorded version has changed, [SavefileError::IncompatibleSchema]
+/// is returned, with a description of the difference.
+///
+/// The file names do not include module paths, but the files record the full name of the type.
+/// An error is returned if a file was recorded for another type with the same name, which
+/// happens if two types with the same name share `dir`, or if the type has been moved to another
+/// module. Use [verify_named_schema_history] to give the history a name of its own.
+///
+/// This is the equivalent of `savefile_abi::verify_compatiblity`, for data types.
+pub fn verify_schema_history<T: WithSchema + ?Sized>(
+    dir: impl AsRef<Path>,
+    latest_version: u32,
+) -> Result<(), SavefileError> {
+    verify_named_schema_history::<T>(dir, std::any::type_name::<T>(), latest_version)
+}
+
+/// Like [verify_schema_history], but the schemas are recorded under the given name instead
+/// of the name of the type. This keeps the history valid if the type is moved or renamed.
+pub fn verify_named_schema_history<T: WithSchema + ?Sized>(
+    dir: impl AsRef<Path>,
+    name: &str,
+    latest_version: u32,
+) -> Result<(), SavefileError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let file_name = schema_history_type_name(name);
+    for version in 0..=latest_version {
+        let schema = T::schema(version, &mut WithSchemaContext::new());
+        let schema_file_name = dir.join(format!("savefile_{}_{}.schema", file_name, version));
+        if std::fs::metadata(&schema_file_name).is_ok() {
+            let (recorded_name, recorded): (String, Schema) =
+                load_file_noschema(&schema_file_name, CURRENT_SAVEFILE_LIB_VERSION as u32)?;
+            if recorded_name != name {
+                return Err(SavefileError::GeneralError {
+                    msg: format!(
+                        "{} records the schema of {}, not of {}. Types with the same name can't share a schema history directory.",
+                        schema_file_name.display(),
+                        recorded_name,
+                        name
+                    ),
+                });
+            }
+            if let Some(difference) = diff_schema(&schema, &recorded, ".".to_string(), false) {
+                return Err(SavefileError::IncompatibleSchema {
+                    message: format!(
+                        "The schema of version {} of {} differs from the one recorded in {}: {}\n{}",
+                        version,
+                        name,
+                        schema_file_name.display(),
+                        difference,
+                        line_diff(&format!("{:#?}", recorded), &format!("{:#?}", schema))
+                    ),
+                });
+            }
+        } else {
+            save_file_noschema(
+                &schema_file_name,
+                CURRENT_SAVEFILE_LIB_VERSION as u32,
+                &(name.to_string(), schema),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The name of a type, without module paths, usable as part of a file name.
+/// For example `my_crate::Wrapper<alloc::string::String>` becomes `Wrapper_String`.
+fn schema_history_type_name(type_name: &str) -> String {
+    let mut result = String::new();
+    let mut identifier = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            identifier.push(c);
+        } else if c == ':' {
+            // Drop module paths
+            identifier.clear();
+        } else {
+            result.push_str(&identifier);
+            identifier.clear();
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+        }
+    }
+    result.push_str(&identifier);
+    result.trim_end_matches('_').to_string()
+}
+
+/// A line based diff of two texts, with lines only in 'old' prefixed by '-', and lines
+/// only in 'new' prefixed by '+'. Unchanged lines close to changes are included for context.
+fn line_diff(old: &str, new: &str) -> String {
+    const CONTEXT: usize = 3;
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // Longest common subsequence of the changed region. If it is very large,
+    // just show all of it as removed and added.
+    let mut ops: Vec<(char, &str)> = Vec::new();
+    if old_mid.len().saturating_mul(new_mid.len()) <= 4_000_000 {
+        let mut lcs = vec![vec![0u32; new_mid.len() + 1]; old_mid.len() + 1];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() || j < new_mid.len() {
+            if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+                ops.push((' ', old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if i < old_mid.len() && (j == new_mid.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push(('-', old_mid[i]));
+                i += 1;
+            } else {
+                ops.push(('+', new_mid[j]));
+                j += 1;
+            }
+        }
+    } else {
+        ops.extend(old_mid.iter().map(|line| ('-', *line)));
+        ops.extend(new_mid.iter().map(|line| ('+', *line)));
+    }
+
+    let before = &old[prefix.saturating_sub(CONTEXT)..prefix];
+    let after = &old[old.len() - suffix..(old.len() - suffix + CONTEXT).min(old.len())];
+    let mut result = String::new();
+    for line in before {
+        result.push_str(&format!("  {}\n", line));
+    }
+    for (op, line) in ops {
+        result.push_str(&format!("{} {}\n", op, line));
+    }
+    for line in after {
+        result.push_str(&format!("  {}\n", line));
+    }
+    result
+}
+
+/// Context object used to keep track of recursion.
+///
+/// Datastructures which cannot contain recursion do not need to concern themselves with
+/// this. Recursive data structures in rust require the use of Box, Vec, Arc, or similar.
+/// The most common of these datatypes from std are supported by savefile, and will guard
+/// against recursion in a well-defined way.
+/// As a user of Savefile, you only need to use this if you are implementing Savefile for
+/// container or smart-pointer type.
+pub struct WithSchemaContext {
+    seen_types: HashMap<TypeId, usize /*depth*/>,
+}
+
+impl WithSchemaContext {
+    /// Create a new empty WithSchemaContext.
+    /// This is useful for calling ::schema at the top-level.
     pub fn new() -> WithSchemaContext {
         let seen_types = HashMap::new();
         WithSchemaContext { seen_types }
@@ -2620,6 +3750,8 @@ fn type_id_of_value<T: 'static>(_t: T) -> TypeId {
     TypeId::of::<T>()
 }
 
+// The closure type is unique per T, even though T is not mentioned explicitly
+#[allow(clippy::extra_unused_type_parameters)]
 fn get_type_id<T>() -> TypeId {
     type_id_of_value(&(|| {}))
 }
@@ -2684,6 +3816,59 @@ pub trait WithSchema {
     /// The WithSchemaContext can be used to guard against recursive data structures.
     /// See documentation of WithSchemaContext.
     fn schema(version: u32, context: &mut WithSchemaContext) -> Schema;
+
+    /// Returns the [Schema::fingerprint] of the schema of this type, for the given version.
+    ///
+    /// This is used by the load functions to quickly determine if the schema in a file
+    /// is identical to the in-memory schema. The default implementation creates the
+    /// schema and hashes it. The derive macros cache the fingerprint of non-generic
+    /// types, so that the schema does not need to be created at all when loading a file
+    /// with an identical schema.
+    ///
+    /// Implementations must return exactly the fingerprint of the schema returned
+    /// by [WithSchema::schema].
+    fn schema_fingerprint(version: u32) -> u128 {
+        Self::schema(version, &mut WithSchemaContext::new()).fingerprint()
+    }
+}
+
+/// A cache of the schema fingerprint of a type, for each version it has been
+/// requested for.
+///
+/// This is used by the derive macros to implement [WithSchema::schema_fingerprint]
+/// for non-generic types, using a static instance. You probably don't need to use this
+/// type directly.
+#[derive(Debug, Default)]
+pub struct SchemaFingerprintCache {
+    fingerprints: std::sync::Mutex<Vec<(u32, u128)>>,
+}
+
+impl SchemaFingerprintCache {
+    /// Create a new, empty, cache.
+    pub const fn new() -> SchemaFingerprintCache {
+        SchemaFingerprintCache {
+            fingerprints: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+    /// Return the cached fingerprint for the given version, or call 'calculate'
+    /// to determine it if it is not yet known.
+    pub fn get(&self, version: u32, calculate: impl FnOnce() -> u128) -> u128 {
+        let lookup = |fingerprints: &[(u32, u128)]| {
+            fingerprints
+                .iter()
+                .find(|(cached_version, _)| *cached_version == version)
+                .map(|(_, fingerprint)| *fingerprint)
+        };
+        if let Some(fingerprint) = lookup(&self.fingerprints.lock().unwrap_or_else(|err| err.into_inner())) {
+            return fingerprint;
+        }
+        let fingerprint = calculate();
+        let mut fingerprints = self.fingerprints.lock().unwrap_or_else(|err| err.into_inner());
+        if lookup(&fingerprints).is_none() {
+            fingerprints.push((version, fingerprint));
+        }
+        fingerprint
+    }
 }
 
 /// Create a new WithSchemaContext, and then call 'schema' on type T.
@@ -2768,6 +3953,17 @@ pub trait IntrospectItem<'a> {
     fn val(&self) -> &dyn Introspect;
 }
 
+/// A mutable child of an object implementing IntrospectMut.
+///
+/// This is the mutable counterpart of [IntrospectItem].
+pub trait IntrospectItemMut<'a> {
+    /// The key of the child. This must be the same as the key of the
+    /// corresponding [IntrospectItem].
+    fn key(&self) -> &str;
+    /// The mutable introspectable value of the child.
+    fn val_mut(&mut self) -> &mut dyn IntrospectMut;
+}
+
 /// This is an zero-sized introspectable object with no value and no children.
 /// It is used for situations where you wish to have a key but no value.
 struct NullIntrospectable {}
@@ -2778,6 +3974,10 @@ impl Introspect for NullIntrospectable {
         String::new()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
@@ -2806,6 +4006,54 @@ impl IntrospectItem<'_> for String {
     }
 }
 
+/// The value of an introspected object, as returned by [Introspect::introspect_typed_value].
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntrospectValue {
+    /// A signed integer
+    Int(i128),
+    /// An unsigned integer
+    UInt(u128),
+    /// A floating point number
+    Float(f64),
+    /// A boolean
+    Bool(bool),
+    /// A string, or any value without a more specific type
+    Str(String),
+    /// A single character
+    Char(char),
+    /// A point in time, as the number of seconds since 1970-01-01T00:00:00 UTC.
+    /// For times before 1970, the seconds are negative, and rounded down.
+    Timestamp {
+        /// Whole seconds since 1970-01-01T00:00:00 UTC.
+        secs: i64,
+        /// Nanoseconds, in the range 0..1_000_000_000.
+        nanos: u32,
+    },
+    /// Binary data
+    Bytes(Vec<u8>),
+    /// The object has no value of its own. This is the case for collections and structs,
+    /// for example. Also used for `Option::None`.
+    None,
+}
+
+/// The kind of object, as returned by [Introspect::introspect_kind].
+///
+/// This tells what an object without children is an empty instance of, which matters
+/// when writing it as for example JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntrospectKind {
+    /// Anything which isn't one of the other kinds, like a primitive or an enum
+    Value,
+    /// A collection with children keyed by position, like `Vec` or `HashSet`
+    Sequence,
+    /// A map whose children alternate between the key and the value of each entry,
+    /// like `HashMap` or `BTreeMap`
+    Map,
+    /// A struct, or another object with children identified by their key, like
+    /// `hashbrown::HashMap`, which has one child per entry, keyed by the value of the map key
+    Struct,
+}
+
 /// Max number of introspect children.
 ///
 /// As a sort of guard against infinite loops, the default 'len'-implementation only
@@ -2831,6 +4079,27 @@ pub trait Introspect {
     /// as children.
     fn introspect_value(&self) -> String;
 
+    /// Returns the value of the object, excluding children, as a typed value.
+    /// This allows telling for example the integer 42 from the string "42".
+    ///
+    /// Objects which have no value of their own, like collections and structs, should return
+    /// [IntrospectValue::None]. The default implementation returns [IntrospectValue::None] if
+    /// [Introspect::introspect_kind] is a sequence or a map, and otherwise the result of
+    /// [Introspect::introspect_value] as a [IntrospectValue::Str].
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        match self.introspect_kind() {
+            IntrospectKind::Value => IntrospectValue::Str(self.introspect_value()),
+            IntrospectKind::Sequence | IntrospectKind::Map | IntrospectKind::Struct => IntrospectValue::None,
+        }
+    }
+
+    /// Returns the kind of object. `#[derive(Savefile)]` returns [IntrospectKind::Struct] for structs,
+    /// and savefile returns [IntrospectKind::Sequence], [IntrospectKind::Map] or [IntrospectKind::Struct]
+    /// for its collection types. The default implementation returns [IntrospectKind::Value].
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Value
+    }
+
     /// Returns an the name and &dyn Introspect for the child with the given index,
     /// or if no child with that index exists, None.
     /// All the children should be indexed consecutively starting at 0 with no gaps,
@@ -2839,6 +4108,19 @@ pub trait Introspect {
     /// encouraged.
     fn introspect_child<'a>(&'a self, index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>>;
 
+    /// Returns up to `count` children, starting with the child at index `start`.
+    ///
+    /// The default implementation calls introspect_child for each index, stopping at the
+    /// first index without a child. Types which can't access a child by index efficiently, like
+    /// hash maps, override this so that a range can be returned using a single iteration.
+    /// As long as the object isn't modified, the children are in the same order as
+    /// returned by introspect_child.
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        (start..start.saturating_add(count))
+            .map_while(|index| self.introspect_child(index))
+            .collect()
+    }
+
     /// Returns the total number of children.
     /// The default implementation calculates this by simply calling introspect_child with
     /// higher and higher indexes until it returns None.
@@ -2854,6 +4136,33 @@ pub trait Introspect {
     }
 }
 
+/// Gives the ability to modify an object through introspection.
+///
+/// `#[derive(Savefile)]` implements this trait for structs and enums. Fields with
+/// types which don't implement `IntrospectMut` are still visited, but can't be modified.
+///
+/// Savefile implements this trait for primitives, strings, `Option`, `Box`, `Vec`, `VecDeque` and arrays.
+pub trait IntrospectMut: Introspect {
+    /// Returns the child with the given index, with mutable access to its value.
+    /// The children must have the same indices and keys as those returned by
+    /// [Introspect::introspect_child].
+    ///
+    /// The default implementation returns None.
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        let _ = index;
+        None
+    }
+
+    /// Set the value of this object by parsing the given string. The format should
+    /// be the same as that returned by [Introspect::introspect_value].
+    ///
+    /// The default implementation returns [IntrospectionError::ReadOnly].
+    fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+        let _ = value;
+        Err(IntrospectionError::ReadOnly)
+    }
+}
+
 /// This trait must be implemented for all data structures you wish to
 /// be able to deserialize.
 ///
@@ -2881,6 +4190,28 @@ pub trait Savefile: Serialize + Deserialize + WithSchema + Packed {}
 
 impl<T: Serialize + Deserialize + WithSchema + Packed> Savefile for T {}
 
+/// Return types supported for functions given in the `savefile_post_load`
+/// and `savefile_pre_save` attributes.
+///
+/// A hook can return `()`, or a `Result` with an error type which can be converted
+/// into [SavefileError]. The error aborts the load or save operation.
+pub trait HookResult {
+    /// Convert into the result of the load or save operation.
+    fn into_savefile_result(self) -> Result<(), SavefileError>;
+}
+
+impl HookResult for () {
+    fn into_savefile_result(self) -> Result<(), SavefileError> {
+        Ok(())
+    }
+}
+
+impl<E: Into<SavefileError>> HookResult for Result<(), E> {
+    fn into_savefile_result(self) -> Result<(), SavefileError> {
+        self.map_err(|err| err.into())
+    }
+}
+
 /// A field is serialized according to its value.
 /// The name is just for diagnostics.
 #[derive(Debug, PartialEq, Clone)]
@@ -3049,7 +4380,7 @@ pub struct Variant {
     /// Name of variant
     pub name: String,
     /// Discriminant in binary file-format
-    pub discriminant: u8,
+    pub discriminant: u32,
     /// Fields of variant
     pub fields: Vec<Field>,
 }
@@ -3077,8 +4408,8 @@ impl Variant {
 
 /// Schema for an enum.
 ///
-/// An enum is serialized as its u8 variant discriminant
-/// followed by all the field for that variant.
+/// An enum is serialized as its variant discriminant, using
+/// 'discriminant_size' bytes, followed by all the field for that variant.
 /// The name of each variant, as well as its order in
 /// the enum (the discriminant), is significant.
 /// The memory format is given by 'has_explicit_repr',
@@ -3196,7 +4527,7 @@ impl SchemaEnum {
         true
     }
     fn serialized_size(&self) -> Option<usize> {
-        let discr_size = 1usize; //Discriminant is always 1 byte
+        let discr_size = self.discriminant_size as usize;
         self.variants
             .iter()
             .fold(Some(discr_size), |prev, x| maybe_max(prev, x.serialized_size()))
@@ -3247,9 +4578,60 @@ pub enum SchemaPrimitive {
     schema_i128,
     /// char
     schema_char,
+    /// A calendar date, saved as an i32 number of days since 1970-01-01.
+    schema_date,
+    /// A time of day, saved as u32 seconds since midnight, then u32 nanoseconds.
+    /// The nanoseconds can be 1_000_000_000 or more during a leap second.
+    schema_time,
+    /// A date and time without time zone, saved as i64 seconds since 1970-01-01T00:00:00,
+    /// then u32 nanoseconds (as for `schema_time`).
+    schema_datetime,
+    /// A date and time with a fixed offset from UTC. Saved like `schema_datetime`,
+    /// using the UTC time, followed by the i32 offset in seconds east of UTC.
+    schema_datetime_offset,
+    /// A signed duration, saved as i64 seconds, then u32 nanoseconds in the range
+    /// 0..1_000_000_000. For negative durations, the seconds are rounded down.
+    schema_duration,
+    /// IEEE 754 half precision float, saved as its u16 bit pattern
+    schema_f16,
+    /// bfloat16 (the upper 16 bits of an f32), saved as its u16 bit pattern
+    schema_bf16,
+    /// A decimal number, saved as an i128 mantissa and a u8 scale. The value is
+    /// mantissa / 10^scale.
+    schema_decimal,
+    /// A signed integer of arbitrary size, saved as a usize byte count, then
+    /// that many bytes of little endian two's complement.
+    schema_bigint,
+    /// An unsigned integer of arbitrary size, saved as a usize byte count, then
+    /// that many bytes of little endian magnitude.
+    schema_biguint,
+    /// A UUID, saved as its 16 bytes in big endian (RFC 9562) order.
+    schema_uuid,
 }
 impl SchemaPrimitive {
+    /// Returns true if a value saved as 'narrower' can always be represented exactly
+    /// by this type. This is the case for unsigned integers to wider unsigned integers,
+    /// signed integers to wider signed integers, and f32 to f64.
+    pub fn is_lossless_widening_of(&self, narrower: &SchemaPrimitive) -> bool {
+        use SchemaPrimitive::*;
+        matches!(
+            (narrower, self),
+            (schema_u8, schema_u16 | schema_u32 | schema_u64 | schema_u128)
+                | (schema_u16, schema_u32 | schema_u64 | schema_u128)
+                | (schema_u32, schema_u64 | schema_u128)
+                | (schema_u64, schema_u128)
+                | (schema_i8, schema_i16 | schema_i32 | schema_i64 | schema_i128)
+                | (schema_i16, schema_i32 | schema_i64 | schema_i128)
+                | (schema_i32, schema_i64 | schema_i128)
+                | (schema_i64, schema_i128)
+                | (schema_f32, schema_f64)
+        )
+    }
     fn layout_compatible(&self, other: &SchemaPrimitive) -> bool {
+        if self.has_private_memory_layout() {
+            // The memory layout of these types is private to the crates defining them
+            return false;
+        }
         if let (SchemaPrimitive::schema_string(layout1), SchemaPrimitive::schema_string(layout2)) = (self, other) {
             if *layout1 == VecOrStringLayout::Unknown || *layout2 == VecOrStringLayout::Unknown {
                 return false;
@@ -3257,6 +4639,19 @@ impl SchemaPrimitive {
         }
         self == other
     }
+    fn has_private_memory_layout(&self) -> bool {
+        matches!(
+            self,
+            SchemaPrimitive::schema_date
+                | SchemaPrimitive::schema_time
+                | SchemaPrimitive::schema_datetime
+                | SchemaPrimitive::schema_datetime_offset
+                | SchemaPrimitive::schema_duration
+                | SchemaPrimitive::schema_decimal
+                | SchemaPrimitive::schema_bigint
+                | SchemaPrimitive::schema_biguint
+        )
+    }
     fn name(&self) -> &'static str {
         match *self {
             SchemaPrimitive::schema_i8 => "i8",
@@ -3275,6 +4670,17 @@ impl SchemaPrimitive {
             SchemaPrimitive::schema_u128 => "u128",
             SchemaPrimitive::schema_i128 => "i128",
             SchemaPrimitive::schema_char => "char",
+            SchemaPrimitive::schema_date => "date",
+            SchemaPrimitive::schema_time => "time",
+            SchemaPrimitive::schema_datetime => "datetime",
+            SchemaPrimitive::schema_datetime_offset => "datetime_offset",
+            SchemaPrimitive::schema_duration => "duration",
+            SchemaPrimitive::schema_f16 => "f16",
+            SchemaPrimitive::schema_bf16 => "bf16",
+            SchemaPrimitive::schema_decimal => "Decimal",
+            SchemaPrimitive::schema_bigint => "BigInt",
+            SchemaPrimitive::schema_biguint => "BigUint",
+            SchemaPrimitive::schema_uuid => "Uuid",
         }
     }
 
@@ -3291,15 +4697,26 @@ impl SchemaPrimitive {
             SchemaPrimitive::schema_canary1 => Some(4),
             SchemaPrimitive::schema_i128 | SchemaPrimitive::schema_u128 => Some(16),
             SchemaPrimitive::schema_char => Some(4),
+            SchemaPrimitive::schema_date => Some(4),
+            SchemaPrimitive::schema_time => Some(8),
+            SchemaPrimitive::schema_datetime | SchemaPrimitive::schema_duration => Some(12),
+            SchemaPrimitive::schema_datetime_offset => Some(16),
+            SchemaPrimitive::schema_f16 | SchemaPrimitive::schema_bf16 => Some(2),
+            SchemaPrimitive::schema_decimal => Some(17),
+            SchemaPrimitive::schema_bigint | SchemaPrimitive::schema_biguint => None,
+            SchemaPrimitive::schema_uuid => Some(16),
         }
     }
 }
 
-fn diff_primitive(a: SchemaPrimitive, b: SchemaPrimitive, path: &str) -> Option<String> {
+fn diff_primitive(a: SchemaPrimitive, b: SchemaPrimitive, path: &str, allow_widening: bool) -> Option<String> {
     if a != b {
         if let (SchemaPrimitive::schema_string(_), SchemaPrimitive::schema_string(_)) = (&a, &b) {
             return None; //Strings have the same schema, even if they're not memory-layout compatible
         }
+        if allow_widening && a.is_lossless_widening_of(&b) {
+            return None;
+        }
         return Some(format!(
             "At location [{}]: Application protocol has datatype {}, but disk format has {}",
             path,
@@ -3756,6 +5173,10 @@ impl Introspect for Schema {
         "Schema".to_string()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
@@ -3950,42 +5371,251 @@ impl Schema {
     }
 }
 
-fn diff_vector(a: &Schema, b: &Schema, path: String) -> Option<String> {
-    diff_schema(a, b, path + "/*", false)
-}
-
-fn diff_array(a: &SchemaArray, b: &SchemaArray, path: String) -> Option<String> {
-    if a.count != b.count {
-        return Some(format!(
-            "At location [{}]: In memory array has length {}, but disk format length {}.",
-            path, a.count, b.count
-        ));
-    }
-
-    diff_schema(&a.item_type, &b.item_type, format!("{}/[{}]", path, a.count), false)
-}
-
-fn diff_option(a: &Schema, b: &Schema, path: String) -> Option<String> {
-    diff_schema(a, b, path + "/?", false)
-}
+const SCHEMA_FINGERPRINT_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const SCHEMA_FINGERPRINT_PRIME: u128 = 0x0000000001000000000000000000013b;
 
-fn diff_enum(a: &SchemaEnum, b: &SchemaEnum, path: String) -> Option<String> {
-    let path = (path + &b.dbg_name).to_string();
-    if a.variants.len() != b.variants.len() {
-        return Some(format!(
-            "At location [{}]: In memory enum has {} variants, but disk format has {} variants.",
-            path,
-            a.variants.len(),
-            b.variants.len()
-        ));
+/// Calculates [Schema::fingerprint].
+///
+/// This is a 128 bit FNV-1a hash of a canonical encoding of the schema.
+/// Both the encoding and the hash are part of the file format, and must never change.
+struct SchemaFingerprinter {
+    state: u128,
+    found_undefined: bool,
+}
+
+impl SchemaFingerprinter {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u128;
+            self.state = self.state.wrapping_mul(SCHEMA_FINGERPRINT_PRIME);
+        }
+    }
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+    fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+    fn write_str(&mut self, value: &str) {
+        self.write_usize(value.len());
+        self.write(value.as_bytes());
+    }
+    fn primitive(&mut self, primitive: &SchemaPrimitive) {
+        // The memory layout of strings is not part of the fingerprint
+        let id: u8 = match primitive {
+            SchemaPrimitive::schema_i8 => 0,
+            SchemaPrimitive::schema_u8 => 1,
+            SchemaPrimitive::schema_i16 => 2,
+            SchemaPrimitive::schema_u16 => 3,
+            SchemaPrimitive::schema_i32 => 4,
+            SchemaPrimitive::schema_u32 => 5,
+            SchemaPrimitive::schema_i64 => 6,
+            SchemaPrimitive::schema_u64 => 7,
+            SchemaPrimitive::schema_string(_) => 8,
+            SchemaPrimitive::schema_f32 => 9,
+            SchemaPrimitive::schema_f64 => 10,
+            SchemaPrimitive::schema_bool => 11,
+            SchemaPrimitive::schema_canary1 => 12,
+            SchemaPrimitive::schema_u128 => 13,
+            SchemaPrimitive::schema_i128 => 14,
+            SchemaPrimitive::schema_char => 15,
+            SchemaPrimitive::schema_date => 16,
+            SchemaPrimitive::schema_time => 17,
+            SchemaPrimitive::schema_datetime => 18,
+            SchemaPrimitive::schema_datetime_offset => 19,
+            SchemaPrimitive::schema_duration => 20,
+            SchemaPrimitive::schema_f16 => 21,
+            SchemaPrimitive::schema_bf16 => 22,
+            SchemaPrimitive::schema_decimal => 23,
+            SchemaPrimitive::schema_bigint => 24,
+            SchemaPrimitive::schema_biguint => 25,
+            SchemaPrimitive::schema_uuid => 26,
+        };
+        self.write_u8(id);
+    }
+    fn fields(&mut self, fields: &[Field]) {
+        // Field names are only for diagnostics, and are not part of the fingerprint
+        self.write_usize(fields.len());
+        for field in fields {
+            self.schema(&field.value);
+        }
+    }
+    fn abi_def(&mut self, def: &AbiTraitDefinition) {
+        self.write_str(&def.name);
+        self.write_bool(def.sync);
+        self.write_bool(def.send);
+        self.write_usize(def.methods.len());
+        for method in &def.methods {
+            self.write_str(&method.name);
+            self.schema(&method.info.return_value);
+            self.write_u8(match method.info.receiver {
+                ReceiverType::Shared => 0,
+                ReceiverType::Mut => 1,
+                ReceiverType::PinMut => 2,
+            });
+            self.write_bool(method.info.async_trait_heuristic);
+            self.write_usize(method.info.arguments.len());
+            for argument in &method.info.arguments {
+                self.schema(&argument.schema);
+            }
+        }
     }
-    if a.discriminant_size != b.discriminant_size {
-        return Some(format!(
-            "At location [{}]: In memory enum has a representation with {} bytes for the discriminant, but disk format has {}.",
-            path,
-            a.discriminant_size,
-            b.discriminant_size
-        ));
+    fn schema(&mut self, schema: &Schema) {
+        match schema {
+            Schema::Struct(schema_struct) => {
+                self.write_u8(0);
+                self.fields(&schema_struct.fields);
+            }
+            Schema::Enum(schema_enum) => {
+                self.write_u8(1);
+                self.write_u8(schema_enum.discriminant_size);
+                self.write_usize(schema_enum.variants.len());
+                for variant in &schema_enum.variants {
+                    self.write_str(&variant.name);
+                    self.write_u32(variant.discriminant);
+                    self.fields(&variant.fields);
+                }
+            }
+            Schema::Primitive(primitive) => {
+                self.write_u8(2);
+                self.primitive(primitive);
+            }
+            Schema::Vector(item, _layout) => {
+                self.write_u8(3);
+                self.schema(item);
+            }
+            Schema::Array(array) => {
+                self.write_u8(4);
+                self.write_usize(array.count);
+                self.schema(&array.item_type);
+            }
+            Schema::SchemaOption(item) => {
+                self.write_u8(5);
+                self.schema(item);
+            }
+            Schema::Undefined => {
+                self.write_u8(6);
+                self.found_undefined = true;
+            }
+            Schema::ZeroSize => self.write_u8(7),
+            Schema::Custom(custom) => {
+                self.write_u8(8);
+                self.write_str(custom);
+            }
+            Schema::Boxed(item) => {
+                self.write_u8(9);
+                self.schema(item);
+            }
+            Schema::Slice(item) => {
+                self.write_u8(10);
+                self.schema(item);
+            }
+            Schema::Str => self.write_u8(11),
+            Schema::Reference(item) => {
+                self.write_u8(12);
+                self.schema(item);
+            }
+            Schema::Trait(mut_self, def) => {
+                self.write_u8(13);
+                self.write_bool(*mut_self);
+                self.abi_def(def);
+            }
+            Schema::FnClosure(mut_self, def) => {
+                self.write_u8(14);
+                self.write_bool(*mut_self);
+                self.abi_def(def);
+            }
+            Schema::Recursion(depth) => {
+                self.write_u8(15);
+                self.write_usize(*depth);
+            }
+            Schema::StdIoError => self.write_u8(16),
+            Schema::Future(def, send, sync, unpin) => {
+                self.write_u8(17);
+                self.abi_def(def);
+                self.write_bool(*send);
+                self.write_bool(*sync);
+                self.write_bool(*unpin);
+            }
+            Schema::UninitSlice => self.write_u8(18),
+            Schema::UtcTimestamp => self.write_u8(19),
+        }
+    }
+}
+
+impl Schema {
+    /// A stable 128 bit hash of the schema.
+    ///
+    /// Only the parts of the schema which matter for the disk format are included.
+    /// Names of structs and fields, as well as memory layout information, are not.
+    /// If two schemas have the same fingerprint, [diff_schema] will not find any
+    /// difference between them (barring hash collisions). The fingerprint is the
+    /// same on all platforms, and for all versions of savefile.
+    ///
+    /// The value 0 is reserved for schemas containing [Schema::Undefined], which
+    /// never match any other schema.
+    pub fn fingerprint(&self) -> u128 {
+        let mut fingerprinter = SchemaFingerprinter {
+            state: SCHEMA_FINGERPRINT_OFFSET_BASIS,
+            found_undefined: false,
+        };
+        fingerprinter.schema(self);
+        if fingerprinter.found_undefined {
+            0
+        } else {
+            fingerprinter.state.max(1)
+        }
+    }
+}
+
+fn diff_vector(a: &Schema, b: &Schema, path: String, allow_widening: bool) -> Option<String> {
+    diff_schema_impl(a, b, path + "/*", false, allow_widening)
+}
+
+fn diff_array(a: &SchemaArray, b: &SchemaArray, path: String, allow_widening: bool) -> Option<String> {
+    if a.count != b.count {
+        return Some(format!(
+            "At location [{}]: In memory array has length {}, but disk format length {}.",
+            path, a.count, b.count
+        ));
+    }
+
+    diff_schema_impl(
+        &a.item_type,
+        &b.item_type,
+        format!("{}/[{}]", path, a.count),
+        false,
+        allow_widening,
+    )
+}
+
+fn diff_option(a: &Schema, b: &Schema, path: String, allow_widening: bool) -> Option<String> {
+    diff_schema_impl(a, b, path + "/?", false, allow_widening)
+}
+
+fn diff_enum(a: &SchemaEnum, b: &SchemaEnum, path: String, allow_widening: bool) -> Option<String> {
+    let path = (path + &b.dbg_name).to_string();
+    if a.variants.len() != b.variants.len() {
+        return Some(format!(
+            "At location [{}]: In memory enum has {} variants, but disk format has {} variants.",
+            path,
+            a.variants.len(),
+            b.variants.len()
+        ));
+    }
+    if a.discriminant_size != b.discriminant_size {
+        return Some(format!(
+            "At location [{}]: In memory enum has a representation with {} bytes for the discriminant, but disk format has {}.",
+            path,
+            a.discriminant_size,
+            b.discriminant_size
+        ));
     }
     for i in 0..a.variants.len() {
         if a.variants[i].name != b.variants[i].name {
@@ -4007,6 +5637,7 @@ fn diff_enum(a: &SchemaEnum, b: &SchemaEnum, path: String) -> Option<String> {
             "enum",
             "",
             "",
+            allow_widening,
         );
         if let Some(err) = r {
             return Some(err);
@@ -4014,7 +5645,7 @@ fn diff_enum(a: &SchemaEnum, b: &SchemaEnum, path: String) -> Option<String> {
     }
     None
 }
-fn diff_struct(a: &SchemaStruct, b: &SchemaStruct, path: String) -> Option<String> {
+fn diff_struct(a: &SchemaStruct, b: &SchemaStruct, path: String, allow_widening: bool) -> Option<String> {
     diff_fields(
         &a.fields,
         &b.fields,
@@ -4022,6 +5653,7 @@ fn diff_struct(a: &SchemaStruct, b: &SchemaStruct, path: String) -> Option<Strin
         "struct",
         &(" (struct ".to_string() + &a.dbg_name + ")"),
         &(" (struct ".to_string() + &b.dbg_name + ")"),
+        allow_widening,
     )
 }
 fn diff_fields(
@@ -4031,6 +5663,7 @@ fn diff_fields(
     structuretype: &str,
     extra_a: &str,
     extra_b: &str,
+    allow_widening: bool,
 ) -> Option<String> {
     if a.len() != b.len() {
         return Some(format!(
@@ -4044,11 +5677,12 @@ fn diff_fields(
         ));
     }
     for i in 0..a.len() {
-        let r = diff_schema(
+        let r = diff_schema_impl(
             &a[i].value,
             &b[i].value,
             (path.to_string() + "/" + &b[i].name).to_string(),
             false,
+            allow_widening,
         );
         if let Some(err) = r {
             return Some(err);
@@ -4071,13 +5705,45 @@ fn diff_fields(
 /// a is the caller
 /// b is the callee
 pub fn diff_schema(a: &Schema, b: &Schema, path: String, is_return_pos: bool) -> Option<String> {
+    diff_schema_impl(a, b, path, is_return_pos, false)
+}
+
+/// Like [diff_schema], but also accepts lossless widening conversions from the
+/// disk format 'b' to the in-memory schema 'a'.
+///
+/// The accepted widenings are unsigned integer to wider unsigned integer,
+/// signed integer to wider signed integer and f32 to f64 (see
+/// [SchemaPrimitive::is_lossless_widening_of]). Note that `Vec<T>` and `VecDeque<T>`
+/// have the same schema, and are thus always interchangeable.
+///
+/// Data can only be converted if the schemas consist of structs, enums, primitives,
+/// vectors, arrays and options. Schemas containing anything else, such as recursive
+/// types, are only accepted if they are identical.
+///
+/// Returns None if data saved with schema 'b' can be loaded as 'a', possibly
+/// after converting some primitives.
+fn diff_schema_widening(a: &Schema, b: &Schema, path: String) -> Option<String> {
+    diff_schema_impl(a, b, path, false, true)
+}
+
+fn diff_schema_impl(a: &Schema, b: &Schema, path: String, is_return_pos: bool, allow_widening: bool) -> Option<String> {
+    if allow_widening && !transcode_widening_supports(a) {
+        let description = match a {
+            Schema::Recursion(_) => "recursive types".to_string(),
+            _ => a.top_level_description(),
+        };
+        return Some(format!(
+            "At location [{}]: Automatic widening conversion is not supported for data containing {}",
+            path, description
+        ));
+    }
     let (atype, btype) = match (a, b) {
-        (Schema::Struct(a), Schema::Struct(b)) => return diff_struct(a, b, path),
-        (Schema::Enum(a), Schema::Enum(b)) => return diff_enum(a, b, path),
-        (Schema::Primitive(a1), Schema::Primitive(b1)) => return diff_primitive(*a1, *b1, &path),
-        (Schema::Vector(a1, _a2), Schema::Vector(b1, _b2)) => return diff_vector(a1, b1, path),
+        (Schema::Struct(a), Schema::Struct(b)) => return diff_struct(a, b, path, allow_widening),
+        (Schema::Enum(a), Schema::Enum(b)) => return diff_enum(a, b, path, allow_widening),
+        (Schema::Primitive(a1), Schema::Primitive(b1)) => return diff_primitive(*a1, *b1, &path, allow_widening),
+        (Schema::Vector(a1, _a2), Schema::Vector(b1, _b2)) => return diff_vector(a1, b1, path, allow_widening),
         (Schema::SchemaOption(a), Schema::SchemaOption(b)) => {
-            return diff_option(a, b, path);
+            return diff_option(a, b, path, allow_widening);
         }
         (Schema::Undefined, Schema::Undefined) => {
             return Some(format!("At location [{}]: Undefined schema encountered.", path))
@@ -4085,7 +5751,7 @@ pub fn diff_schema(a: &Schema, b: &Schema, path: String, is_return_pos: bool) ->
         (Schema::ZeroSize, Schema::ZeroSize) => {
             return None;
         }
-        (Schema::Array(a), Schema::Array(b)) => return diff_array(a, b, path),
+        (Schema::Array(a), Schema::Array(b)) => return diff_array(a, b, path, allow_widening),
         (Schema::Custom(a), Schema::Custom(b)) => {
             if a != b {
                 return Some(format!(
@@ -4197,6 +5863,236 @@ fn diff_abi_def(a: &AbiTraitDefinition, b: &AbiTraitDefinition, path: String, is
     return None;
 }
 
+/// Returns true if [WideningReader] can convert data with the given top-level schema
+fn transcode_widening_supports(schema: &Schema) -> bool {
+    matches!(
+        schema,
+        Schema::Struct(_)
+            | Schema::Enum(_)
+            | Schema::Primitive(_)
+            | Schema::Vector(_, _)
+            | Schema::Array(_)
+            | Schema::SchemaOption(_)
+            | Schema::ZeroSize
+            | Schema::UtcTimestamp
+            | Schema::StdIoError
+    )
+}
+
+enum TranscodeStep<'s> {
+    Value(&'s Schema, &'s Schema),
+    Repeat(&'s Schema, &'s Schema, usize),
+}
+
+/// Reads data saved with the disk schema 'file' from 'src', converting
+/// it to the in-memory schema 'memory' on the way. The data is converted as it is read,
+/// a little at a time.
+///
+/// The schemas must already have been verified using [diff_schema_widening].
+struct WideningReader<'s, 'a, R: Read> {
+    src: Deserializer<'a, R>,
+    steps: Vec<TranscodeStep<'s>>,
+    buf: Vec<u8>,
+    pos: usize,
+    /// The error that caused reading to fail, if any
+    error: Option<SavefileError>,
+}
+
+impl<'s, 'a, R: Read> WideningReader<'s, 'a, R> {
+    fn new(memory: &'s Schema, file: &'s Schema, src: &'a mut R, file_version: u32) -> Self {
+        WideningReader {
+            src: Deserializer {
+                reader: src,
+                file_version,
+                ephemeral_state: HashMap::new(),
+            },
+            steps: vec![TranscodeStep::Value(memory, file)],
+            buf: Vec::new(),
+            pos: 0,
+            error: None,
+        }
+    }
+
+    fn step(&mut self, step: TranscodeStep<'s>) -> Result<(), SavefileError> {
+        let (memory, file) = match step {
+            TranscodeStep::Value(memory, file) => (memory, file),
+            TranscodeStep::Repeat(_, _, 0) => return Ok(()),
+            TranscodeStep::Repeat(memory, file, count) => {
+                self.steps.push(TranscodeStep::Repeat(memory, file, count - 1));
+                (memory, file)
+            }
+        };
+        let src = &mut self.src;
+        let dst = &mut Serializer {
+            writer: &mut self.buf,
+            file_version: src.file_version,
+        };
+        match (memory, file) {
+            (Schema::Struct(a), Schema::Struct(b)) => {
+                for (a_field, b_field) in a.fields.iter().zip(b.fields.iter()).rev() {
+                    self.steps.push(TranscodeStep::Value(&a_field.value, &b_field.value));
+                }
+            }
+            (Schema::Enum(a), Schema::Enum(b)) => {
+                let discriminant = match b.discriminant_size {
+                    1 => {
+                        let d = src.read_u8()?;
+                        dst.write_u8(d)?;
+                        d as u32
+                    }
+                    2 => {
+                        let d = src.read_u16()?;
+                        dst.write_u16(d)?;
+                        d as u32
+                    }
+                    4 => {
+                        let d = src.read_u32()?;
+                        dst.write_u32(d)?;
+                        d
+                    }
+                    _ => {
+                        return Err(SavefileError::GeneralError {
+                            msg: format!("Unsupported enum discriminant size: {}", b.discriminant_size),
+                        })
+                    }
+                };
+                let Some(index) = b.variants.iter().position(|x| x.discriminant == discriminant) else {
+                    return Err(SavefileError::GeneralError {
+                        msg: "Corrupt file - unknown enum variant detected.".to_string(),
+                    });
+                };
+                for (a_field, b_field) in a.variants[index]
+                    .fields
+                    .iter()
+                    .zip(b.variants[index].fields.iter())
+                    .rev()
+                {
+                    self.steps.push(TranscodeStep::Value(&a_field.value, &b_field.value));
+                }
+            }
+            (Schema::Primitive(a), Schema::Primitive(b)) => {
+                transcode_primitive(*a, *b, src, dst)?;
+            }
+            (Schema::Vector(a, _), Schema::Vector(b, _)) => {
+                let len = src.read_usize()?;
+                dst.write_usize(len)?;
+                self.steps.push(TranscodeStep::Repeat(a, b, len));
+            }
+            (Schema::Array(a), Schema::Array(b)) => {
+                self.steps
+                    .push(TranscodeStep::Repeat(&a.item_type, &b.item_type, b.count));
+            }
+            (Schema::SchemaOption(a), Schema::SchemaOption(b)) => {
+                let is_some = src.read_bool()?;
+                dst.write_bool(is_some)?;
+                if is_some {
+                    self.steps.push(TranscodeStep::Value(a, b));
+                }
+            }
+            (Schema::ZeroSize, Schema::ZeroSize) => {}
+            (Schema::UtcTimestamp, Schema::UtcTimestamp) => {
+                dst.write_i64(src.read_i64()?)?;
+            }
+            (Schema::StdIoError, Schema::StdIoError) => {
+                dst.write_u16(src.read_u16()?)?;
+                dst.write_string(&src.read_string()?)?;
+            }
+            (a, _) => {
+                return Err(SavefileError::GeneralError {
+                    msg: format!(
+                        "Automatic widening conversion is not supported for data containing {}",
+                        a.top_level_description()
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for WideningReader<'_, '_, R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+            let Some(step) = self.steps.pop() else {
+                return Ok(0);
+            };
+            if let Err(err) = self.step(step) {
+                let io_error = std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string());
+                self.error = Some(err);
+                return Err(io_error);
+            }
+        }
+        let count = out.len().min(self.buf.len() - self.pos);
+        out[..count].copy_from_slice(&self.buf[self.pos..self.pos + count]);
+        self.pos += count;
+        Ok(count)
+    }
+}
+
+fn transcode_primitive(
+    memory: SchemaPrimitive,
+    file: SchemaPrimitive,
+    src: &mut Deserializer<impl Read>,
+    dst: &mut Serializer<impl Write>,
+) -> Result<(), SavefileError> {
+    use SchemaPrimitive::*;
+    match (file, memory) {
+        (schema_string(_), _) => {
+            dst.write_string(&src.read_string()?)?;
+        }
+        (schema_f32, schema_f64) => {
+            dst.write_f64(src.read_f32()? as f64)?;
+        }
+        (schema_bigint, schema_bigint) | (schema_biguint, schema_biguint) => {
+            let len = src.read_usize()?;
+            dst.write_usize(len)?;
+            dst.write_bytes(&src.read_bytes(len)?)?;
+        }
+        (file, memory) if file == memory => {
+            let mut buf = [0u8; 16];
+            let size = file.serialized_size().expect("primitives have a fixed size");
+            src.read_bytes_to_buf(&mut buf[..size])?;
+            dst.write_buf(&buf[..size])?;
+        }
+        (schema_u8 | schema_u16 | schema_u32 | schema_u64, _) => {
+            let value: u128 = match file {
+                schema_u8 => src.read_u8()? as u128,
+                schema_u16 => src.read_u16()? as u128,
+                schema_u32 => src.read_u32()? as u128,
+                _ => src.read_u64()? as u128,
+            };
+            match memory {
+                schema_u16 => dst.write_u16(value as u16)?,
+                schema_u32 => dst.write_u32(value as u32)?,
+                schema_u64 => dst.write_u64(value as u64)?,
+                _ => dst.write_u128(value)?,
+            }
+        }
+        (schema_i8 | schema_i16 | schema_i32 | schema_i64, _) => {
+            let value: i128 = match file {
+                schema_i8 => src.read_i8()? as i128,
+                schema_i16 => src.read_i16()? as i128,
+                schema_i32 => src.read_i32()? as i128,
+                _ => src.read_i64()? as i128,
+            };
+            match memory {
+                schema_i16 => dst.write_i16(value as i16)?,
+                schema_i32 => dst.write_i32(value as i32)?,
+                schema_i64 => dst.write_i64(value as i64)?,
+                _ => dst.write_i128(value)?,
+            }
+        }
+        (file, memory) => {
+            return Err(SavefileError::GeneralError {
+                msg: format!("Cannot convert {} to {}", file.name(), memory.name()),
+            });
+        }
+    }
+    Ok(())
+}
+
 impl WithSchema for Field {
     fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
         Schema::Undefined
@@ -4233,7 +6129,12 @@ impl WithSchema for Variant {
 impl Serialize for Variant {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
         serializer.write_string(&self.name)?;
-        serializer.write_u8(self.discriminant)?;
+        if serializer.file_version >= 3 {
+            serializer.write_u32(self.discriminant)?;
+        } else {
+            // Older formats truncate the discriminant. See SchemaEnum::deserialize.
+            serializer.write_u8(self.discriminant as u8)?;
+        }
         serializer.write_usize(self.fields.len())?;
         for field in &self.fields {
             field.serialize(serializer)?;
@@ -4247,7 +6148,11 @@ impl Deserialize for Variant {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
         Ok(Variant {
             name: deserializer.read_string()?,
-            discriminant: deserializer.read_u8()?,
+            discriminant: if deserializer.file_version >= 3 {
+                deserializer.read_u32()?
+            } else {
+                deserializer.read_u8()? as u32
+            },
             fields: {
                 let l = deserializer.read_usize()?;
                 let mut ret = Vec::new();
@@ -4356,6 +6261,17 @@ impl Serialize for SchemaPrimitive {
             SchemaPrimitive::schema_i128 => 14,
             SchemaPrimitive::schema_u128 => 15,
             SchemaPrimitive::schema_char => 16,
+            SchemaPrimitive::schema_date => 17,
+            SchemaPrimitive::schema_time => 18,
+            SchemaPrimitive::schema_datetime => 19,
+            SchemaPrimitive::schema_datetime_offset => 20,
+            SchemaPrimitive::schema_duration => 21,
+            SchemaPrimitive::schema_f16 => 22,
+            SchemaPrimitive::schema_bf16 => 23,
+            SchemaPrimitive::schema_decimal => 24,
+            SchemaPrimitive::schema_bigint => 25,
+            SchemaPrimitive::schema_biguint => 26,
+            SchemaPrimitive::schema_uuid => 27,
             SchemaPrimitive::schema_string(layout) => {
                 serializer.write_u8(9)?;
                 if serializer.file_version > 0 {
@@ -4413,6 +6329,17 @@ impl Deserialize for SchemaPrimitive {
             14 => SchemaPrimitive::schema_i128,
             15 => SchemaPrimitive::schema_u128,
             16 => SchemaPrimitive::schema_char,
+            17 => SchemaPrimitive::schema_date,
+            18 => SchemaPrimitive::schema_time,
+            19 => SchemaPrimitive::schema_datetime,
+            20 => SchemaPrimitive::schema_datetime_offset,
+            21 => SchemaPrimitive::schema_duration,
+            22 => SchemaPrimitive::schema_f16,
+            23 => SchemaPrimitive::schema_bf16,
+            24 => SchemaPrimitive::schema_decimal,
+            25 => SchemaPrimitive::schema_bigint,
+            26 => SchemaPrimitive::schema_biguint,
+            27 => SchemaPrimitive::schema_uuid,
             c => {
                 return Err(SavefileError::GeneralError {
                     msg: format!(
@@ -4455,6 +6382,20 @@ impl Deserialize for SchemaEnum {
         for _ in 0..l {
             ret.push(Variant::deserialize(deserializer)?);
         }
+        if deserializer.file_version < 3 && ret.len() > 256 {
+            // Older formats stored the discriminant as an u8, wrapping around for enums with
+            // more than 256 variants. The derived discriminants are always increasing,
+            // so the actual values can be recovered.
+            let mut prev: Option<u32> = None;
+            for variant in ret.iter_mut() {
+                if let Some(prev) = prev {
+                    while variant.discriminant <= prev {
+                        variant.discriminant += 256;
+                    }
+                }
+                prev = Some(variant.discriminant);
+            }
+        }
         let (discriminant_size, has_explicit_repr, size, alignment) = if deserializer.file_version > 0 {
             (
                 u8::deserialize(deserializer)?,
@@ -4499,7 +6440,7 @@ impl Arbitrary for VecOrStringLayout {
 impl Arbitrary for SchemaPrimitive {
     fn arbitrary(g: &mut Gen) -> Self {
         let x = u8::arbitrary(g);
-        match x % 16 {
+        match x % 27 {
             0 => SchemaPrimitive::schema_i8,
             1 => SchemaPrimitive::schema_u8,
             2 => SchemaPrimitive::schema_i16,
@@ -4516,6 +6457,17 @@ impl Arbitrary for SchemaPrimitive {
             13 => SchemaPrimitive::schema_u128,
             14 => SchemaPrimitive::schema_i128,
             15 => SchemaPrimitive::schema_char,
+            16 => SchemaPrimitive::schema_date,
+            17 => SchemaPrimitive::schema_time,
+            18 => SchemaPrimitive::schema_datetime,
+            19 => SchemaPrimitive::schema_datetime_offset,
+            20 => SchemaPrimitive::schema_duration,
+            21 => SchemaPrimitive::schema_f16,
+            22 => SchemaPrimitive::schema_bf16,
+            23 => SchemaPrimitive::schema_decimal,
+            24 => SchemaPrimitive::schema_bigint,
+            25 => SchemaPrimitive::schema_biguint,
+            26 => SchemaPrimitive::schema_uuid,
             _ => unreachable!(),
         }
     }
@@ -4799,6 +6751,12 @@ impl Introspect for String {
         None
     }
 }
+impl IntrospectMut for String {
+    fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+        value.clone_into(self);
+        Ok(())
+    }
+}
 impl Serialize for String {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
         serializer.write_string(self)
@@ -4843,6 +6801,10 @@ impl<T: Introspect> Introspect for Mutex<T> {
         format!("Mutex<{}>", std::any::type_name::<T>())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             Some(Box::new(IntrospectItemMutex { g: self.lock() }))
@@ -4872,6 +6834,10 @@ impl<T: Introspect> Introspect for std::sync::Mutex<T> {
         format!("Mutex<{}>", std::any::type_name::<T>())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         match self.lock() {
             Ok(item) => {
@@ -4952,6 +6918,12 @@ impl<T: Introspect> Introspect for std::cell::Ref<'_, T> {
         let sub_value = (**self).introspect_value();
         format!("Ref({})", sub_value)
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        (**self).introspect_typed_value()
+    }
+    fn introspect_kind(&self) -> IntrospectKind {
+        (**self).introspect_kind()
+    }
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         (**self).introspect_child(index)
     }
@@ -4976,6 +6948,10 @@ impl<T: Introspect> Introspect for RefCell<T> {
         format!("RefCell({})", sub_value)
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.borrow().introspect_typed_value()
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         // Introspect not supported
         if index != 0 {
@@ -4996,6 +6972,14 @@ impl<T: Introspect> Introspect for Rc<T> {
         format!("Rc({})", self.deref().introspect_value())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.deref().introspect_typed_value()
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        self.deref().introspect_kind()
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         self.deref().introspect_child(index)
     }
@@ -5010,6 +6994,14 @@ impl<T: Introspect> Introspect for Arc<T> {
         format!("Arc({})", self.deref().introspect_value())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.deref().introspect_typed_value()
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        self.deref().introspect_kind()
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         self.deref().introspect_child(index)
     }
@@ -5023,6 +7015,9 @@ impl<T: Introspect> Introspect for RwLock<T> {
     fn introspect_value(&self) -> String {
         format!("RwLock<{}>", std::any::type_name::<T>())
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             Some(Box::new(IntrospectItemRwLock { g: self.read() }))
@@ -5082,12 +7077,146 @@ pub fn introspect_item<'a>(key: String, val: &'a dyn Introspect) -> Box<dyn Intr
     Box::new(IntrospectItemSimple { key, val })
 }
 
+/// Standard child for IntrospectMut trait. Simply owned key string and mutable reference to dyn IntrospectMut
+pub struct IntrospectItemMutSimple<'a> {
+    key: String,
+    val: &'a mut dyn IntrospectMut,
+}
+
+impl<'a> IntrospectItemMut<'a> for IntrospectItemMutSimple<'a> {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn val_mut(&mut self) -> &mut dyn IntrospectMut {
+        self.val
+    }
+}
+
+/// Create a default IntrospectItemMut with the given key and IntrospectMut.
+pub fn introspect_item_mut<'a>(key: String, val: &'a mut dyn IntrospectMut) -> Box<dyn IntrospectItemMut<'a> + 'a> {
+    Box::new(IntrospectItemMutSimple { key, val })
+}
+
+/// A read only view of an introspected object, used for children which
+/// don't implement IntrospectMut.
+struct IntrospectReadOnly<'a>(&'a dyn Introspect);
+
+impl Introspect for IntrospectReadOnly<'_> {
+    fn introspect_value(&self) -> String {
+        self.0.introspect_value()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.0.introspect_typed_value()
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        self.0.introspect_kind()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        self.0.introspect_child(index)
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.0.introspect_len()
+    }
+}
+impl IntrospectMut for IntrospectReadOnly<'_> {}
+
+struct IntrospectItemReadOnly<'a> {
+    key: String,
+    val: IntrospectReadOnly<'a>,
+}
+
+impl<'a> IntrospectItemMut<'a> for IntrospectItemReadOnly<'a> {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn val_mut(&mut self) -> &mut dyn IntrospectMut {
+        &mut self.val
+    }
+}
+
+/// Used by the derive macro to create the mutable child for a field, regardless
+/// of whether the type of the field implements IntrospectMut or only Introspect.
+///
+/// Fields are wrapped like `(&&IntrospectMutField::new(&field)).introspect_field_mut(key, &mut field)`.
+/// Method resolution then picks [IntrospectMutFieldEditable] if the field implements
+/// IntrospectMut, and [IntrospectMutFieldReadOnly] otherwise.
+#[doc(hidden)]
+pub struct IntrospectMutField<T>(PhantomData<T>);
+
+impl<T> IntrospectMutField<T> {
+    #[doc(hidden)]
+    pub fn new(_field: &T) -> IntrospectMutField<T> {
+        IntrospectMutField(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait IntrospectMutFieldEditable {
+    type Field;
+    fn introspect_field_mut<'a>(&self, key: String, val: &'a mut Self::Field) -> Box<dyn IntrospectItemMut<'a> + 'a>;
+}
+impl<T: IntrospectMut> IntrospectMutFieldEditable for &IntrospectMutField<T> {
+    type Field = T;
+    fn introspect_field_mut<'a>(&self, key: String, val: &'a mut T) -> Box<dyn IntrospectItemMut<'a> + 'a> {
+        introspect_item_mut(key, val)
+    }
+}
+
+#[doc(hidden)]
+pub trait IntrospectMutFieldReadOnly {
+    type Field;
+    fn introspect_field_mut<'a>(&self, key: String, val: &'a mut Self::Field) -> Box<dyn IntrospectItemMut<'a> + 'a>;
+}
+impl<T: Introspect> IntrospectMutFieldReadOnly for IntrospectMutField<T> {
+    type Field = T;
+    fn introspect_field_mut<'a>(&self, key: String, val: &'a mut T) -> Box<dyn IntrospectItemMut<'a> + 'a> {
+        Box::new(IntrospectItemReadOnly {
+            key,
+            val: IntrospectReadOnly(val),
+        })
+    }
+}
+
+/// Implements [Introspect::introspect_children_range] for maps which are introspected
+/// as alternating "Key #n" and "Value #n" children.
+fn introspect_map_children_range<'a, K: Introspect + 'a, V: Introspect + 'a>(
+    iter: impl Iterator<Item = (&'a K, &'a V)>,
+    start: usize,
+    count: usize,
+) -> Vec<Box<dyn IntrospectItem<'a> + 'a>> {
+    let end = start.saturating_add(count);
+    let mut result = Vec::new();
+    for (bucket, (key, val)) in iter.enumerate().skip(start / 2) {
+        let index = 2 * bucket;
+        if index >= end {
+            break;
+        }
+        if index >= start {
+            result.push(introspect_item(format!("Key #{}", index), key));
+        }
+        if index + 1 < end {
+            result.push(introspect_item(format!("Value #{}", index + 1), val));
+        }
+    }
+    result
+}
+
 #[cfg(not(feature = "nightly"))]
 impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Introspect for HashMap<K, V, S> {
     fn introspect_value(&self) -> String {
         format!("HashMap<{},{}>", std::any::type_name::<K>(), std::any::type_name::<V>())
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Map
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         let bucket = index / 2;
         let off = index % 2;
@@ -5101,8 +7230,11 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
             None
         }
     }
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        introspect_map_children_range(self.iter(), start, count)
+    }
     fn introspect_len(&self) -> usize {
-        self.len()
+        2 * self.len()
     }
 }
 
@@ -5112,6 +7244,10 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
         format!("HashMap<{},{}>", std::any::type_name::<K>(), std::any::type_name::<V>())
     }
 
+    default fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Map
+    }
+
     default fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         let bucket = index / 2;
         let off = index % 2;
@@ -5125,8 +7261,11 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
             None
         }
     }
+    default fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        introspect_map_children_range(self.iter(), start, count)
+    }
     default fn introspect_len(&self) -> usize {
-        self.len()
+        2 * self.len()
     }
 }
 
@@ -5139,6 +7278,10 @@ where
         format!("HashMap<{},{}>", std::any::type_name::<K>(), std::any::type_name::<V>())
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some((key, val)) = self.iter().nth(index) {
             Some(introspect_item(key.to_string(), val))
@@ -5146,6 +7289,13 @@ where
             None
         }
     }
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .skip(start)
+            .take(count)
+            .map(|(key, val)| introspect_item(key.to_string(), val))
+            .collect()
+    }
     fn introspect_len(&self) -> usize {
         self.len()
     }
@@ -5156,6 +7306,10 @@ impl<K: Introspect + Eq + Hash, S: ::std::hash::BuildHasher> Introspect for Hash
         format!("HashSet<{}>", std::any::type_name::<K>())
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some(key) = self.iter().nth(index) {
             Some(introspect_item(format!("#{}", index), key))
@@ -5163,6 +7317,14 @@ impl<K: Introspect + Eq + Hash, S: ::std::hash::BuildHasher> Introspect for Hash
             None
         }
     }
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .map(|(index, key)| introspect_item(format!("#{}", index), key))
+            .collect()
+    }
     fn introspect_len(&self) -> usize {
         self.len()
     }
@@ -5173,6 +7335,10 @@ impl<K: Introspect> Introspect for BTreeSet<K> {
         format!("BTreeSet<{}>", std::any::type_name::<K>())
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some(key) = self.iter().nth(index) {
             Some(introspect_item(format!("#{}", index), key))
@@ -5180,6 +7346,14 @@ impl<K: Introspect> Introspect for BTreeSet<K> {
             None
         }
     }
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .map(|(index, key)| introspect_item(format!("#{}", index), key))
+            .collect()
+    }
     fn introspect_len(&self) -> usize {
         self.len()
     }
@@ -5194,6 +7368,10 @@ impl<K: Introspect, V: Introspect> Introspect for BTreeMap<K, V> {
         )
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Map
+    }
+
     // This has very bad performance. But with the model behind Savefile Introspect it
     // is presently hard to do much better
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
@@ -5209,8 +7387,11 @@ impl<K: Introspect, V: Introspect> Introspect for BTreeMap<K, V> {
             None
         }
     }
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        introspect_map_children_range(self.iter(), start, count)
+    }
     fn introspect_len(&self) -> usize {
-        self.len()
+        2 * self.len()
     }
 }
 
@@ -5406,6 +7587,10 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
         )
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Map
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         let bucket = index / 2;
         let off = index % 2;
@@ -5421,7 +7606,7 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
     }
 
     fn introspect_len(&self) -> usize {
-        self.len()
+        2 * self.len()
     }
 }
 
@@ -5435,6 +7620,10 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
         )
     }
 
+    default fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Map
+    }
+
     default fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         let bucket = index / 2;
         let off = index % 2;
@@ -5450,7 +7639,7 @@ impl<K: Introspect + Eq + Hash, V: Introspect, S: ::std::hash::BuildHasher> Intr
     }
 
     default fn introspect_len(&self) -> usize {
-        self.len()
+        2 * self.len()
     }
 }
 
@@ -5467,7 +7656,11 @@ where
         )
     }
 
-    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some((k, v)) = self.get_index(index) {
             Some(introspect_item(k.to_string(), v))
         } else {
@@ -5512,6 +7705,10 @@ impl<K: Introspect + Eq + Hash, S: ::std::hash::BuildHasher> Introspect for Inde
         format!("IndexSet<{}>", std::any::type_name::<K>())
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some(val) = self.get_index(index) {
             Some(introspect_item(format!("#{}", index), val))
@@ -5657,6 +7854,10 @@ impl<T: Introspect> Introspect for Removed<T> {
         format!("Removed<{}>", std::any::type_name::<T>())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -5724,6 +7925,10 @@ impl<T: Introspect, D: ValueConstructor<T>> Introspect for AbiRemoved<T, D> {
         format!("AbiRemoved<{}>", std::any::type_name::<T>())
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -5749,11 +7954,148 @@ impl<T: WithSchema + Deserialize, D: ValueConstructor<T>> Deserialize for AbiRem
     }
 }
 
+/// Implemented by types annotated with the `#[savefile_remote="path::to::Type"]`
+/// attribute, describing how to save the foreign type `T`.
+///
+/// This is normally not implemented manually. Use the [Remote] wrapper
+/// to store values of `T`.
+pub trait RemoteMirror<T> {
+    /// The schema of `T`, see [WithSchema::schema].
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema;
+    /// Serialize the given value, see [Serialize::serialize].
+    fn serialize(value: &T, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError>;
+    /// Deserialize a value, see [Deserialize::deserialize].
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<T, SavefileError>;
+    /// See [Introspect::introspect_value].
+    fn introspect_value(_value: &T) -> String {
+        std::any::type_name::<T>().to_string()
+    }
+    /// See [Introspect::introspect_child].
+    fn introspect_child(_value: &T, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+    /// See [Introspect::introspect_len].
+    fn introspect_len(_value: &T) -> usize {
+        0
+    }
+}
+
+/// Wrapper which allows a type `T` from another crate to be saved, using
+/// the mirror type `M` (see [RemoteMirror]).
+///
+/// Remote derefs to `T`, and has the same memory layout as `T`.
+/// The wrapped value has no public field name, so that it doesn't shadow
+/// the fields of `T`.
+#[repr(transparent)]
+pub struct Remote<T, M> {
+    value: T,
+    phantom: PhantomData<fn() -> M>,
+}
+
+impl<T, M> Remote<T, M> {
+    /// Wrap the given value
+    pub fn new(value: T) -> Remote<T, M> {
+        Remote {
+            value,
+            phantom: PhantomData,
+        }
+    }
+    /// Return the wrapped value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, M> From<T> for Remote<T, M> {
+    fn from(value: T) -> Self {
+        Remote::new(value)
+    }
+}
+
+impl<T, M> Deref for Remote<T, M> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, M> std::ops::DerefMut for Remote<T, M> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Clone, M> Clone for Remote<T, M> {
+    fn clone(&self) -> Self {
+        Remote::new(self.value.clone())
+    }
+}
+impl<T: Copy, M> Copy for Remote<T, M> {}
+
+impl<T: std::fmt::Debug, M> std::fmt::Debug for Remote<T, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: Default, M> Default for Remote<T, M> {
+    fn default() -> Self {
+        Remote::new(T::default())
+    }
+}
+
+impl<T: PartialEq, M> PartialEq for Remote<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+impl<T: Eq, M> Eq for Remote<T, M> {}
+
+impl<T: Hash, M> Hash for Remote<T, M> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<T, M: RemoteMirror<T>> WithSchema for Remote<T, M> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        M::schema(version, context)
+    }
+}
+impl<T, M> Packed for Remote<T, M> {}
+impl<T, M: RemoteMirror<T>> Serialize for Remote<T, M> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        M::serialize(&self.value, serializer)
+    }
+}
+impl<T, M: RemoteMirror<T>> Deserialize for Remote<T, M> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Remote::new(M::deserialize(deserializer)?))
+    }
+}
+impl<T, M: RemoteMirror<T>> Introspect for Remote<T, M> {
+    fn introspect_value(&self) -> String {
+        M::introspect_value(&self.value)
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        M::introspect_child(&self.value, index)
+    }
+
+    fn introspect_len(&self) -> usize {
+        M::introspect_len(&self.value)
+    }
+}
+
 impl<T> Introspect for PhantomData<T> {
     fn introspect_value(&self) -> String {
         "PhantomData".to_string()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -5783,6 +8125,12 @@ impl<T: Introspect> Introspect for Box<T> {
     fn introspect_value(&self) -> String {
         self.deref().introspect_value()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.deref().introspect_typed_value()
+    }
+    fn introspect_kind(&self) -> IntrospectKind {
+        self.deref().introspect_kind()
+    }
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         self.deref().introspect_child(index)
     }
@@ -5790,6 +8138,14 @@ impl<T: Introspect> Introspect for Box<T> {
         self.deref().introspect_len()
     }
 }
+impl<T: IntrospectMut> IntrospectMut for Box<T> {
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        self.deref_mut().introspect_child_mut(index)
+    }
+    fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+        self.deref_mut().set_from_str(value)
+    }
+}
 impl<T: Introspect> Introspect for Option<T> {
     fn introspect_value(&self) -> String {
         if let Some(cont) = self {
@@ -5799,6 +8155,20 @@ impl<T: Introspect> Introspect for Option<T> {
         }
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        match self {
+            Some(cont) => cont.introspect_typed_value(),
+            None => IntrospectValue::None,
+        }
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        match self {
+            Some(cont) => cont.introspect_kind(),
+            None => IntrospectKind::Value,
+        }
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if let Some(cont) = self {
             cont.introspect_child(index)
@@ -5815,6 +8185,31 @@ impl<T: Introspect> Introspect for Option<T> {
     }
 }
 
+// Setting a value on None creates a default value and then sets it, which is why T: Default
+// is required. The value can be given either as 'Some(x)' or just 'x'.
+impl<T: IntrospectMut + Default> IntrospectMut for Option<T> {
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        self.as_mut()?.introspect_child_mut(index)
+    }
+    fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+        if value == "None" {
+            *self = None;
+            return Ok(());
+        }
+        let inner = value
+            .strip_prefix("Some(")
+            .and_then(|x| x.strip_suffix(')'))
+            .unwrap_or(value);
+        if let Some(cont) = self {
+            return cont.set_from_str(inner);
+        }
+        let mut temp = T::default();
+        temp.set_from_str(inner)?;
+        *self = Some(temp);
+        Ok(())
+    }
+}
+
 impl<T: WithSchema> WithSchema for Option<T> {
     fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
         Schema::SchemaOption(Box::new(T::schema(version, context)))
@@ -6099,6 +8494,13 @@ impl Introspect for chrono::DateTime<chrono::Utc> {
         self.to_string()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Timestamp {
+            secs: self.timestamp(),
+            nanos: self.timestamp_subsec_nanos(),
+        }
+    }
+
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
@@ -6131,885 +8533,3207 @@ impl Deserialize for chrono::DateTime<chrono::Utc> {
     }
 }
 
-#[cfg(any(feature = "bit-vec", feature = "bit-vec08"))]
-#[cfg(target_endian = "big")]
-compile_error!("savefile bit-vec feature does not support big-endian machines");
+#[cfg(feature = "chrono")]
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
 
-#[cfg(feature = "bit-vec")]
-impl WithSchema for bit_vec::BitVec {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Struct(SchemaStruct {
-            dbg_name: "BitVec".to_string(),
-            size: None,
-            alignment: None,
-            fields: vec![
-                Field {
-                    name: "num_bits".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "num_bytes".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "buffer".to_string(),
-                    value: Box::new(Schema::Vector(
-                        Box::new(u8::schema(version, context)),
-                        VecOrStringLayout::Unknown,
-                    )),
-                    offset: None,
-                },
-            ],
-        })
-    }
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn write_seconds_nanos(serializer: &mut Serializer<impl Write>, secs: i64, nanos: u32) -> Result<(), SavefileError> {
+    serializer.write_i64(secs)?;
+    serializer.write_u32(nanos)
 }
 
-#[cfg(feature = "bit-vec")]
-impl Introspect for bit_vec::BitVec {
-    fn introspect_value(&self) -> String {
-        let mut ret = String::new();
-        for i in 0..self.len() {
-            if self[i] {
-                ret.push('1');
-            } else {
-                ret.push('0');
-            }
-        }
-        ret
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn date_time_out_of_range<T>() -> SavefileError {
+    SavefileError::DateTimeOutOfRange {
+        type_name: std::any::type_name::<T>(),
     }
+}
 
-    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        None
-    }
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn read_seconds_nanos(deserializer: &mut Deserializer<impl Read>) -> Result<(i64, u32), SavefileError> {
+    let secs = deserializer.read_i64()?;
+    let nanos = deserializer.read_u32()?;
+    Ok((secs, nanos))
 }
 
-#[cfg(feature = "bit-vec")]
-impl Serialize for bit_vec::BitVec<u32> {
-    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let l = self.len();
-        serializer.write_usize(l)?;
-        let storage = self.storage();
-        let rawbytes_ptr = storage.as_ptr() as *const u8;
-        let rawbytes: &[u8] = unsafe { std::slice::from_raw_parts(rawbytes_ptr, 4 * storage.len()) };
-        serializer.write_usize(rawbytes.len() | (1 << 63))?;
-        serializer.write_bytes(rawbytes)?;
-        Ok(())
+#[cfg(feature = "chrono")]
+impl Packed for chrono::NaiveDate {}
+#[cfg(feature = "chrono")]
+impl WithSchema for chrono::NaiveDate {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_date)
     }
 }
-
-#[cfg(feature = "bit-vec")]
-impl Packed for bit_vec::BitVec<u32> {}
-
-#[cfg(feature = "bit-vec")]
-impl Deserialize for bit_vec::BitVec<u32> {
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let numbits = deserializer.read_usize()?;
-        let mut numbytes = deserializer.read_usize()?;
-        if numbytes & (1 << 63) != 0 {
-            //New format
-            numbytes &= !(1 << 63);
-            let mut ret = bit_vec::BitVec::with_capacity(numbytes * 8);
-            unsafe {
-                let num_words = numbytes / 4;
-                let storage = ret.storage_mut();
-                storage.resize(num_words, 0);
-                let storage_ptr = storage.as_ptr() as *mut u8;
-                let storage_bytes: &mut [u8] = std::slice::from_raw_parts_mut(storage_ptr, 4 * num_words);
-                deserializer.read_bytes_to_buf(storage_bytes)?;
-                ret.set_len(numbits);
-            }
-            Ok(ret)
-        } else {
-            let bytes = deserializer.read_bytes(numbytes)?;
-            let mut ret = bit_vec::BitVec::from_bytes(&bytes);
-            ret.truncate(numbits);
-            Ok(ret)
-        }
+#[cfg(feature = "chrono")]
+impl Serialize for chrono::NaiveDate {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        use chrono::Datelike;
+        serializer.write_i32(self.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE)
     }
 }
-
-#[cfg(feature = "bit-set")]
-impl WithSchema for bit_set::BitSet {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Struct(SchemaStruct {
-            dbg_name: "BitSet".to_string(),
-            size: None,
-            alignment: None,
-            fields: vec![
-                Field {
-                    name: "num_bits".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "num_bytes".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "buffer".to_string(),
-                    value: Box::new(Schema::Vector(
-                        Box::new(u8::schema(version, context)),
-                        VecOrStringLayout::Unknown,
-                    )),
-                    offset: None,
-                },
-            ],
-        })
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::NaiveDate {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let days = deserializer.read_i32()?;
+        days.checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+            .ok_or_else(date_time_out_of_range::<Self>)
     }
 }
-
-#[cfg(feature = "bit-set")]
-impl Introspect for bit_set::BitSet {
+#[cfg(feature = "chrono")]
+impl Introspect for chrono::NaiveDate {
     fn introspect_value(&self) -> String {
-        let mut ret = String::new();
-        for i in 0..self.len() {
-            if self.contains(i) {
-                use std::fmt::Write;
-                if !ret.is_empty() {
-                    ret += " ";
-                }
-                write!(&mut ret, "{}", i).unwrap();
-            }
-        }
-        ret
+        self.to_string()
     }
 
-    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
 }
 
-#[cfg(feature = "bit-set")]
-impl Packed for bit_set::BitSet<u32> {}
-
-#[cfg(feature = "bit-set")]
-impl Serialize for bit_set::BitSet<u32> {
+#[cfg(feature = "chrono")]
+impl Packed for chrono::NaiveTime {}
+#[cfg(feature = "chrono")]
+impl WithSchema for chrono::NaiveTime {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_time)
+    }
+}
+#[cfg(feature = "chrono")]
+impl Serialize for chrono::NaiveTime {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let bitset = self.get_ref();
-        bitset.serialize(serializer)
+        use chrono::Timelike;
+        serializer.write_u32(self.num_seconds_from_midnight())?;
+        serializer.write_u32(self.nanosecond())
     }
 }
-
-#[cfg(feature = "bit-set")]
-impl Deserialize for bit_set::BitSet<u32> {
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::NaiveTime {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let bit_vec: bit_vec::BitVec = bit_vec::BitVec::deserialize(deserializer)?;
-        Ok(bit_set::BitSet::from_bit_vec(bit_vec))
-    }
-}
-
-#[cfg(feature = "bit-vec08")]
-impl WithSchema for bit_vec08::BitVec {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Struct(SchemaStruct {
-            dbg_name: "BitVec".to_string(),
-            size: None,
-            alignment: None,
-            fields: vec![
-                Field {
-                    name: "num_bits".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "num_bytes".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "buffer".to_string(),
-                    value: Box::new(Schema::Vector(
-                        Box::new(u8::schema(version, context)),
-                        VecOrStringLayout::Unknown,
-                    )),
-                    offset: None,
-                },
-            ],
-        })
+        let secs = deserializer.read_u32()?;
+        let nanos = deserializer.read_u32()?;
+        chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or_else(date_time_out_of_range::<Self>)
     }
 }
-
-#[cfg(feature = "bit-vec08")]
-impl Introspect for bit_vec08::BitVec {
+#[cfg(feature = "chrono")]
+impl Introspect for chrono::NaiveTime {
     fn introspect_value(&self) -> String {
-        let mut ret = String::new();
-        for i in 0..self.len() {
-            if self[i] {
-                ret.push('1');
-            } else {
-                ret.push('0');
-            }
-        }
-        ret
+        self.to_string()
     }
 
-    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
 }
 
-#[cfg(feature = "bit-vec08")]
-impl Serialize for bit_vec08::BitVec<u32> {
+#[cfg(feature = "chrono")]
+impl Packed for chrono::NaiveDateTime {}
+#[cfg(feature = "chrono")]
+impl WithSchema for chrono::NaiveDateTime {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_datetime)
+    }
+}
+#[cfg(feature = "chrono")]
+impl Serialize for chrono::NaiveDateTime {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let l = self.len();
-        serializer.write_usize(l)?;
-        let storage = self.storage();
-        let rawbytes_ptr = storage.as_ptr() as *const u8;
-        let rawbytes: &[u8] = unsafe { std::slice::from_raw_parts(rawbytes_ptr, 4 * storage.len()) };
-        serializer.write_usize(rawbytes.len() | (1 << 63))?;
-        serializer.write_bytes(rawbytes)?;
-        Ok(())
+        let utc = self.and_utc();
+        write_seconds_nanos(serializer, utc.timestamp(), utc.timestamp_subsec_nanos())
     }
 }
-
-#[cfg(feature = "bit-vec08")]
-impl Packed for bit_vec08::BitVec<u32> {}
-
-#[cfg(feature = "bit-vec08")]
-impl Deserialize for bit_vec08::BitVec<u32> {
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::NaiveDateTime {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let numbits = deserializer.read_usize()?;
-        let mut numbytes = deserializer.read_usize()?;
-        if numbytes & (1 << 63) != 0 {
-            //New format
-            numbytes &= !(1 << 63);
-            let mut ret = bit_vec08::BitVec::with_capacity(numbytes * 8);
-            unsafe {
-                let num_words = numbytes / 4;
-                let storage = ret.storage_mut();
-                storage.resize(num_words, 0);
-                let storage_ptr = storage.as_ptr() as *mut u8;
-                let storage_bytes: &mut [u8] = std::slice::from_raw_parts_mut(storage_ptr, 4 * num_words);
-                deserializer.read_bytes_to_buf(storage_bytes)?;
-                ret.set_len(numbits);
-            }
-            Ok(ret)
-        } else {
-            let bytes = deserializer.read_bytes(numbytes)?;
-            let mut ret = bit_vec08::BitVec::from_bytes(&bytes);
-            ret.truncate(numbits);
-            Ok(ret)
-        }
+        let (secs, nanos) = read_seconds_nanos(deserializer)?;
+        chrono::DateTime::from_timestamp(secs, nanos)
+            .map(|x| x.naive_utc())
+            .ok_or_else(date_time_out_of_range::<Self>)
     }
 }
-
-#[cfg(feature = "bit-set")]
-impl WithSchema for bit_set08::BitSet {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Struct(SchemaStruct {
-            dbg_name: "BitSet".to_string(),
-            size: None,
-            alignment: None,
-            fields: vec![
-                Field {
-                    name: "num_bits".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "num_bytes".to_string(),
-                    value: Box::new(usize::schema(version, context)),
-                    offset: None,
-                },
-                Field {
-                    name: "buffer".to_string(),
-                    value: Box::new(Schema::Vector(
-                        Box::new(u8::schema(version, context)),
-                        VecOrStringLayout::Unknown,
-                    )),
-                    offset: None,
-                },
-            ],
-        })
+#[cfg(feature = "chrono")]
+impl Introspect for chrono::NaiveDateTime {
+    fn introspect_value(&self) -> String {
+        // The Debug format is ISO 8601, with a 'T' between date and time
+        format!("{:?}", self)
     }
-}
 
-#[cfg(feature = "bit-set08")]
-impl Introspect for bit_set08::BitSet {
-    fn introspect_value(&self) -> String {
-        let mut ret = String::new();
-        for i in 0..self.len() {
-            if self.contains(i) {
-                use std::fmt::Write;
-                if !ret.is_empty() {
-                    ret += " ";
-                }
-                write!(&mut ret, "{}", i).unwrap();
-            }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        let utc = self.and_utc();
+        IntrospectValue::Timestamp {
+            secs: utc.timestamp(),
+            nanos: utc.timestamp_subsec_nanos(),
         }
-        ret
     }
 
-    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
 }
 
-#[cfg(feature = "bit-set08")]
-impl Packed for bit_set08::BitSet<u32> {}
-
-#[cfg(feature = "bit-set08")]
-impl Serialize for bit_set08::BitSet<u32> {
+#[cfg(feature = "chrono")]
+impl Packed for chrono::DateTime<chrono::FixedOffset> {}
+#[cfg(feature = "chrono")]
+impl WithSchema for chrono::DateTime<chrono::FixedOffset> {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_datetime_offset)
+    }
+}
+#[cfg(feature = "chrono")]
+impl Serialize for chrono::DateTime<chrono::FixedOffset> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let bitset = self.get_ref();
-        bitset.serialize(serializer)
+        write_seconds_nanos(serializer, self.timestamp(), self.timestamp_subsec_nanos())?;
+        serializer.write_i32(self.offset().local_minus_utc())
     }
 }
-
-#[cfg(feature = "bit-set")]
-impl Deserialize for bit_set08::BitSet<u32> {
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::DateTime<chrono::FixedOffset> {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let bit_vec: bit_vec08::BitVec = bit_vec08::BitVec::deserialize(deserializer)?;
-        Ok(bit_set08::BitSet::from_bit_vec(bit_vec))
+        let (secs, nanos) = read_seconds_nanos(deserializer)?;
+        let offset = deserializer.read_i32()?;
+        let offset = chrono::FixedOffset::east_opt(offset).ok_or_else(date_time_out_of_range::<Self>)?;
+        let utc = chrono::DateTime::from_timestamp(secs, nanos).ok_or_else(date_time_out_of_range::<Self>)?;
+        Ok(utc.with_timezone(&offset))
     }
 }
-
-impl<T: Introspect> Introspect for BinaryHeap<T> {
+#[cfg(feature = "chrono")]
+impl Introspect for chrono::DateTime<chrono::FixedOffset> {
     fn introspect_value(&self) -> String {
-        "BinaryHeap".to_string()
+        self.to_rfc3339()
     }
 
-    fn introspect_child<'a>(&'a self, index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
-        if index >= self.len() {
-            return None;
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Timestamp {
+            secs: self.timestamp(),
+            nanos: self.timestamp_subsec_nanos(),
         }
-        return Some(introspect_item(index.to_string(), self.iter().nth(index).unwrap()));
     }
 
-    fn introspect_len(&self) -> usize {
-        self.len()
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl<T> Packed for BinaryHeap<T> {}
-impl<T: WithSchema> WithSchema for BinaryHeap<T> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            VecOrStringLayout::Unknown,
-        )
+#[cfg(feature = "chrono")]
+impl Packed for chrono::Duration {}
+#[cfg(feature = "chrono")]
+impl WithSchema for chrono::Duration {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_duration)
     }
 }
-impl<T: Serialize + Ord> Serialize for BinaryHeap<T> {
+#[cfg(feature = "chrono")]
+impl Serialize for chrono::Duration {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let l = self.len();
-        serializer.write_usize(l)?;
-        for item in self.iter() {
-            item.serialize(serializer)?
+        // num_seconds rounds towards zero, and subsec_nanos has the same sign as the duration.
+        let mut secs = self.num_seconds();
+        let mut nanos = self.subsec_nanos();
+        if nanos < 0 {
+            secs -= 1;
+            nanos += 1_000_000_000;
         }
-        Ok(())
+        write_seconds_nanos(serializer, secs, nanos as u32)
     }
 }
-impl<T: Deserialize + Ord> Deserialize for BinaryHeap<T> {
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::Duration {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let l = deserializer.read_usize()?;
-        let mut ret = BinaryHeap::with_capacity(l);
-        for _ in 0..l {
-            ret.push(T::deserialize(deserializer)?);
-        }
-        Ok(ret)
+        let (secs, nanos) = read_seconds_nanos(deserializer)?;
+        chrono::Duration::new(secs, nanos).ok_or_else(date_time_out_of_range::<Self>)
     }
 }
-
-#[cfg(feature = "smallvec")]
-impl<T: smallvec::Array> Introspect for smallvec::SmallVec<T>
-where
-    T::Item: Introspect,
-{
+#[cfg(feature = "chrono")]
+impl Introspect for chrono::Duration {
     fn introspect_value(&self) -> String {
-        format!("SmallVec<{}>", std::any::type_name::<T>())
+        self.to_string()
     }
 
-    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if let Some(val) = self.get(index) {
-            Some(introspect_item(index.to_string(), val))
-        } else {
-            None
-        }
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
+}
 
-    fn introspect_len(&self) -> usize {
-        self.len()
+/// Julian day number of 1970-01-01
+#[cfg(feature = "time")]
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+#[cfg(feature = "time")]
+fn iso8601_date(date: time::Date) -> String {
+    let year = date.year();
+    if (0..=9999).contains(&year) {
+        format!("{:04}-{:02}-{:02}", year, date.month() as u8, date.day())
+    } else {
+        format!("{:+05}-{:02}-{:02}", year, date.month() as u8, date.day())
     }
 }
 
-#[cfg(feature = "smallvec")]
-impl<T: smallvec::Array> WithSchema for smallvec::SmallVec<T>
-where
-    T::Item: WithSchema,
-{
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T::Item>(|context| T::Item::schema(version, context))),
-            VecOrStringLayout::Unknown,
-        )
+#[cfg(feature = "time")]
+fn iso8601_time(time: time::Time) -> String {
+    let (hour, minute, second, nanos) = time.as_hms_nano();
+    let fraction = if nanos == 0 {
+        String::new()
+    } else if nanos % 1_000_000 == 0 {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos % 1_000 == 0 {
+        format!(".{:06}", nanos / 1_000)
+    } else {
+        format!(".{:09}", nanos)
+    };
+    format!("{:02}:{:02}:{:02}{}", hour, minute, second, fraction)
+}
+
+#[cfg(feature = "time")]
+fn iso8601_offset(offset: time::UtcOffset) -> String {
+    let (hours, minutes, seconds) = offset.as_hms();
+    let sign = if offset.is_negative() { '-' } else { '+' };
+    if seconds != 0 {
+        format!("{}{:02}:{:02}:{:02}", sign, hours.abs(), minutes.abs(), seconds.abs())
+    } else {
+        format!("{}{:02}:{:02}", sign, hours.abs(), minutes.abs())
     }
 }
-#[cfg(feature = "smallvec")]
-impl<T: smallvec::Array> Packed for smallvec::SmallVec<T> {}
 
-#[cfg(feature = "smallvec")]
-impl<T: smallvec::Array> Serialize for smallvec::SmallVec<T>
-where
-    T::Item: Serialize,
-{
+#[cfg(feature = "time")]
+fn unix_timestamp_to_utc(secs: i64, nanos: u32) -> Result<time::OffsetDateTime, SavefileError> {
+    time::OffsetDateTime::from_unix_timestamp(secs)
+        .and_then(|x| x.replace_nanosecond(nanos))
+        .map_err(|_| date_time_out_of_range::<time::OffsetDateTime>())
+}
+
+#[cfg(feature = "time")]
+impl Packed for time::Date {}
+#[cfg(feature = "time")]
+impl WithSchema for time::Date {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_date)
+    }
+}
+#[cfg(feature = "time")]
+impl Serialize for time::Date {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let l = self.len();
-        serializer.write_usize(l)?;
-        for item in self.iter() {
-            item.serialize(serializer)?
-        }
-        Ok(())
+        serializer.write_i32(self.to_julian_day() - UNIX_EPOCH_JULIAN_DAY)
     }
 }
-#[cfg(feature = "smallvec")]
-impl<T: smallvec::Array> Deserialize for smallvec::SmallVec<T>
-where
-    T::Item: Deserialize,
-{
+#[cfg(feature = "time")]
+impl Deserialize for time::Date {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let l = deserializer.read_usize()?;
-        let mut ret = Self::with_capacity(l);
-        for _ in 0..l {
-            ret.push(T::Item::deserialize(deserializer)?);
-        }
-        Ok(ret)
+        let days = deserializer.read_i32()?;
+        let julian_day = days
+            .checked_add(UNIX_EPOCH_JULIAN_DAY)
+            .ok_or_else(date_time_out_of_range::<Self>)?;
+        time::Date::from_julian_day(julian_day).map_err(|_| date_time_out_of_range::<Self>())
     }
 }
-
-fn regular_serialize_vec<T: Serialize>(
-    items: &[T],
-    serializer: &mut Serializer<impl Write>,
-) -> Result<(), SavefileError> {
-    let l = items.len();
-    serializer.write_usize(l)?;
-    if std::mem::size_of::<T>() == 0 {
-        return Ok(());
+#[cfg(feature = "time")]
+impl Introspect for time::Date {
+    fn introspect_value(&self) -> String {
+        iso8601_date(*self)
     }
 
-    if std::mem::size_of::<T>() < 32 {
-        //<-- This optimization seems to help a little actually, but maybe not enough to warrant it
-        let chunks = items.chunks_exact((64 / std::mem::size_of::<T>()).max(1));
-        let remainder = chunks.remainder();
-        for chunk in chunks {
-            for item in chunk {
-                item.serialize(serializer)?;
-            }
-        }
-        for item in remainder {
-            item.serialize(serializer)?;
-        }
-        Ok(())
-    } else {
-        for item in items {
-            item.serialize(serializer)?;
-        }
-        Ok(())
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl<T: WithSchema> WithSchema for Box<[T]> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            VecOrStringLayout::Unknown,
-        )
+#[cfg(feature = "time")]
+impl Packed for time::PrimitiveDateTime {}
+#[cfg(feature = "time")]
+impl WithSchema for time::PrimitiveDateTime {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_datetime)
     }
 }
-impl<T: WithSchema> WithSchema for Arc<[T]> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            VecOrStringLayout::Unknown,
-        )
+#[cfg(feature = "time")]
+impl Serialize for time::PrimitiveDateTime {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        write_seconds_nanos(serializer, self.assume_utc().unix_timestamp(), self.nanosecond())
     }
 }
-impl<T: Introspect> Introspect for Box<[T]> {
+#[cfg(feature = "time")]
+impl Deserialize for time::PrimitiveDateTime {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let (secs, nanos) = read_seconds_nanos(deserializer)?;
+        let utc = unix_timestamp_to_utc(secs, nanos)?;
+        Ok(time::PrimitiveDateTime::new(utc.date(), utc.time()))
+    }
+}
+#[cfg(feature = "time")]
+impl Introspect for time::PrimitiveDateTime {
     fn introspect_value(&self) -> String {
-        return "Box[]".to_string();
+        format!("{}T{}", iso8601_date(self.date()), iso8601_time(self.time()))
     }
 
-    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if index >= self.len() {
-            return None;
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        let utc = self.assume_utc();
+        IntrospectValue::Timestamp {
+            secs: utc.unix_timestamp(),
+            nanos: utc.nanosecond(),
         }
-        return Some(introspect_item(index.to_string(), &self[index]));
     }
-    fn introspect_len(&self) -> usize {
-        self.len()
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl<T: Introspect> Introspect for Arc<[T]> {
+#[cfg(feature = "time")]
+impl Packed for time::OffsetDateTime {}
+#[cfg(feature = "time")]
+impl WithSchema for time::OffsetDateTime {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_datetime_offset)
+    }
+}
+#[cfg(feature = "time")]
+impl Serialize for time::OffsetDateTime {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        write_seconds_nanos(serializer, self.unix_timestamp(), self.nanosecond())?;
+        serializer.write_i32(self.offset().whole_seconds())
+    }
+}
+#[cfg(feature = "time")]
+impl Deserialize for time::OffsetDateTime {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let (secs, nanos) = read_seconds_nanos(deserializer)?;
+        let offset = deserializer.read_i32()?;
+        let offset = time::UtcOffset::from_whole_seconds(offset).map_err(|_| date_time_out_of_range::<Self>())?;
+        unix_timestamp_to_utc(secs, nanos)?
+            .checked_to_offset(offset)
+            .ok_or_else(date_time_out_of_range::<Self>)
+    }
+}
+#[cfg(feature = "time")]
+impl Introspect for time::OffsetDateTime {
     fn introspect_value(&self) -> String {
-        return "Arc[]".to_string();
+        format!(
+            "{}T{}{}",
+            iso8601_date(self.date()),
+            iso8601_time(self.time()),
+            iso8601_offset(self.offset())
+        )
     }
 
-    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if index >= self.len() {
-            return None;
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Timestamp {
+            secs: self.unix_timestamp(),
+            nanos: self.nanosecond(),
         }
-        return Some(introspect_item(index.to_string(), &self[index]));
     }
-    fn introspect_len(&self) -> usize {
-        self.len()
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl WithSchema for Arc<str> {
+#[cfg(feature = "half")]
+impl Packed for half::f16 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+#[cfg(feature = "half")]
+impl WithSchema for half::f16 {
     fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
-        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+        Schema::Primitive(SchemaPrimitive::schema_f16)
     }
 }
-impl Introspect for Arc<str> {
+#[cfg(feature = "half")]
+impl Serialize for half::f16 {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u16(self.to_bits())
+    }
+}
+#[cfg(feature = "half")]
+impl Deserialize for half::f16 {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(half::f16::from_bits(deserializer.read_u16()?))
+    }
+}
+#[cfg(feature = "half")]
+impl Introspect for half::f16 {
     fn introspect_value(&self) -> String {
-        self.deref().to_string()
+        self.to_string()
     }
 
-    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_>>> {
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Float(self.to_f64())
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
-    fn introspect_len(&self) -> usize {
-        0
+}
+
+#[cfg(feature = "half")]
+impl Packed for half::bf16 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
     }
 }
-impl Serialize for Arc<str> {
+#[cfg(feature = "half")]
+impl WithSchema for half::bf16 {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_bf16)
+    }
+}
+#[cfg(feature = "half")]
+impl Serialize for half::bf16 {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        serializer.write_string(self)
+        serializer.write_u16(self.to_bits())
     }
 }
-
-impl Packed for Arc<str> {}
-
-impl Deserialize for Arc<str> {
+#[cfg(feature = "half")]
+impl Deserialize for half::bf16 {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        let s = deserializer.read_string()?;
-
-        let state = deserializer.get_state::<Arc<str>, HashMap<String, Arc<str>>>();
+        Ok(half::bf16::from_bits(deserializer.read_u16()?))
+    }
+}
+#[cfg(feature = "half")]
+impl Introspect for half::bf16 {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
 
-        if let Some(needle) = state.get(&s) {
-            return Ok(Arc::clone(needle));
-        }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Float(self.to_f64())
+    }
 
-        let arc_ref = state.entry(s.clone()).or_insert(s.into());
-        Ok(Arc::clone(arc_ref))
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl<T: Serialize + Packed> Serialize for Box<[T]> {
-    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        unsafe {
-            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
-                regular_serialize_vec(self, serializer)
-            } else {
-                let l = self.len();
-                serializer.write_usize(l)?;
-                serializer.write_buf(std::slice::from_raw_parts(
-                    (*self).as_ptr() as *const u8,
-                    std::mem::size_of::<T>() * l,
-                ))
-            }
-        }
+// OrderedFloat is repr(transparent), and is saved exactly like the wrapped float.
+#[cfg(feature = "ordered-float")]
+impl<T: Packed> Packed for ordered_float::OrderedFloat<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        T::repr_c_optimization_safe(version)
     }
 }
-impl<T: Packed> Packed for Box<[T]> {}
-
-impl<T: Serialize + Packed> Serialize for Arc<[T]> {
+#[cfg(feature = "ordered-float")]
+impl<T: WithSchema> WithSchema for ordered_float::OrderedFloat<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        T::schema(version, context)
+    }
+}
+#[cfg(feature = "ordered-float")]
+impl<T: Serialize> Serialize for ordered_float::OrderedFloat<T> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        unsafe {
-            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
-                regular_serialize_vec(self, serializer)
-            } else {
-                let l = self.len();
-                serializer.write_usize(l)?;
-                serializer.write_buf(std::slice::from_raw_parts(
-                    (*self).as_ptr() as *const u8,
-                    std::mem::size_of::<T>() * l,
-                ))
-            }
-        }
+        self.0.serialize(serializer)
     }
 }
-impl<T: Packed> Packed for Arc<[T]> {}
-
-impl<T: Deserialize + Packed> Deserialize for Arc<[T]> {
+#[cfg(feature = "ordered-float")]
+impl<T: Deserialize> Deserialize for ordered_float::OrderedFloat<T> {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(Vec::<T>::deserialize(deserializer)?.into())
+        Ok(ordered_float::OrderedFloat(T::deserialize(deserializer)?))
     }
 }
-impl<T: Deserialize + Packed> Deserialize for Box<[T]> {
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(Vec::<T>::deserialize(deserializer)?.into_boxed_slice())
+#[cfg(feature = "ordered-float")]
+impl<T: Introspect> Introspect for ordered_float::OrderedFloat<T> {
+    fn introspect_value(&self) -> String {
+        self.0.introspect_value()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.0.introspect_typed_value()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
-impl WithSchema for &'_ str {
-    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
-        Schema::Primitive(SchemaPrimitive::schema_string(calculate_string_memory_layout()))
-        //TODO: This is _not_ the same memory layout as vec. Make a new Box type for slices?
+
+// NotNan is saved like the wrapped float, but is not Packed, since loading
+// must check that the value is not NaN.
+#[cfg(feature = "ordered-float")]
+impl<T> Packed for ordered_float::NotNan<T> {}
+#[cfg(feature = "ordered-float")]
+impl<T: WithSchema> WithSchema for ordered_float::NotNan<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        T::schema(version, context)
     }
 }
-impl Serialize for &'_ str {
+#[cfg(feature = "ordered-float")]
+impl<T: ordered_float::FloatCore + Serialize> Serialize for ordered_float::NotNan<T> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        let l = self.len();
-        serializer.write_usize(l)?;
-        serializer.write_buf(self.as_bytes())
+        (**self).serialize(serializer)
     }
 }
+#[cfg(feature = "ordered-float")]
+impl<T: ordered_float::FloatCore + Deserialize> Deserialize for ordered_float::NotNan<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        ordered_float::NotNan::new(T::deserialize(deserializer)?).map_err(|_| SavefileError::ValidationFailed {
+            msg: "NotNan value was NaN".to_string(),
+        })
+    }
+}
+#[cfg(feature = "ordered-float")]
+impl<T: ordered_float::FloatCore + Introspect> Introspect for ordered_float::NotNan<T> {
+    fn introspect_value(&self) -> String {
+        (**self).introspect_value()
+    }
 
-impl<T: WithSchema> WithSchema for &'_ [T] {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            calculate_slice_memory_layout::<T>(),
-        )
-        //TODO: This is _not_ the same memory layout as vec. Make a new Box type for slices?
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        (**self).introspect_typed_value()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
-impl<T: Serialize + Packed> Serialize for &'_ [T] {
+
+#[cfg(feature = "rust_decimal")]
+impl Packed for rust_decimal::Decimal {}
+#[cfg(feature = "rust_decimal")]
+impl WithSchema for rust_decimal::Decimal {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_decimal)
+    }
+}
+#[cfg(feature = "rust_decimal")]
+impl Serialize for rust_decimal::Decimal {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        unsafe {
-            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
-                regular_serialize_vec(self, serializer)
-            } else {
-                let l = self.len();
-                serializer.write_usize(l)?;
-                #[allow(clippy::manual_slice_size_calculation)] // I feel this way is clearer
-                serializer.write_buf(std::slice::from_raw_parts(
-                    self.as_ptr() as *const u8,
-                    std::mem::size_of::<T>() * l,
-                ))
+        serializer.write_i128(self.mantissa())?;
+        serializer.write_u8(self.scale() as u8)
+    }
+}
+#[cfg(feature = "rust_decimal")]
+impl Deserialize for rust_decimal::Decimal {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let mantissa = deserializer.read_i128()?;
+        let scale = deserializer.read_u8()?;
+        rust_decimal::Decimal::try_from_i128_with_scale(mantissa, scale as u32).map_err(|err| {
+            SavefileError::GeneralError {
+                msg: format!("Corrupt file - invalid Decimal: {}", err),
             }
-        }
+        })
     }
 }
+#[cfg(feature = "rust_decimal")]
+impl Introspect for rust_decimal::Decimal {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
 
-/// Deserialize a slice into a Vec
-/// Unsized slices cannot be deserialized into unsized slices.
-pub fn deserialize_slice_as_vec<R: Read, T: Deserialize + Packed>(
-    deserializer: &mut Deserializer<R>,
-) -> Result<Vec<T>, SavefileError> {
-    Vec::deserialize(deserializer)
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
 }
 
-impl<T> Packed for Vec<T> {}
-
-/// 0 = Uninitialized
-static STRING_IS_STANDARD_LAYOUT: AtomicU8 = AtomicU8::new(255);
-#[derive(Debug)]
+#[cfg(feature = "num-bigint")]
+impl Packed for num_bigint::BigInt {}
+#[cfg(feature = "num-bigint")]
+impl WithSchema for num_bigint::BigInt {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_bigint)
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Serialize for num_bigint::BigInt {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bytes = self.to_signed_bytes_le();
+        serializer.write_usize(bytes.len())?;
+        serializer.write_bytes(&bytes)
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Deserialize for num_bigint::BigInt {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let len = deserializer.read_usize()?;
+        Ok(num_bigint::BigInt::from_signed_bytes_le(&deserializer.read_bytes(len)?))
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Introspect for num_bigint::BigInt {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Packed for num_bigint::BigUint {}
+#[cfg(feature = "num-bigint")]
+impl WithSchema for num_bigint::BigUint {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_biguint)
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Serialize for num_bigint::BigUint {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bytes = self.to_bytes_le();
+        serializer.write_usize(bytes.len())?;
+        serializer.write_bytes(&bytes)
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Deserialize for num_bigint::BigUint {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let len = deserializer.read_usize()?;
+        Ok(num_bigint::BigUint::from_bytes_le(&deserializer.read_bytes(len)?))
+    }
+}
+#[cfg(feature = "num-bigint")]
+impl Introspect for num_bigint::BigUint {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+#[cfg(any(feature = "bit-vec", feature = "bit-vec08"))]
+#[cfg(target_endian = "big")]
+compile_error!("savefile bit-vec feature does not support big-endian machines");
+
+#[cfg(feature = "bit-vec")]
+impl WithSchema for bit_vec::BitVec {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct {
+            dbg_name: "BitVec".to_string(),
+            size: None,
+            alignment: None,
+            fields: vec![
+                Field {
+                    name: "num_bits".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "num_bytes".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "buffer".to_string(),
+                    value: Box::new(Schema::Vector(
+                        Box::new(u8::schema(version, context)),
+                        VecOrStringLayout::Unknown,
+                    )),
+                    offset: None,
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+impl Introspect for bit_vec::BitVec {
+    fn introspect_value(&self) -> String {
+        let mut ret = String::new();
+        for i in 0..self.len() {
+            if self[i] {
+                ret.push('1');
+            } else {
+                ret.push('0');
+            }
+        }
+        ret
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+impl Serialize for bit_vec::BitVec<u32> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let l = self.len();
+        serializer.write_usize(l)?;
+        let storage = self.storage();
+        let rawbytes_ptr = storage.as_ptr() as *const u8;
+        let rawbytes: &[u8] = unsafe { std::slice::from_raw_parts(rawbytes_ptr, 4 * storage.len()) };
+        serializer.write_usize(rawbytes.len() | (1 << 63))?;
+        serializer.write_bytes(rawbytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bit-vec")]
+impl Packed for bit_vec::BitVec<u32> {}
+
+#[cfg(feature = "bit-vec")]
+impl Deserialize for bit_vec::BitVec<u32> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let numbits = deserializer.read_usize()?;
+        let mut numbytes = deserializer.read_usize()?;
+        if numbytes & (1 << 63) != 0 {
+            //New format
+            numbytes &= !(1 << 63);
+            let mut ret = bit_vec::BitVec::with_capacity(numbytes * 8);
+            unsafe {
+                let num_words = numbytes / 4;
+                let storage = ret.storage_mut();
+                storage.resize(num_words, 0);
+                let storage_ptr = storage.as_ptr() as *mut u8;
+                let storage_bytes: &mut [u8] = std::slice::from_raw_parts_mut(storage_ptr, 4 * num_words);
+                deserializer.read_bytes_to_buf(storage_bytes)?;
+                ret.set_len(numbits);
+            }
+            Ok(ret)
+        } else {
+            let bytes = deserializer.read_bytes(numbytes)?;
+            let mut ret = bit_vec::BitVec::from_bytes(&bytes);
+            ret.truncate(numbits);
+            Ok(ret)
+        }
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl WithSchema for bit_set::BitSet {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct {
+            dbg_name: "BitSet".to_string(),
+            size: None,
+            alignment: None,
+            fields: vec![
+                Field {
+                    name: "num_bits".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "num_bytes".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "buffer".to_string(),
+                    value: Box::new(Schema::Vector(
+                        Box::new(u8::schema(version, context)),
+                        VecOrStringLayout::Unknown,
+                    )),
+                    offset: None,
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl Introspect for bit_set::BitSet {
+    fn introspect_value(&self) -> String {
+        let mut ret = String::new();
+        for i in 0..self.len() {
+            if self.contains(i) {
+                use std::fmt::Write;
+                if !ret.is_empty() {
+                    ret += " ";
+                }
+                write!(&mut ret, "{}", i).unwrap();
+            }
+        }
+        ret
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl Packed for bit_set::BitSet<u32> {}
+
+#[cfg(feature = "bit-set")]
+impl Serialize for bit_set::BitSet<u32> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bitset = self.get_ref();
+        bitset.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl Deserialize for bit_set::BitSet<u32> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let bit_vec: bit_vec::BitVec = bit_vec::BitVec::deserialize(deserializer)?;
+        Ok(bit_set::BitSet::from_bit_vec(bit_vec))
+    }
+}
+
+#[cfg(feature = "bit-vec08")]
+impl WithSchema for bit_vec08::BitVec {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct {
+            dbg_name: "BitVec".to_string(),
+            size: None,
+            alignment: None,
+            fields: vec![
+                Field {
+                    name: "num_bits".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "num_bytes".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "buffer".to_string(),
+                    value: Box::new(Schema::Vector(
+                        Box::new(u8::schema(version, context)),
+                        VecOrStringLayout::Unknown,
+                    )),
+                    offset: None,
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "bit-vec08")]
+impl Introspect for bit_vec08::BitVec {
+    fn introspect_value(&self) -> String {
+        let mut ret = String::new();
+        for i in 0..self.len() {
+            if self[i] {
+                ret.push('1');
+            } else {
+                ret.push('0');
+            }
+        }
+        ret
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+#[cfg(feature = "bit-vec08")]
+impl Serialize for bit_vec08::BitVec<u32> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let l = self.len();
+        serializer.write_usize(l)?;
+        let storage = self.storage();
+        let rawbytes_ptr = storage.as_ptr() as *const u8;
+        let rawbytes: &[u8] = unsafe { std::slice::from_raw_parts(rawbytes_ptr, 4 * storage.len()) };
+        serializer.write_usize(rawbytes.len() | (1 << 63))?;
+        serializer.write_bytes(rawbytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bit-vec08")]
+impl Packed for bit_vec08::BitVec<u32> {}
+
+#[cfg(feature = "bit-vec08")]
+impl Deserialize for bit_vec08::BitVec<u32> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let numbits = deserializer.read_usize()?;
+        let mut numbytes = deserializer.read_usize()?;
+        if numbytes & (1 << 63) != 0 {
+            //New format
+            numbytes &= !(1 << 63);
+            let mut ret = bit_vec08::BitVec::with_capacity(numbytes * 8);
+            unsafe {
+                let num_words = numbytes / 4;
+                let storage = ret.storage_mut();
+                storage.resize(num_words, 0);
+                let storage_ptr = storage.as_ptr() as *mut u8;
+                let storage_bytes: &mut [u8] = std::slice::from_raw_parts_mut(storage_ptr, 4 * num_words);
+                deserializer.read_bytes_to_buf(storage_bytes)?;
+                ret.set_len(numbits);
+            }
+            Ok(ret)
+        } else {
+            let bytes = deserializer.read_bytes(numbytes)?;
+            let mut ret = bit_vec08::BitVec::from_bytes(&bytes);
+            ret.truncate(numbits);
+            Ok(ret)
+        }
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl WithSchema for bit_set08::BitSet {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct {
+            dbg_name: "BitSet".to_string(),
+            size: None,
+            alignment: None,
+            fields: vec![
+                Field {
+                    name: "num_bits".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "num_bytes".to_string(),
+                    value: Box::new(usize::schema(version, context)),
+                    offset: None,
+                },
+                Field {
+                    name: "buffer".to_string(),
+                    value: Box::new(Schema::Vector(
+                        Box::new(u8::schema(version, context)),
+                        VecOrStringLayout::Unknown,
+                    )),
+                    offset: None,
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(feature = "bit-set08")]
+impl Introspect for bit_set08::BitSet {
+    fn introspect_value(&self) -> String {
+        let mut ret = String::new();
+        for i in 0..self.len() {
+            if self.contains(i) {
+                use std::fmt::Write;
+                if !ret.is_empty() {
+                    ret += " ";
+                }
+                write!(&mut ret, "{}", i).unwrap();
+            }
+        }
+        ret
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+#[cfg(feature = "bit-set08")]
+impl Packed for bit_set08::BitSet<u32> {}
+
+#[cfg(feature = "bit-set08")]
+impl Serialize for bit_set08::BitSet<u32> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bitset = self.get_ref();
+        bitset.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "bit-set")]
+impl Deserialize for bit_set08::BitSet<u32> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let bit_vec: bit_vec08::BitVec = bit_vec08::BitVec::deserialize(deserializer)?;
+        Ok(bit_set08::BitSet::from_bit_vec(bit_vec))
+    }
+}
+
+impl<T: Introspect> Introspect for BinaryHeap<T> {
+    fn introspect_value(&self) -> String {
+        "BinaryHeap".to_string()
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child<'a>(&'a self, index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(introspect_item(index.to_string(), self.iter().nth(index).unwrap()));
+    }
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .map(|(index, val)| introspect_item(index.to_string(), val))
+            .collect()
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Packed for BinaryHeap<T> {}
+impl<T: WithSchema> WithSchema for BinaryHeap<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+impl<T: Serialize + Ord> Serialize for BinaryHeap<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let l = self.len();
+        serializer.write_usize(l)?;
+        for item in self.iter() {
+            item.serialize(serializer)?
+        }
+        Ok(())
+    }
+}
+impl<T: Deserialize + Ord> Deserialize for BinaryHeap<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = BinaryHeap::with_capacity(l);
+        for _ in 0..l {
+            ret.push(T::deserialize(deserializer)?);
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T: smallvec::Array> Introspect for smallvec::SmallVec<T>
+where
+    T::Item: Introspect,
+{
+    fn introspect_value(&self) -> String {
+        format!("SmallVec<{}>", std::any::type_name::<T>())
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if let Some(val) = self.get(index) {
+            Some(introspect_item(index.to_string(), val))
+        } else {
+            None
+        }
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T: smallvec::Array> WithSchema for smallvec::SmallVec<T>
+where
+    T::Item: WithSchema,
+{
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T::Item>(|context| T::Item::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+#[cfg(feature = "smallvec")]
+impl<T: smallvec::Array> Packed for smallvec::SmallVec<T> {}
+
+#[cfg(feature = "smallvec")]
+impl<T: smallvec::Array> Serialize for smallvec::SmallVec<T>
+where
+    T::Item: Serialize,
+{
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let l = self.len();
+        serializer.write_usize(l)?;
+        for item in self.iter() {
+            item.serialize(serializer)?
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "smallvec")]
+impl<T: smallvec::Array> Deserialize for smallvec::SmallVec<T>
+where
+    T::Item: Deserialize,
+{
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = Self::with_capacity(l);
+        for _ in 0..l {
+            ret.push(T::Item::deserialize(deserializer)?);
+        }
+        Ok(ret)
+    }
+}
+
+fn regular_serialize_vec<T: Serialize>(
+    items: &[T],
+    serializer: &mut Serializer<impl Write>,
+) -> Result<(), SavefileError> {
+    let l = items.len();
+    serializer.write_usize(l)?;
+    if std::mem::size_of::<T>() == 0 {
+        return Ok(());
+    }
+
+    if std::mem::size_of::<T>() < 32 {
+        //<-- This optimization seems to help a little actually, but maybe not enough to warrant it
+        let chunks = items.chunks_exact((64 / std::mem::size_of::<T>()).max(1));
+        let remainder = chunks.remainder();
+        for chunk in chunks {
+            for item in chunk {
+                item.serialize(serializer)?;
+            }
+        }
+        for item in remainder {
+            item.serialize(serializer)?;
+        }
+        Ok(())
+    } else {
+        for item in items {
+            item.serialize(serializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: WithSchema> WithSchema for Box<[T]> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+impl<T: WithSchema> WithSchema for Arc<[T]> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+impl<T: Introspect> Introspect for Box<[T]> {
+    fn introspect_value(&self) -> String {
+        return "Box[]".to_string();
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(introspect_item(index.to_string(), &self[index]));
+    }
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Introspect> Introspect for Arc<[T]> {
+    fn introspect_value(&self) -> String {
+        return "Arc[]".to_string();
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(introspect_item(index.to_string(), &self[index]));
+    }
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl WithSchema for Arc<str> {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+    }
+}
+impl Introspect for Arc<str> {
+    fn introspect_value(&self) -> String {
+        self.deref().to_string()
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_>>> {
+        None
+    }
+    fn introspect_len(&self) -> usize {
+        0
+    }
+}
+impl Serialize for Arc<str> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_string(self)
+    }
+}
+
+impl Packed for Arc<str> {}
+
+impl Deserialize for Arc<str> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let s = deserializer.read_string()?;
+
+        let state = deserializer.get_state::<Arc<str>, HashMap<String, Arc<str>>>();
+
+        if let Some(needle) = state.get(&s) {
+            return Ok(Arc::clone(needle));
+        }
+
+        let arc_ref = state.entry(s.clone()).or_insert(s.into());
+        Ok(Arc::clone(arc_ref))
+    }
+}
+
+impl<T: Serialize + Packed> Serialize for Box<[T]> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        unsafe {
+            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
+                regular_serialize_vec(self, serializer)
+            } else {
+                let l = self.len();
+                serializer.write_usize(l)?;
+                serializer.write_buf(std::slice::from_raw_parts(
+                    (*self).as_ptr() as *const u8,
+                    std::mem::size_of::<T>() * l,
+                ))
+            }
+        }
+    }
+}
+impl<T: Packed> Packed for Box<[T]> {}
+
+impl<T: Serialize + Packed> Serialize for Arc<[T]> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        unsafe {
+            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
+                regular_serialize_vec(self, serializer)
+            } else {
+                let l = self.len();
+                serializer.write_usize(l)?;
+                serializer.write_buf(std::slice::from_raw_parts(
+                    (*self).as_ptr() as *const u8,
+                    std::mem::size_of::<T>() * l,
+                ))
+            }
+        }
+    }
+}
+impl<T: Packed> Packed for Arc<[T]> {}
+
+impl<T: Deserialize + Packed> Deserialize for Arc<[T]> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Vec::<T>::deserialize(deserializer)?.into())
+    }
+}
+impl<T: Deserialize + Packed> Deserialize for Box<[T]> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Vec::<T>::deserialize(deserializer)?.into_boxed_slice())
+    }
+}
+impl WithSchema for &'_ str {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(calculate_string_memory_layout()))
+        //TODO: This is _not_ the same memory layout as vec. Make a new Box type for slices?
+    }
+}
+impl Serialize for &'_ str {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let l = self.len();
+        serializer.write_usize(l)?;
+        serializer.write_buf(self.as_bytes())
+    }
+}
+
+impl<T: WithSchema> WithSchema for &'_ [T] {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            calculate_slice_memory_layout::<T>(),
+        )
+        //TODO: This is _not_ the same memory layout as vec. Make a new Box type for slices?
+    }
+}
+impl<T: Serialize + Packed> Serialize for &'_ [T] {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        unsafe {
+            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
+                regular_serialize_vec(self, serializer)
+            } else {
+                let l = self.len();
+                serializer.write_usize(l)?;
+                #[allow(clippy::manual_slice_size_calculation)] // I feel this way is clearer
+                serializer.write_buf(std::slice::from_raw_parts(
+                    self.as_ptr() as *const u8,
+                    std::mem::size_of::<T>() * l,
+                ))
+            }
+        }
+    }
+}
+
+/// Deserialize a slice into a Vec
+/// Unsized slices cannot be deserialized into unsized slices.
+pub fn deserialize_slice_as_vec<R: Read, T: Deserialize + Packed>(
+    deserializer: &mut Deserializer<R>,
+) -> Result<Vec<T>, SavefileError> {
+    Vec::deserialize(deserializer)
+}
+
+impl<T> Packed for Vec<T> {}
+
+/// 0 = Uninitialized
+static STRING_IS_STANDARD_LAYOUT: AtomicU8 = AtomicU8::new(255);
+#[derive(Debug)]
 #[repr(C)]
 struct RawVecInspector {
     p1: usize,
     p2: usize,
     p3: usize,
 }
-#[derive(Debug)]
-#[repr(C)]
-struct RawSliceInspector {
-    p1: usize,
-    p2: usize,
+#[derive(Debug)]
+#[repr(C)]
+struct RawSliceInspector {
+    p1: usize,
+    p2: usize,
+}
+impl RawSliceInspector {
+    const fn get_layout(&self) -> VecOrStringLayout {
+        if self.p1 == 0 {
+            VecOrStringLayout::LengthData
+        } else {
+            VecOrStringLayout::DataLength
+        }
+    }
+}
+impl RawVecInspector {
+    fn get_layout(&self, ptr: *const u8) -> VecOrStringLayout {
+        let ptr = ptr as usize;
+        // We know size is 1, and capacity is 2.
+        const LENGTH: usize = 0;
+        const CAPACITY: usize = 7;
+        match (self.p1, self.p2, self.p3) {
+            (LENGTH, CAPACITY, x) if x == ptr => VecOrStringLayout::LengthCapacityData,
+            (CAPACITY, LENGTH, x) if x == ptr => VecOrStringLayout::CapacityLengthData,
+            (LENGTH, x, CAPACITY) if x == ptr => VecOrStringLayout::LengthDataCapacity,
+            (CAPACITY, x, LENGTH) if x == ptr => VecOrStringLayout::CapacityDataLength,
+            (x, LENGTH, CAPACITY) if x == ptr => VecOrStringLayout::DataLengthCapacity,
+            (x, CAPACITY, LENGTH) if x == ptr => VecOrStringLayout::DataCapacityLength,
+            _ => VecOrStringLayout::Unknown,
+        }
+    }
+}
+
+/// Calculate the memory layout of `&[T]`.
+///
+/// I.e, of the reference to the data.
+/// This type is typically 16 bytes, consisting of two words, one being the length,
+/// the other being a pointer to the start of the data.
+pub const fn calculate_slice_memory_layout<T>() -> VecOrStringLayout {
+    if std::mem::size_of::<&[T]>() != 16 || std::mem::size_of::<RawSliceInspector>() != 16 {
+        VecOrStringLayout::Unknown
+    } else {
+        let test_slice: &[T] = &[];
+        let insp: RawSliceInspector = unsafe { std::mem::transmute_copy::<&[T], RawSliceInspector>(&test_slice) };
+        insp.get_layout()
+    }
+}
+/// Calculate the memory layout of a Vec of the given type
+pub fn calculate_vec_memory_layout<T>() -> VecOrStringLayout {
+    if std::mem::size_of::<Vec<u8>>() != 24 || std::mem::size_of::<RawVecInspector>() != 24 {
+        VecOrStringLayout::Unknown
+    } else {
+        let test_vec = Vec::with_capacity(7);
+        let insp: RawVecInspector = unsafe { std::mem::transmute_copy(&test_vec) };
+        let ptr = test_vec.as_ptr();
+        insp.get_layout(ptr)
+    }
+}
+fn calculate_string_memory_layout() -> VecOrStringLayout {
+    let mut is_std = STRING_IS_STANDARD_LAYOUT.load(Ordering::Relaxed);
+    if is_std != 255 {
+        // SAFETY
+        // We 'is_std' is always initialized using a valid VecOrStringLayout enum value,
+        // unless it's 255.
+        return unsafe { std::mem::transmute::<u8, VecOrStringLayout>(is_std) };
+    }
+    if std::mem::size_of::<String>() != 24 || std::mem::size_of::<RawVecInspector>() != 24 {
+        is_std = VecOrStringLayout::Unknown as u8;
+    } else {
+        let test_string = String::with_capacity(7);
+        let insp: RawVecInspector = unsafe { std::mem::transmute_copy(&test_string) };
+        let ptr = test_string.as_ptr();
+
+        is_std = insp.get_layout(ptr) as u8;
+
+        drop(test_string);
+    }
+
+    STRING_IS_STANDARD_LAYOUT.store(is_std, Ordering::Relaxed);
+    return unsafe { std::mem::transmute::<u8, VecOrStringLayout>(is_std) };
+}
+
+impl<T: WithSchema> WithSchema for Vec<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            calculate_vec_memory_layout::<T>(),
+        )
+    }
+}
+
+impl<T: Introspect> Introspect for Vec<T> {
+    fn introspect_value(&self) -> String {
+        return "vec[]".to_string();
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(introspect_item(index.to_string(), &self[index]));
+    }
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: IntrospectMut> IntrospectMut for Vec<T> {
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        let item = self.get_mut(index)?;
+        Some(introspect_item_mut(index.to_string(), item))
+    }
+}
+
+impl<T: Serialize + Packed> Serialize for Vec<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        unsafe {
+            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
+                regular_serialize_vec(self, serializer)
+            } else {
+                let l = self.len();
+                serializer.write_usize(l)?;
+                serializer.write_buf(std::slice::from_raw_parts(
+                    self.as_ptr() as *const u8,
+                    std::mem::size_of::<T>() * l,
+                ))
+            }
+        }
+    }
+}
+
+fn regular_deserialize_vec<T: Deserialize>(
+    deserializer: &mut Deserializer<impl Read>,
+) -> Result<Vec<T>, SavefileError> {
+    let l = deserializer.read_usize()?;
+
+    #[cfg(feature = "size_sanity_checks")]
+    {
+        if l > 1_000_000 {
+            return Err(SavefileError::GeneralError {
+                msg: format!("Too many items in Vec: {}", l),
+            });
+        }
+    }
+    let mut ret = Vec::with_capacity(l);
+    for _ in 0..l {
+        ret.push(T::deserialize(deserializer)?);
+    }
+    Ok(ret)
+}
+
+impl<T: Deserialize + Packed> Deserialize for Vec<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        if unsafe { T::repr_c_optimization_safe(deserializer.file_version) }.is_false() {
+            Ok(regular_deserialize_vec(deserializer)?)
+        } else {
+            use std::mem;
+
+            let align = mem::align_of::<T>();
+            let elem_size = mem::size_of::<T>();
+            let num_elems = deserializer.read_usize()?;
+
+            if num_elems == 0 {
+                return Ok(Vec::new());
+            }
+            let num_bytes = elem_size * num_elems;
+
+            let layout = if let Ok(layout) = std::alloc::Layout::from_size_align(num_bytes, align) {
+                Ok(layout)
+            } else {
+                Err(SavefileError::MemoryAllocationLayoutError)
+            }?;
+            let ptr = if elem_size == 0 {
+                NonNull::dangling().as_ptr()
+            } else {
+                let ptr = unsafe { std::alloc::alloc(layout) };
+                if ptr.is_null() {
+                    panic!("Failed to allocate {} bytes of memory", num_bytes);
+                }
+
+                ptr
+            };
+
+            {
+                let slice = unsafe { std::slice::from_raw_parts_mut(ptr, num_bytes) };
+                match deserializer.reader.read_exact(slice) {
+                    Ok(()) => Ok(()),
+                    Err(err) => {
+                        unsafe {
+                            std::alloc::dealloc(ptr, layout);
+                        }
+                        Err(err)
+                    }
+                }?;
+            }
+            let ret = unsafe { Vec::from_raw_parts(ptr as *mut T, num_elems, num_elems) };
+            Ok(ret)
+        }
+    }
+}
+
+impl<T: Introspect> Introspect for VecDeque<T> {
+    fn introspect_value(&self) -> String {
+        format!("VecDeque<{}>", std::any::type_name::<T>())
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if let Some(val) = self.get(index) {
+            Some(introspect_item(index.to_string(), val))
+        } else {
+            None
+        }
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: IntrospectMut> IntrospectMut for VecDeque<T> {
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        let item = self.get_mut(index)?;
+        Some(introspect_item_mut(index.to_string(), item))
+    }
+}
+
+impl<T: WithSchema> WithSchema for VecDeque<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+
+impl<T> Packed for VecDeque<T> {}
+impl<T: Serialize> Serialize for VecDeque<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        regular_serialize_vecdeque(self, serializer)
+    }
+}
+
+impl<T: Deserialize> Deserialize for VecDeque<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(regular_deserialize_vecdeque(deserializer)?)
+    }
+}
+
+fn regular_serialize_vecdeque<T: Serialize>(
+    item: &VecDeque<T>,
+    serializer: &mut Serializer<impl Write>,
+) -> Result<(), SavefileError> {
+    let l = item.len();
+    serializer.write_usize(l)?;
+    for item in item.iter() {
+        item.serialize(serializer)?
+    }
+    Ok(())
+}
+
+fn regular_deserialize_vecdeque<T: Deserialize>(
+    deserializer: &mut Deserializer<impl Read>,
+) -> Result<VecDeque<T>, SavefileError> {
+    let l = deserializer.read_usize()?;
+    let mut ret = VecDeque::with_capacity(l);
+    for _ in 0..l {
+        ret.push_back(T::deserialize(deserializer)?);
+    }
+    Ok(ret)
+}
+
+impl Packed for bool {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+} //It isn't really guaranteed that bool is an u8 or i8 where false = 0 and true = 1. But it's true in practice. And the breakage would be hard to measure if this were ever changed, so a change is unlikely.
+impl Packed for u8 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for i8 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for u16 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for i16 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for u32 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for i32 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for u64 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for u128 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for i128 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for i64 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for char {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for f32 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for f64 {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+impl Packed for usize {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::no()
+    } // Doesn't have a fixed size
+}
+impl Packed for isize {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::no()
+    } // Doesn't have a fixed size
+}
+impl Packed for () {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        IsPacked::yes()
+    }
+}
+
+impl<T: WithSchema, const N: usize> WithSchema for [T; N] {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Array(SchemaArray {
+            item_type: Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            count: N,
+        })
+    }
+}
+
+impl<T: Introspect, const N: usize> Introspect for [T; N] {
+    fn introspect_value(&self) -> String {
+        format!("[{}; {}]", std::any::type_name::<T>(), N)
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index >= self.len() {
+            None
+        } else {
+            Some(introspect_item(index.to_string(), &self[index]))
+        }
+    }
+}
+
+impl<T: IntrospectMut, const N: usize> IntrospectMut for [T; N] {
+    fn introspect_child_mut<'a>(&'a mut self, index: usize) -> Option<Box<dyn IntrospectItemMut<'a> + 'a>> {
+        let item = self.get_mut(index)?;
+        Some(introspect_item_mut(index.to_string(), item))
+    }
+}
+
+impl<T: Packed, const N: usize> Packed for [T; N] {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        T::repr_c_optimization_safe(version)
+    }
+}
+impl<T: Serialize + Packed, const N: usize> Serialize for [T; N] {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        if N == 0 {
+            return Ok(());
+        }
+        unsafe {
+            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
+                for item in self.iter() {
+                    item.serialize(serializer)?
+                }
+                Ok(())
+            } else {
+                serializer.write_buf(std::slice::from_raw_parts(
+                    self.as_ptr() as *const u8,
+                    std::mem::size_of::<T>() * N,
+                ))
+            }
+        }
+    }
+}
+
+impl<T: Deserialize + Packed, const N: usize> Deserialize for [T; N] {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        if N == 0 {
+            return Ok([(); N].map(|_| unreachable!()));
+        }
+
+        if unsafe { T::repr_c_optimization_safe(deserializer.file_version) }.is_false() {
+            let mut data: [MaybeUninit<T>; N] = unsafe {
+                MaybeUninit::uninit().assume_init() //This seems strange, but is correct according to rust docs: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html, see chapter 'Initializing an array element-by-element'
+            };
+            for idx in 0..N {
+                data[idx] = MaybeUninit::new(T::deserialize(deserializer)?); //This leaks on panic, but we shouldn't panic and at least it isn't UB!
+            }
+            let ptr = &mut data as *mut _ as *mut [T; N];
+            let res = unsafe { ptr.read() };
+            Ok(res)
+        } else {
+            let mut data: [MaybeUninit<T>; N] = unsafe {
+                MaybeUninit::uninit().assume_init() //This seems strange, but is correct according to rust docs: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html, see chapter 'Initializing an array element-by-element'
+            };
+
+            {
+                let ptr = data.as_mut_ptr();
+                let num_bytes: usize = std::mem::size_of::<T>() * N;
+                let slice: &mut [MaybeUninit<u8>] =
+                    unsafe { std::slice::from_raw_parts_mut(ptr as *mut MaybeUninit<u8>, num_bytes) };
+                deserializer
+                    .reader
+                    .read_exact(unsafe { std::mem::transmute::<&mut [MaybeUninit<u8>], &mut [u8]>(slice) })?;
+            }
+            let ptr = &mut data as *mut _ as *mut [T; N];
+            let res = unsafe { ptr.read() };
+            Ok(res)
+        }
+    }
+}
+
+impl<T1> Packed for Range<T1> {}
+impl<T1: WithSchema> WithSchema for Range<T1> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::new_tuple2::<T1, T1>(version, context)
+    }
+}
+impl<T1: Serialize> Serialize for Range<T1> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.start.serialize(serializer)?;
+        self.end.serialize(serializer)?;
+        Ok(())
+    }
+}
+impl<T1: Deserialize> Deserialize for Range<T1> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(T1::deserialize(deserializer)?..T1::deserialize(deserializer)?)
+    }
+}
+impl<T1: Introspect> Introspect for Range<T1> {
+    fn introspect_value(&self) -> String {
+        return "Range".to_string();
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        if index == 0 {
+            return Some(introspect_item("start".to_string(), &self.start));
+        }
+        if index == 1 {
+            return Some(introspect_item("end".to_string(), &self.end));
+        }
+        return None;
+    }
+}
+
+impl<T> Packed for RangeInclusive<T> {}
+impl<T: WithSchema> WithSchema for RangeInclusive<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "RangeInclusive".to_string(),
+            vec![
+                Field::new("start".to_string(), Box::new(T::schema(version, context))),
+                Field::new("end".to_string(), Box::new(T::schema(version, context))),
+            ],
+        ))
+    }
+}
+impl<T: Serialize> Serialize for RangeInclusive<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.start().serialize(serializer)?;
+        self.end().serialize(serializer)?;
+        Ok(())
+    }
+}
+impl<T: Deserialize> Deserialize for RangeInclusive<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let start = T::deserialize(deserializer)?;
+        let end = T::deserialize(deserializer)?;
+        Ok(start..=end)
+    }
+}
+impl<T: Introspect> Introspect for RangeInclusive<T> {
+    fn introspect_value(&self) -> String {
+        "RangeInclusive".to_string()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        match index {
+            0 => Some(introspect_item("start".to_string(), self.start())),
+            1 => Some(introspect_item("end".to_string(), self.end())),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Packed for Bound<T> {}
+impl<T: WithSchema> WithSchema for Bound<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        let mut value_variant = |name: &str, discriminant: u32| Variant {
+            name: name.to_string(),
+            discriminant,
+            fields: vec![Field::new("0".to_string(), Box::new(T::schema(version, context)))],
+        };
+        let included = value_variant("Included", 0);
+        let excluded = value_variant("Excluded", 1);
+        Schema::Enum(SchemaEnum::new(
+            "Bound".to_string(),
+            1,
+            vec![
+                included,
+                excluded,
+                Variant {
+                    name: "Unbounded".to_string(),
+                    discriminant: 2,
+                    fields: vec![],
+                },
+            ],
+        ))
+    }
+}
+impl<T: Serialize> Serialize for Bound<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        match self {
+            Bound::Included(x) => {
+                serializer.write_u8(0)?;
+                x.serialize(serializer)
+            }
+            Bound::Excluded(x) => {
+                serializer.write_u8(1)?;
+                x.serialize(serializer)
+            }
+            Bound::Unbounded => serializer.write_u8(2),
+        }
+    }
+}
+impl<T: Deserialize> Deserialize for Bound<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        match deserializer.read_u8()? {
+            0 => Ok(Bound::Included(T::deserialize(deserializer)?)),
+            1 => Ok(Bound::Excluded(T::deserialize(deserializer)?)),
+            2 => Ok(Bound::Unbounded),
+            _ => Err(SavefileError::GeneralError {
+                msg: "Corrupt file - unknown enum variant detected.".to_string(),
+            }),
+        }
+    }
+}
+impl<T: Introspect> Introspect for Bound<T> {
+    fn introspect_value(&self) -> String {
+        match self {
+            Bound::Included(_) => "Included".to_string(),
+            Bound::Excluded(_) => "Excluded".to_string(),
+            Bound::Unbounded => "Unbounded".to_string(),
+        }
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        match self {
+            Bound::Included(x) | Bound::Excluded(x) if index == 0 => Some(introspect_item("0".to_string(), x)),
+            _ => None,
+        }
+    }
+}
+
+impl Packed for std::cmp::Ordering {}
+impl WithSchema for std::cmp::Ordering {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        let variant = |name: &str, discriminant: u32| Variant {
+            name: name.to_string(),
+            discriminant,
+            fields: vec![],
+        };
+        Schema::Enum(SchemaEnum::new(
+            "Ordering".to_string(),
+            1,
+            vec![variant("Less", 0), variant("Equal", 1), variant("Greater", 2)],
+        ))
+    }
+}
+impl Serialize for std::cmp::Ordering {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_u8(match self {
+            std::cmp::Ordering::Less => 0,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Greater => 2,
+        })
+    }
+}
+impl Deserialize for std::cmp::Ordering {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        match deserializer.read_u8()? {
+            0 => Ok(std::cmp::Ordering::Less),
+            1 => Ok(std::cmp::Ordering::Equal),
+            2 => Ok(std::cmp::Ordering::Greater),
+            _ => Err(SavefileError::GeneralError {
+                msg: "Corrupt file - unknown enum variant detected.".to_string(),
+            }),
+        }
+    }
+}
+impl Introspect for std::cmp::Ordering {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+// Wrapping, Saturating and Reverse are all repr(transparent), and are saved
+// exactly like the wrapped value.
+
+impl<T: Packed> Packed for Wrapping<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        T::repr_c_optimization_safe(version)
+    }
+}
+impl<T: WithSchema> WithSchema for Wrapping<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        T::schema(version, context)
+    }
+}
+impl<T: Serialize> Serialize for Wrapping<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)
+    }
+}
+impl<T: Deserialize> Deserialize for Wrapping<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Wrapping(T::deserialize(deserializer)?))
+    }
+}
+impl<T: Introspect> Introspect for Wrapping<T> {
+    fn introspect_value(&self) -> String {
+        self.0.introspect_value()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.0.introspect_typed_value()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        self.0.introspect_child(index)
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.0.introspect_len()
+    }
+}
+
+impl<T: Packed> Packed for Saturating<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        T::repr_c_optimization_safe(version)
+    }
+}
+impl<T: WithSchema> WithSchema for Saturating<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        T::schema(version, context)
+    }
+}
+impl<T: Serialize> Serialize for Saturating<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)
+    }
+}
+impl<T: Deserialize> Deserialize for Saturating<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Saturating(T::deserialize(deserializer)?))
+    }
+}
+impl<T: Introspect> Introspect for Saturating<T> {
+    fn introspect_value(&self) -> String {
+        self.0.introspect_value()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.0.introspect_typed_value()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        self.0.introspect_child(index)
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.0.introspect_len()
+    }
+}
+
+impl<T: Packed> Packed for Reverse<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        T::repr_c_optimization_safe(version)
+    }
+}
+impl<T: WithSchema> WithSchema for Reverse<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        T::schema(version, context)
+    }
+}
+impl<T: Serialize> Serialize for Reverse<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)
+    }
+}
+impl<T: Deserialize> Deserialize for Reverse<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(Reverse(T::deserialize(deserializer)?))
+    }
+}
+impl<T: Introspect> Introspect for Reverse<T> {
+    fn introspect_value(&self) -> String {
+        self.0.introspect_value()
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        self.0.introspect_typed_value()
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        self.0.introspect_child(index)
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.0.introspect_len()
+    }
+}
+
+/// The non-zero integer types are saved like the corresponding primitive.
+/// They are not Packed, since loading must check that the value is not zero.
+macro_rules! impl_nonzero {
+    ($nonzero:ty, $primitive:ty) => {
+        impl Packed for $nonzero {}
+        impl WithSchema for $nonzero {
+            fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+                <$primitive>::schema(version, context)
+            }
+        }
+        impl Serialize for $nonzero {
+            fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+                self.get().serialize(serializer)
+            }
+        }
+        impl Deserialize for $nonzero {
+            fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+                <$nonzero>::new(<$primitive>::deserialize(deserializer)?).ok_or_else(|| SavefileError::GeneralError {
+                    msg: format!("Corrupt file - zero value for {}", stringify!($nonzero)),
+                })
+            }
+        }
+        impl Introspect for $nonzero {
+            fn introspect_value(&self) -> String {
+                self.get().to_string()
+            }
+
+            fn introspect_typed_value(&self) -> IntrospectValue {
+                self.get().introspect_typed_value()
+            }
+
+            fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+                None
+            }
+        }
+    };
+}
+impl_nonzero!(std::num::NonZeroU8, u8);
+impl_nonzero!(std::num::NonZeroU16, u16);
+impl_nonzero!(std::num::NonZeroU32, u32);
+impl_nonzero!(std::num::NonZeroU64, u64);
+impl_nonzero!(std::num::NonZeroU128, u128);
+impl_nonzero!(std::num::NonZeroUsize, usize);
+impl_nonzero!(std::num::NonZeroI8, i8);
+impl_nonzero!(std::num::NonZeroI16, i16);
+impl_nonzero!(std::num::NonZeroI32, i32);
+impl_nonzero!(std::num::NonZeroI64, i64);
+impl_nonzero!(std::num::NonZeroI128, i128);
+impl_nonzero!(std::num::NonZeroIsize, isize);
+
+impl<T: Introspect> Introspect for LinkedList<T> {
+    fn introspect_value(&self) -> String {
+        format!("LinkedList<{}>", std::any::type_name::<T>())
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .nth(index)
+            .map(|val| introspect_item(index.to_string(), val))
+    }
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .map(|(index, val)| introspect_item(index.to_string(), val))
+            .collect()
+    }
+
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
+impl<T: WithSchema> WithSchema for LinkedList<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Vector(
+            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            VecOrStringLayout::Unknown,
+        )
+    }
+}
+impl<T> Packed for LinkedList<T> {}
+impl<T: Serialize> Serialize for LinkedList<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.len())?;
+        for item in self.iter() {
+            item.serialize(serializer)?
+        }
+        Ok(())
+    }
+}
+impl<T: Deserialize> Deserialize for LinkedList<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = LinkedList::new();
+        for _ in 0..l {
+            ret.push_back(T::deserialize(deserializer)?);
+        }
+        Ok(ret)
+    }
+}
+
+/// Schema of a byte string, the same as for `Vec<u8>`.
+fn byte_string_schema() -> Schema {
+    Schema::Vector(
+        Box::new(Schema::Primitive(SchemaPrimitive::schema_u8)),
+        VecOrStringLayout::Unknown,
+    )
+}
+
+// OsString is saved as its platform specific encoding, which is the same on all platforms
+// for valid UTF-8. On platforms other than unix, loading fails for strings which
+// aren't valid UTF-8.
+impl Packed for OsString {}
+impl WithSchema for OsString {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        byte_string_schema()
+    }
+}
+impl Serialize for OsString {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bytes = self.as_encoded_bytes();
+        serializer.write_usize(bytes.len())?;
+        serializer.write_bytes(bytes)
+    }
+}
+impl Deserialize for OsString {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let bytes = deserializer.read_bytes(l)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            Ok(OsString::from_vec(bytes))
+        }
+        #[cfg(not(unix))]
+        {
+            Ok(OsString::from(String::from_utf8(bytes)?))
+        }
+    }
+}
+impl Introspect for OsString {
+    fn introspect_value(&self) -> String {
+        self.to_string_lossy().to_string()
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+impl Packed for CString {}
+impl WithSchema for CString {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        byte_string_schema()
+    }
+}
+impl Serialize for CString {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let bytes = self.as_bytes();
+        serializer.write_usize(bytes.len())?;
+        serializer.write_bytes(bytes)
+    }
+}
+impl Deserialize for CString {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let bytes = deserializer.read_bytes(l)?;
+        CString::new(bytes).map_err(|_| SavefileError::GeneralError {
+            msg: "Corrupt file - CString contained a nul byte".to_string(),
+        })
+    }
+}
+impl Introspect for CString {
+    fn introspect_value(&self) -> String {
+        self.to_string_lossy().to_string()
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+impl Packed for Box<str> {}
+impl WithSchema for Box<str> {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+    }
+}
+impl Serialize for Box<str> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_string(self)
+    }
+}
+impl Deserialize for Box<str> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(deserializer.read_string()?.into_boxed_str())
+    }
+}
+impl Introspect for Box<str> {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+impl Packed for Rc<str> {}
+impl WithSchema for Rc<str> {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+    }
+}
+impl Serialize for Rc<str> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_string(self)
+    }
+}
+impl Deserialize for Rc<str> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(deserializer.read_string()?.into())
+    }
+}
+impl Introspect for Rc<str> {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
+
+    fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        None
+    }
+}
+
+impl<T1: Packed> Packed for (T1,) {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        if offset_of_tuple!((T1,), 0) == 0 && std::mem::size_of::<T1>() == std::mem::size_of::<(T1,)>() {
+            T1::repr_c_optimization_safe(version)
+        } else {
+            IsPacked::no()
+        }
+    }
+}
+impl<T1: Packed, T2: Packed> Packed for (T1, T2) {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        if offset_of_tuple!((T1, T2), 0) == 0
+            && std::mem::size_of::<T1>() + std::mem::size_of::<T2>() == std::mem::size_of::<(T1, T2)>()
+        {
+            T1::repr_c_optimization_safe(version) & T2::repr_c_optimization_safe(version)
+        } else {
+            IsPacked::no()
+        }
+    }
+}
+impl<T1: Packed, T2: Packed, T3: Packed> Packed for (T1, T2, T3) {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        if offset_of_tuple!((T1, T2, T3), 0) == 0
+            && offset_of_tuple!((T1, T2, T3), 1) == std::mem::size_of::<T1>()
+            && std::mem::size_of::<T1>() + std::mem::size_of::<T2>() + std::mem::size_of::<T3>()
+                == std::mem::size_of::<(T1, T2, T3)>()
+        {
+            T1::repr_c_optimization_safe(version)
+                & T2::repr_c_optimization_safe(version)
+                & T3::repr_c_optimization_safe(version)
+        } else {
+            IsPacked::no()
+        }
+    }
+}
+impl<T1: Packed, T2: Packed, T3: Packed, T4: Packed> Packed for (T1, T2, T3, T4) {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        if offset_of_tuple!((T1, T2, T3, T4), 0) == 0
+            && offset_of_tuple!((T1, T2, T3, T4), 1) == std::mem::size_of::<T1>()
+            && offset_of_tuple!((T1, T2, T3, T4), 2) == std::mem::size_of::<T1>() + std::mem::size_of::<T2>()
+            && std::mem::size_of::<T1>()
+                + std::mem::size_of::<T2>()
+                + std::mem::size_of::<T3>()
+                + std::mem::size_of::<T4>()
+                == std::mem::size_of::<(T1, T2, T3, T4)>()
+        {
+            T1::repr_c_optimization_safe(version)
+                & T2::repr_c_optimization_safe(version)
+                & T3::repr_c_optimization_safe(version)
+                & T4::repr_c_optimization_safe(version)
+        } else {
+            IsPacked::no()
+        }
+    }
+}
+
+impl<T1: WithSchema, T2: WithSchema, T3: WithSchema> WithSchema for (T1, T2, T3) {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::new_tuple3::<T1, T2, T3>(version, context)
+    }
+}
+impl<T1: Serialize, T2: Serialize, T3: Serialize> Serialize for (T1, T2, T3) {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)?;
+        self.1.serialize(serializer)?;
+        self.2.serialize(serializer)
+    }
+}
+impl<T1: Deserialize, T2: Deserialize, T3: Deserialize> Deserialize for (T1, T2, T3) {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok((
+            T1::deserialize(deserializer)?,
+            T2::deserialize(deserializer)?,
+            T3::deserialize(deserializer)?,
+        ))
+    }
+}
+
+impl<T1: WithSchema, T2: WithSchema> WithSchema for (T1, T2) {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::new_tuple2::<T1, T2>(version, context)
+    }
+}
+impl<T1: Serialize, T2: Serialize> Serialize for (T1, T2) {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)?;
+        self.1.serialize(serializer)
+    }
+}
+impl<T1: Deserialize, T2: Deserialize> Deserialize for (T1, T2) {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok((T1::deserialize(deserializer)?, T2::deserialize(deserializer)?))
+    }
+}
+
+impl<T1: WithSchema> WithSchema for (T1,) {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::new_tuple1::<T1>(version, context)
+    }
+}
+impl<T1: Serialize> Serialize for (T1,) {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.0.serialize(serializer)
+    }
+}
+impl<T1: Deserialize> Deserialize for (T1,) {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok((T1::deserialize(deserializer)?,))
+    }
+}
+
+/// Implements the savefile traits for tuples with 4 or more elements.
+/// The 4-tuple already has hand written `Packed` and `Introspect` impls, so only
+/// gets the 'core' traits.
+macro_rules! impl_tuple {
+    (@core $len:literal, $tup:ty; $($t:ident $idx:tt),+) => {
+        impl<$($t: WithSchema),+> WithSchema for $tup {
+            fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+                Schema::Struct(SchemaStruct {
+                    dbg_name: concat!(stringify!($len), "-Tuple").to_string(),
+                    size: Some(std::mem::size_of::<$tup>()),
+                    alignment: Some(std::mem::align_of::<$tup>()),
+                    fields: vec![
+                        $(
+                            Field {
+                                name: stringify!($idx).to_string(),
+                                value: Box::new($t::schema(version, context)),
+                                offset: Some(offset_of_tuple!($tup, $idx)),
+                            },
+                        )+
+                    ],
+                })
+            }
+        }
+        impl<$($t: Serialize),+> Serialize for $tup {
+            fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+                $(
+                    self.$idx.serialize(serializer)?;
+                )+
+                Ok(())
+            }
+        }
+        impl<$($t: Deserialize),+> Deserialize for $tup {
+            fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+                Ok(($($t::deserialize(deserializer)?,)+))
+            }
+        }
+    };
+    (@all $len:literal, $tup:ty; $($t:ident $idx:tt),+) => {
+        impl_tuple!(@core $len, $tup; $($t $idx),+);
+        impl<$($t: Packed),+> Packed for $tup {
+            unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+                // All members must be laid out in declaration order, without padding.
+                let mut offset = 0;
+                $(
+                    if offset_of_tuple!($tup, $idx) != offset {
+                        return IsPacked::no();
+                    }
+                    offset += std::mem::size_of::<$t>();
+                )+
+                if offset != std::mem::size_of::<$tup>() {
+                    return IsPacked::no();
+                }
+                $($t::repr_c_optimization_safe(version))&+
+            }
+        }
+        impl<$($t: Introspect),+> Introspect for $tup {
+            fn introspect_value(&self) -> String {
+                concat!(stringify!($len), "-tuple").to_string()
+            }
+
+            fn introspect_typed_value(&self) -> IntrospectValue {
+                IntrospectValue::None
+            }
+
+            fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+                match index {
+                    $(
+                        $idx => Some(introspect_item(stringify!($idx).to_string(), &self.$idx)),
+                    )+
+                    _ => None,
+                }
+            }
+
+            fn introspect_len(&self) -> usize {
+                $len
+            }
+        }
+    };
+    ($kind:ident $len:literal; $($t:ident $idx:tt),+) => {
+        impl_tuple!(@ $kind $len, ($($t,)+); $($t $idx),+);
+    };
+}
+impl_tuple!(core 4; T1 0, T2 1, T3 2, T4 3);
+impl_tuple!(all 5; T1 0, T2 1, T3 2, T4 3, T5 4);
+impl_tuple!(all 6; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5);
+impl_tuple!(all 7; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6);
+impl_tuple!(all 8; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7);
+impl_tuple!(all 9; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8);
+impl_tuple!(all 10; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9);
+impl_tuple!(all 11; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9, T11 10);
+impl_tuple!(all 12; T1 0, T2 1, T3 2, T4 3, T5 4, T6 5, T7 6, T8 7, T9 8, T10 9, T11 10, T12 11);
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar, const D: usize> Introspect for nalgebra::Point<T, D> {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar, const R: usize, const C: usize> Introspect for nalgebra::SMatrix<T, R, C> {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> Introspect for nalgebra::Isometry3<T> {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> Introspect for nalgebra::Isometry2<T> {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> Introspect for nalgebra::UnitQuaternion<T> {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
+    }
+}
+
+/// Schema of a statically sized nalgebra matrix. Column vectors are arrays of their
+/// elements, other matrices are arrays of columns.
+#[cfg(feature = "nalgebra")]
+fn nalgebra_matrix_schema<T: WithSchema>(
+    rows: usize,
+    columns: usize,
+    version: u32,
+    context: &mut WithSchemaContext,
+) -> Schema {
+    let column = Schema::Array(SchemaArray {
+        item_type: Box::new(T::schema(version, context)),
+        count: rows,
+    });
+    if columns == 1 {
+        column
+    } else {
+        Schema::Array(SchemaArray {
+            item_type: Box::new(column),
+            count: columns,
+        })
+    }
+}
+
+/// nalgebra's statically sized matrices (and points, quaternions and so on) are all
+/// repr(C), and consist of just the elements. This checks that there's no padding.
+#[cfg(feature = "nalgebra")]
+unsafe fn nalgebra_packed<T: Packed, M>(elements: usize, version: u32) -> IsPacked {
+    if std::mem::size_of::<M>() == elements * std::mem::size_of::<T>() {
+        T::repr_c_optimization_safe(version)
+    } else {
+        IsPacked::no()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: Packed + nalgebra::Scalar, const D: usize> Packed for nalgebra::Point<T, D> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        nalgebra_packed::<T, Self>(D, version)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: WithSchema + nalgebra::Scalar, const D: usize> WithSchema for nalgebra::Point<T, D> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        nalgebra_matrix_schema::<T>(D, 1, version, context)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Serialize + Packed + nalgebra::Scalar, const D: usize> Serialize for nalgebra::Point<T, D> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.coords.serialize(serializer)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Deserialize + Packed + nalgebra::Scalar, const D: usize> Deserialize for nalgebra::Point<T, D> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(nalgebra::Point::from(nalgebra::SVector::<T, D>::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: Packed + nalgebra::Scalar, const R: usize, const C: usize> Packed for nalgebra::SMatrix<T, R, C> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        nalgebra_packed::<T, Self>(R * C, version)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: WithSchema + nalgebra::Scalar, const R: usize, const C: usize> WithSchema for nalgebra::SMatrix<T, R, C> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        nalgebra_matrix_schema::<T>(R, C, version, context)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Serialize + Packed + nalgebra::Scalar, const R: usize, const C: usize> Serialize
+    for nalgebra::SMatrix<T, R, C>
+{
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        // The storage is column major, which is also the order of the schema
+        self.data.0.serialize(serializer)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Deserialize + Packed + nalgebra::Scalar, const R: usize, const C: usize> Deserialize
+    for nalgebra::SMatrix<T, R, C>
+{
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(nalgebra::SMatrix::from_data(nalgebra::ArrayStorage(
+            <[[T; R]; C]>::deserialize(deserializer)?,
+        )))
+    }
+}
+
+// Saved in memory order, i, j, k, w. Note that this is different from the
+// order used within Isometry3.
+#[cfg(feature = "nalgebra")]
+impl<T: Packed + nalgebra::Scalar> Packed for nalgebra::UnitQuaternion<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        nalgebra_packed::<T, Self>(4, version)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: WithSchema + nalgebra::Scalar> WithSchema for nalgebra::UnitQuaternion<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        nalgebra_matrix_schema::<T>(4, 1, version, context)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Serialize + Packed + nalgebra::Scalar> Serialize for nalgebra::UnitQuaternion<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.as_ref().coords.serialize(serializer)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Deserialize + Packed + nalgebra::Scalar> Deserialize for nalgebra::UnitQuaternion<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(nalgebra::UnitQuaternion::new_unchecked(nalgebra::Quaternion {
+            coords: nalgebra::Vector4::deserialize(deserializer)?,
+        }))
+    }
+}
+
+// Saved in memory order, the rotation as a complex number (re, im), then the translation.
+#[cfg(feature = "nalgebra")]
+impl<T: Packed + nalgebra::Scalar> Packed for nalgebra::Isometry2<T> {
+    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+        nalgebra_packed::<T, Self>(4, version)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: WithSchema + nalgebra::Scalar> WithSchema for nalgebra::Isometry2<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "Isometry2".to_string(),
+            vec![
+                Field::new(
+                    "rotation".to_string(),
+                    Box::new(nalgebra_matrix_schema::<T>(2, 1, version, context)),
+                ),
+                Field::new(
+                    "translation".to_string(),
+                    Box::new(nalgebra_matrix_schema::<T>(2, 1, version, context)),
+                ),
+            ],
+        ))
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Serialize + Packed + nalgebra::Scalar> Serialize for nalgebra::Isometry2<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.rotation.re.serialize(serializer)?;
+        self.rotation.im.serialize(serializer)?;
+        self.translation.vector.serialize(serializer)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Deserialize + Packed + nalgebra::Scalar> Deserialize for nalgebra::Isometry2<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let re = T::deserialize(deserializer)?;
+        let im = T::deserialize(deserializer)?;
+        Ok(nalgebra::Isometry2 {
+            rotation: nalgebra::UnitComplex::new_unchecked(nalgebra::Complex { re, im }),
+            translation: nalgebra::Translation2::from(nalgebra::Vector2::deserialize(deserializer)?),
+        })
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: Packed> Packed for nalgebra::Isometry3<T> {}
+
+#[cfg(feature = "nalgebra")]
+impl<T: WithSchema> WithSchema for nalgebra::Isometry3<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Array(SchemaArray {
+            item_type: Box::new(T::schema(version, context)),
+            count: 7,
+        })
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Serialize + Packed + WithSchema + nalgebra::Scalar> Serialize for nalgebra::Isometry3<T> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        self.translation.vector.x.serialize(serializer)?;
+        self.translation.vector.y.serialize(serializer)?;
+        self.translation.vector.z.serialize(serializer)?;
+
+        self.rotation.coords.w.serialize(serializer)?;
+        self.rotation.coords.x.serialize(serializer)?;
+        self.rotation.coords.y.serialize(serializer)?;
+        self.rotation.coords.z.serialize(serializer)?;
+
+        Ok(())
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T: Deserialize + Packed + WithSchema + nalgebra::Scalar + nalgebra::SimdValue + nalgebra::RealField> Deserialize
+    for nalgebra::Isometry3<T>
+{
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        Ok(nalgebra::Isometry3::from_parts(
+            nalgebra::Point3::new(
+                <T as Deserialize>::deserialize(deserializer)?,
+                <T as Deserialize>::deserialize(deserializer)?,
+                <T as Deserialize>::deserialize(deserializer)?,
+            )
+            .into(),
+            nalgebra::UnitQuaternion::new_unchecked(nalgebra::Quaternion::new(
+                <T as Deserialize>::deserialize(deserializer)?,
+                <T as Deserialize>::deserialize(deserializer)?,
+                <T as Deserialize>::deserialize(deserializer)?,
+                <T as Deserialize>::deserialize(deserializer)?,
+            )),
+        ))
+    }
+}
+
+/// glam types are saved like the arrays returned by the given 'to'-method,
+/// so `Vec3` has the same schema as `[f32; 3]` and nalgebra's `Vector3<f32>`.
+/// Matrices are saved column by column.
+#[cfg(feature = "glam")]
+macro_rules! impl_glam {
+    ($t:ty, $array:ty, $to:ident, $from:ident) => {
+        impl Packed for $t {
+            unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
+                if std::mem::size_of::<$t>() == std::mem::size_of::<$array>() {
+                    <$array>::repr_c_optimization_safe(version)
+                } else {
+                    IsPacked::no()
+                }
+            }
+        }
+        impl WithSchema for $t {
+            fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+                <$array>::schema(version, context)
+            }
+        }
+        impl Serialize for $t {
+            fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+                self.$to().serialize(serializer)
+            }
+        }
+        impl Deserialize for $t {
+            fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+                Ok(<$t>::$from(&<$array>::deserialize(deserializer)?))
+            }
+        }
+        impl Introspect for $t {
+            fn introspect_value(&self) -> String {
+                format!("{:?}", self)
+            }
+
+            fn introspect_typed_value(&self) -> IntrospectValue {
+                IntrospectValue::None
+            }
+
+            fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+                None
+            }
+        }
+    };
+}
+#[cfg(feature = "glam")]
+impl_glam!(glam::Vec2, [f32; 2], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::Vec3, [f32; 3], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::Vec4, [f32; 4], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::Quat, [f32; 4], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::Mat3, [[f32; 3]; 3], to_cols_array_2d, from_cols_array_2d);
+#[cfg(feature = "glam")]
+impl_glam!(glam::Mat4, [[f32; 4]; 4], to_cols_array_2d, from_cols_array_2d);
+#[cfg(feature = "glam")]
+impl_glam!(glam::DVec2, [f64; 2], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::DVec3, [f64; 3], to_array, from_slice);
+#[cfg(feature = "glam")]
+impl_glam!(glam::DVec4, [f64; 4], to_array, from_slice);
+
+/// The schema of a map, the same as for `HashMap<K, V>`.
+#[cfg(any(feature = "hashbrown", feature = "im"))]
+fn map_schema<K: WithSchema, V: WithSchema>(version: u32, context: &mut WithSchemaContext) -> Schema {
+    Schema::Vector(
+        Box::new(Schema::Struct(SchemaStruct {
+            dbg_name: "KeyValuePair".to_string(),
+            size: None,
+            alignment: None,
+            fields: vec![
+                Field {
+                    name: "key".to_string(),
+                    value: Box::new(context.possible_recursion::<K>(|context| K::schema(version, context))),
+                    offset: None,
+                },
+                Field {
+                    name: "value".to_string(),
+                    value: Box::new(context.possible_recursion::<V>(|context| V::schema(version, context))),
+                    offset: None,
+                },
+            ],
+        })),
+        VecOrStringLayout::Unknown,
+    )
 }
-impl RawSliceInspector {
-    const fn get_layout(&self) -> VecOrStringLayout {
-        if self.p1 == 0 {
-            VecOrStringLayout::LengthData
-        } else {
-            VecOrStringLayout::DataLength
-        }
-    }
+
+/// The schema of a collection of T, the same as for `Vec<T>` and `HashSet<T>`.
+#[cfg(any(feature = "hashbrown", feature = "im", feature = "tinyvec"))]
+fn sequence_schema<T: WithSchema>(version: u32, context: &mut WithSchemaContext) -> Schema {
+    Schema::Vector(
+        Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+        VecOrStringLayout::Unknown,
+    )
 }
-impl RawVecInspector {
-    fn get_layout(&self, ptr: *const u8) -> VecOrStringLayout {
-        let ptr = ptr as usize;
-        // We know size is 1, and capacity is 2.
-        const LENGTH: usize = 0;
-        const CAPACITY: usize = 7;
-        match (self.p1, self.p2, self.p3) {
-            (LENGTH, CAPACITY, x) if x == ptr => VecOrStringLayout::LengthCapacityData,
-            (CAPACITY, LENGTH, x) if x == ptr => VecOrStringLayout::CapacityLengthData,
-            (LENGTH, x, CAPACITY) if x == ptr => VecOrStringLayout::LengthDataCapacity,
-            (CAPACITY, x, LENGTH) if x == ptr => VecOrStringLayout::CapacityDataLength,
-            (x, LENGTH, CAPACITY) if x == ptr => VecOrStringLayout::DataLengthCapacity,
-            (x, CAPACITY, LENGTH) if x == ptr => VecOrStringLayout::DataCapacityLength,
-            _ => VecOrStringLayout::Unknown,
-        }
+
+#[cfg(feature = "hashbrown")]
+impl<K, V, S> Packed for hashbrown::HashMap<K, V, S> {}
+#[cfg(feature = "hashbrown")]
+impl<K: WithSchema, V: WithSchema, S> WithSchema for hashbrown::HashMap<K, V, S> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        map_schema::<K, V>(version, context)
     }
 }
-
-/// Calculate the memory layout of `&[T]`.
-///
-/// I.e, of the reference to the data.
-/// This type is typically 16 bytes, consisting of two words, one being the length,
-/// the other being a pointer to the start of the data.
-pub const fn calculate_slice_memory_layout<T>() -> VecOrStringLayout {
-    if std::mem::size_of::<&[T]>() != 16 || std::mem::size_of::<RawSliceInspector>() != 16 {
-        VecOrStringLayout::Unknown
-    } else {
-        let test_slice: &[T] = &[];
-        let insp: RawSliceInspector = unsafe { std::mem::transmute_copy::<&[T], RawSliceInspector>(&test_slice) };
-        insp.get_layout()
+#[cfg(feature = "hashbrown")]
+impl<K: Serialize, V: Serialize, S> Serialize for hashbrown::HashMap<K, V, S> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.len())?;
+        for (k, v) in self.iter() {
+            k.serialize(serializer)?;
+            v.serialize(serializer)?;
+        }
+        Ok(())
     }
 }
-/// Calculate the memory layout of a Vec of the given type
-pub fn calculate_vec_memory_layout<T>() -> VecOrStringLayout {
-    if std::mem::size_of::<Vec<u8>>() != 24 || std::mem::size_of::<RawVecInspector>() != 24 {
-        VecOrStringLayout::Unknown
-    } else {
-        let test_vec = Vec::with_capacity(7);
-        let insp: RawVecInspector = unsafe { std::mem::transmute_copy(&test_vec) };
-        let ptr = test_vec.as_ptr();
-        insp.get_layout(ptr)
+#[cfg(feature = "hashbrown")]
+impl<K: Deserialize + Eq + Hash, V: Deserialize, S: ::std::hash::BuildHasher + Default> Deserialize
+    for hashbrown::HashMap<K, V, S>
+{
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = hashbrown::HashMap::with_capacity_and_hasher(l, S::default());
+        for _ in 0..l {
+            ret.insert(K::deserialize(deserializer)?, V::deserialize(deserializer)?);
+        }
+        Ok(ret)
     }
 }
-fn calculate_string_memory_layout() -> VecOrStringLayout {
-    let mut is_std = STRING_IS_STANDARD_LAYOUT.load(Ordering::Relaxed);
-    if is_std != 255 {
-        // SAFETY
-        // We 'is_std' is always initialized using a valid VecOrStringLayout enum value,
-        // unless it's 255.
-        return unsafe { std::mem::transmute::<u8, VecOrStringLayout>(is_std) };
+#[cfg(feature = "hashbrown")]
+impl<K: Introspect, V: Introspect, S> Introspect for hashbrown::HashMap<K, V, S> {
+    fn introspect_value(&self) -> String {
+        format!(
+            "hashbrown::HashMap<{},{}>",
+            std::any::type_name::<K>(),
+            std::any::type_name::<V>()
+        )
     }
-    if std::mem::size_of::<String>() != 24 || std::mem::size_of::<RawVecInspector>() != 24 {
-        is_std = VecOrStringLayout::Unknown as u8;
-    } else {
-        let test_string = String::with_capacity(7);
-        let insp: RawVecInspector = unsafe { std::mem::transmute_copy(&test_string) };
-        let ptr = test_string.as_ptr();
 
-        is_std = insp.get_layout(ptr) as u8;
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
+    }
 
-        drop(test_string);
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        let (key, val) = self.iter().nth(index)?;
+        Some(Box::new(IntrospectItemSimple {
+            key: key.introspect_value(),
+            val,
+        }))
+    }
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .skip(start)
+            .take(count)
+            .map(|(key, val)| -> Box<dyn IntrospectItem<'_> + '_> {
+                Box::new(IntrospectItemSimple {
+                    key: key.introspect_value(),
+                    val,
+                })
+            })
+            .collect()
     }
 
-    STRING_IS_STANDARD_LAYOUT.store(is_std, Ordering::Relaxed);
-    return unsafe { std::mem::transmute::<u8, VecOrStringLayout>(is_std) };
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
 }
 
-impl<T: WithSchema> WithSchema for Vec<T> {
+#[cfg(feature = "hashbrown")]
+impl<K, S> Packed for hashbrown::HashSet<K, S> {}
+#[cfg(feature = "hashbrown")]
+impl<K: WithSchema, S> WithSchema for hashbrown::HashSet<K, S> {
     fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            calculate_vec_memory_layout::<T>(),
-        )
+        sequence_schema::<K>(version, context)
     }
 }
-
-impl<T: Introspect> Introspect for Vec<T> {
+#[cfg(feature = "hashbrown")]
+impl<K: Serialize, S> Serialize for hashbrown::HashSet<K, S> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.len())?;
+        for item in self.iter() {
+            item.serialize(serializer)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "hashbrown")]
+impl<K: Deserialize + Eq + Hash, S: ::std::hash::BuildHasher + Default> Deserialize for hashbrown::HashSet<K, S> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = hashbrown::HashSet::with_capacity_and_hasher(l, S::default());
+        for _ in 0..l {
+            ret.insert(K::deserialize(deserializer)?);
+        }
+        Ok(ret)
+    }
+}
+#[cfg(feature = "hashbrown")]
+impl<K: Introspect, S> Introspect for hashbrown::HashSet<K, S> {
     fn introspect_value(&self) -> String {
-        return "vec[]".to_string();
+        format!("hashbrown::HashSet<{}>", std::any::type_name::<K>())
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
     }
 
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if index >= self.len() {
-            return None;
-        }
-        return Some(introspect_item(index.to_string(), &self[index]));
+        let key = self.iter().nth(index)?;
+        Some(introspect_item(format!("#{}", index), key))
     }
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .enumerate()
+            .skip(start)
+            .take(count)
+            .map(|(index, key)| introspect_item(format!("#{}", index), key))
+            .collect()
+    }
+
     fn introspect_len(&self) -> usize {
         self.len()
     }
 }
 
-impl<T: Serialize + Packed> Serialize for Vec<T> {
+#[cfg(feature = "im")]
+impl<T: Clone> Packed for im::Vector<T> {}
+#[cfg(feature = "im")]
+impl<T: WithSchema + Clone> WithSchema for im::Vector<T> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        sequence_schema::<T>(version, context)
+    }
+}
+#[cfg(feature = "im")]
+impl<T: Serialize + Clone> Serialize for im::Vector<T> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        unsafe {
-            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
-                regular_serialize_vec(self, serializer)
-            } else {
-                let l = self.len();
-                serializer.write_usize(l)?;
-                serializer.write_buf(std::slice::from_raw_parts(
-                    self.as_ptr() as *const u8,
-                    std::mem::size_of::<T>() * l,
-                ))
-            }
+        serializer.write_usize(self.len())?;
+        for item in self.iter() {
+            item.serialize(serializer)?;
         }
+        Ok(())
     }
 }
-
-fn regular_deserialize_vec<T: Deserialize>(
-    deserializer: &mut Deserializer<impl Read>,
-) -> Result<Vec<T>, SavefileError> {
-    let l = deserializer.read_usize()?;
-
-    #[cfg(feature = "size_sanity_checks")]
-    {
-        if l > 1_000_000 {
-            return Err(SavefileError::GeneralError {
-                msg: format!("Too many items in Vec: {}", l),
-            });
+#[cfg(feature = "im")]
+impl<T: Deserialize + Clone> Deserialize for im::Vector<T> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = im::Vector::new();
+        for _ in 0..l {
+            ret.push_back(T::deserialize(deserializer)?);
         }
+        Ok(ret)
     }
-    let mut ret = Vec::with_capacity(l);
-    for _ in 0..l {
-        ret.push(T::deserialize(deserializer)?);
-    }
-    Ok(ret)
 }
+#[cfg(feature = "im")]
+impl<T: Introspect + Clone> Introspect for im::Vector<T> {
+    fn introspect_value(&self) -> String {
+        format!("im::Vector<{}>", std::any::type_name::<T>())
+    }
 
-impl<T: Deserialize + Packed> Deserialize for Vec<T> {
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        if unsafe { T::repr_c_optimization_safe(deserializer.file_version) }.is_false() {
-            Ok(regular_deserialize_vec(deserializer)?)
-        } else {
-            use std::mem;
-
-            let align = mem::align_of::<T>();
-            let elem_size = mem::size_of::<T>();
-            let num_elems = deserializer.read_usize()?;
-
-            if num_elems == 0 {
-                return Ok(Vec::new());
-            }
-            let num_bytes = elem_size * num_elems;
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
 
-            let layout = if let Ok(layout) = std::alloc::Layout::from_size_align(num_bytes, align) {
-                Ok(layout)
-            } else {
-                Err(SavefileError::MemoryAllocationLayoutError)
-            }?;
-            let ptr = if elem_size == 0 {
-                NonNull::dangling().as_ptr()
-            } else {
-                let ptr = unsafe { std::alloc::alloc(layout) };
-                if ptr.is_null() {
-                    panic!("Failed to allocate {} bytes of memory", num_bytes);
-                }
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        let item = self.get(index)?;
+        Some(introspect_item(index.to_string(), item))
+    }
 
-                ptr
-            };
+    fn introspect_len(&self) -> usize {
+        self.len()
+    }
+}
 
-            {
-                let slice = unsafe { std::slice::from_raw_parts_mut(ptr, num_bytes) };
-                match deserializer.reader.read_exact(slice) {
-                    Ok(()) => Ok(()),
-                    Err(err) => {
-                        unsafe {
-                            std::alloc::dealloc(ptr, layout);
-                        }
-                        Err(err)
-                    }
-                }?;
-            }
-            let ret = unsafe { Vec::from_raw_parts(ptr as *mut T, num_elems, num_elems) };
-            Ok(ret)
+#[cfg(feature = "im")]
+impl<K, V, S> Packed for im::HashMap<K, V, S> {}
+#[cfg(feature = "im")]
+impl<K: WithSchema, V: WithSchema, S> WithSchema for im::HashMap<K, V, S> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        map_schema::<K, V>(version, context)
+    }
+}
+#[cfg(feature = "im")]
+impl<K: Serialize, V: Serialize, S> Serialize for im::HashMap<K, V, S> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.len())?;
+        for (k, v) in self.iter() {
+            k.serialize(serializer)?;
+            v.serialize(serializer)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(feature = "im")]
+impl<K: Deserialize + Eq + Hash + Clone, V: Deserialize + Clone, S: ::std::hash::BuildHasher + Default> Deserialize
+    for im::HashMap<K, V, S>
+{
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = im::HashMap::default();
+        for _ in 0..l {
+            ret.insert(K::deserialize(deserializer)?, V::deserialize(deserializer)?);
         }
+        Ok(ret)
     }
 }
-
-impl<T: Introspect> Introspect for VecDeque<T> {
+#[cfg(feature = "im")]
+impl<K: Introspect, V: Introspect, S> Introspect for im::HashMap<K, V, S> {
     fn introspect_value(&self) -> String {
-        format!("VecDeque<{}>", std::any::type_name::<T>())
+        format!(
+            "im::HashMap<{},{}>",
+            std::any::type_name::<K>(),
+            std::any::type_name::<V>()
+        )
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
     }
 
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if let Some(val) = self.get(index) {
-            Some(introspect_item(index.to_string(), val))
-        } else {
-            None
-        }
+        let (key, val) = self.iter().nth(index)?;
+        Some(Box::new(IntrospectItemSimple {
+            key: key.introspect_value(),
+            val,
+        }))
+    }
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .skip(start)
+            .take(count)
+            .map(|(key, val)| -> Box<dyn IntrospectItem<'_> + '_> {
+                Box::new(IntrospectItemSimple {
+                    key: key.introspect_value(),
+                    val,
+                })
+            })
+            .collect()
     }
 
     fn introspect_len(&self) -> usize {
@@ -7017,546 +11741,556 @@ impl<T: Introspect> Introspect for VecDeque<T> {
     }
 }
 
-impl<T: WithSchema> WithSchema for VecDeque<T> {
+// A slab is saved as its slots up to and including the last occupied one,
+// with vacant slots saved as None. This means all keys stay valid after loading.
+#[cfg(feature = "slab")]
+impl<T> Packed for slab::Slab<T> {}
+#[cfg(feature = "slab")]
+impl<T: WithSchema> WithSchema for slab::Slab<T> {
     fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
         Schema::Vector(
-            Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+            Box::new(Schema::SchemaOption(Box::new(
+                context.possible_recursion::<T>(|context| T::schema(version, context)),
+            ))),
             VecOrStringLayout::Unknown,
         )
     }
 }
-
-impl<T> Packed for VecDeque<T> {}
-impl<T: Serialize> Serialize for VecDeque<T> {
+#[cfg(feature = "slab")]
+impl<T: Serialize> Serialize for slab::Slab<T> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        regular_serialize_vecdeque(self, serializer)
+        let slots = self.iter().next_back().map(|(key, _)| key + 1).unwrap_or(0);
+        serializer.write_usize(slots)?;
+        let mut next_slot = 0;
+        for (key, item) in self.iter() {
+            for _ in next_slot..key {
+                serializer.write_bool(false)?;
+            }
+            serializer.write_bool(true)?;
+            item.serialize(serializer)?;
+            next_slot = key + 1;
+        }
+        Ok(())
     }
 }
-
-impl<T: Deserialize> Deserialize for VecDeque<T> {
+#[cfg(feature = "slab")]
+impl<T: Deserialize> Deserialize for slab::Slab<T> {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(regular_deserialize_vecdeque(deserializer)?)
+        let slots = deserializer.read_usize()?;
+        let mut items = Vec::new();
+        for key in 0..slots {
+            if let Some(item) = Option::<T>::deserialize(deserializer)? {
+                items.push((key, item));
+            }
+        }
+        Ok(items.into_iter().collect())
     }
 }
-
-fn regular_serialize_vecdeque<T: Serialize>(
-    item: &VecDeque<T>,
-    serializer: &mut Serializer<impl Write>,
-) -> Result<(), SavefileError> {
-    let l = item.len();
-    serializer.write_usize(l)?;
-    for item in item.iter() {
-        item.serialize(serializer)?
+#[cfg(feature = "slab")]
+impl<T: Introspect> Introspect for slab::Slab<T> {
+    fn introspect_value(&self) -> String {
+        format!("Slab<{}>", std::any::type_name::<T>())
     }
-    Ok(())
-}
 
-fn regular_deserialize_vecdeque<T: Deserialize>(
-    deserializer: &mut Deserializer<impl Read>,
-) -> Result<VecDeque<T>, SavefileError> {
-    let l = deserializer.read_usize()?;
-    let mut ret = VecDeque::with_capacity(l);
-    for _ in 0..l {
-        ret.push_back(T::deserialize(deserializer)?);
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
     }
-    Ok(ret)
-}
 
-impl Packed for bool {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
-    }
-} //It isn't really guaranteed that bool is an u8 or i8 where false = 0 and true = 1. But it's true in practice. And the breakage would be hard to measure if this were ever changed, so a change is unlikely.
-impl Packed for u8 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        let (key, item) = self.iter().nth(index)?;
+        Some(introspect_item(key.to_string(), item))
     }
-}
-impl Packed for i8 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+    fn introspect_children_range(&self, start: usize, count: usize) -> Vec<Box<dyn IntrospectItem<'_> + '_>> {
+        self.iter()
+            .skip(start)
+            .take(count)
+            .map(|(key, item)| introspect_item(key.to_string(), item))
+            .collect()
     }
-}
-impl Packed for u16 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+    fn introspect_len(&self) -> usize {
+        self.len()
     }
 }
-impl Packed for i16 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> Packed for tinyvec::TinyVec<A> {}
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array<Item: WithSchema>> WithSchema for tinyvec::TinyVec<A> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        sequence_schema::<A::Item>(version, context)
     }
 }
-impl Packed for u32 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array<Item: Serialize>> Serialize for tinyvec::TinyVec<A> {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_usize(self.len())?;
+        for item in self.iter() {
+            item.serialize(serializer)?;
+        }
+        Ok(())
     }
 }
-impl Packed for i32 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array<Item: Deserialize>> Deserialize for tinyvec::TinyVec<A> {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let mut ret = tinyvec::TinyVec::new();
+        for _ in 0..l {
+            ret.push(A::Item::deserialize(deserializer)?);
+        }
+        Ok(ret)
     }
 }
-impl Packed for u64 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array<Item: Introspect>> Introspect for tinyvec::TinyVec<A> {
+    fn introspect_value(&self) -> String {
+        format!("TinyVec<{}>", std::any::type_name::<A::Item>())
     }
-}
-impl Packed for u128 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
     }
-}
-impl Packed for i128 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        let item = self.get(index)?;
+        Some(introspect_item(index.to_string(), item))
     }
-}
-impl Packed for i64 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+    fn introspect_len(&self) -> usize {
+        self.len()
     }
 }
-impl Packed for char {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+
+// RoaringBitmap is saved using its own portable format, which is
+// compatible with other roaring implementations.
+#[cfg(feature = "roaring")]
+impl Packed for roaring::RoaringBitmap {}
+#[cfg(feature = "roaring")]
+impl WithSchema for roaring::RoaringBitmap {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "RoaringBitmap".to_string(),
+            vec![Field::new(
+                "portable".to_string(),
+                Box::new(Schema::Vector(
+                    Box::new(Schema::Primitive(SchemaPrimitive::schema_u8)),
+                    VecOrStringLayout::Unknown,
+                )),
+            )],
+        ))
     }
 }
-impl Packed for f32 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "roaring")]
+impl Serialize for roaring::RoaringBitmap {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        let mut bytes = Vec::with_capacity(self.serialized_size());
+        self.serialize_into(&mut bytes)?;
+        serializer.write_usize(bytes.len())?;
+        serializer.write_bytes(&bytes)
     }
 }
-impl Packed for f64 {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "roaring")]
+impl Deserialize for roaring::RoaringBitmap {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let l = deserializer.read_usize()?;
+        let bytes = deserializer.read_bytes(l)?;
+        roaring::RoaringBitmap::deserialize_from(&bytes[..]).map_err(|err| SavefileError::GeneralError {
+            msg: format!("Corrupt RoaringBitmap: {}", err),
+        })
     }
 }
-impl Packed for usize {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::no()
-    } // Doesn't have a fixed size
-}
-impl Packed for isize {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::no()
-    } // Doesn't have a fixed size
-}
-impl Packed for () {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        IsPacked::yes()
+#[cfg(feature = "roaring")]
+impl Introspect for roaring::RoaringBitmap {
+    fn introspect_value(&self) -> String {
+        format!("RoaringBitmap({} values)", self.len())
     }
-}
 
-impl<T: WithSchema, const N: usize> WithSchema for [T; N] {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Array(SchemaArray {
-            item_type: Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
-            count: N,
-        })
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
     }
-}
 
-impl<T: Introspect, const N: usize> Introspect for [T; N] {
-    fn introspect_value(&self) -> String {
-        format!("[{}; {}]", std::any::type_name::<T>(), N)
+    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
+        // The values are not stored anywhere, so can't be borrowed
+        let _ = index;
+        None
     }
+}
 
-    fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if index >= self.len() {
-            None
-        } else {
-            Some(introspect_item(index.to_string(), &self[index]))
-        }
+/// The schema of the shape or strides of an ndarray of dimension D. Arrays with a
+/// fixed number of dimensions have a fixed size array schema, so that for example
+/// an `Array2` can't be loaded as an `Array3`.
+#[cfg(feature = "ndarray")]
+fn ndarray_dim_schema<D: ndarray::Dimension>(item: SchemaPrimitive) -> Schema {
+    let item = Box::new(Schema::Primitive(item));
+    match D::NDIM {
+        Some(count) => Schema::Array(SchemaArray { item_type: item, count }),
+        None => Schema::Vector(item, VecOrStringLayout::Unknown),
     }
 }
 
-impl<T: Packed, const N: usize> Packed for [T; N] {
-    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
-        T::repr_c_optimization_safe(version)
+#[cfg(feature = "ndarray")]
+fn deserialize_ndarray_dim<D: ndarray::Dimension>(
+    deserializer: &mut Deserializer<impl Read>,
+    ndim: usize,
+) -> Result<D, SavefileError> {
+    let mut dim = D::zeros(ndim);
+    for axis in dim.slice_mut() {
+        *axis = deserializer.read_usize()?;
+    }
+    Ok(dim)
+}
+
+// An ndarray is saved as its shape, the strides of the saved elements, and then
+// the elements. Arrays in standard layout are saved using a single copy if T is Packed,
+// other arrays are saved in logical order.
+#[cfg(feature = "ndarray")]
+impl<T, D> Packed for ndarray::ArrayBase<ndarray::OwnedRepr<T>, D> {}
+#[cfg(feature = "ndarray")]
+impl<T: WithSchema, D: ndarray::Dimension> WithSchema for ndarray::ArrayBase<ndarray::OwnedRepr<T>, D> {
+    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
+        Schema::Struct(SchemaStruct::new(
+            "ndarray".to_string(),
+            vec![
+                Field::new(
+                    "shape".to_string(),
+                    Box::new(ndarray_dim_schema::<D>(SchemaPrimitive::schema_u64)),
+                ),
+                Field::new(
+                    "strides".to_string(),
+                    Box::new(ndarray_dim_schema::<D>(SchemaPrimitive::schema_i64)),
+                ),
+                Field::new(
+                    "elements".to_string(),
+                    Box::new(Schema::Vector(
+                        Box::new(context.possible_recursion::<T>(|context| T::schema(version, context))),
+                        VecOrStringLayout::Unknown,
+                    )),
+                ),
+            ],
+        ))
     }
 }
-impl<T: Serialize + Packed, const N: usize> Serialize for [T; N] {
+#[cfg(feature = "ndarray")]
+impl<T: Serialize + Packed, D: ndarray::Dimension> Serialize for ndarray::ArrayBase<ndarray::OwnedRepr<T>, D> {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        if N == 0 {
-            return Ok(());
+        if D::NDIM.is_none() {
+            serializer.write_usize(self.ndim())?;
         }
-        unsafe {
-            if T::repr_c_optimization_safe(serializer.file_version).is_false() {
-                for item in self.iter() {
-                    item.serialize(serializer)?
-                }
-                Ok(())
-            } else {
-                serializer.write_buf(std::slice::from_raw_parts(
-                    self.as_ptr() as *const u8,
-                    std::mem::size_of::<T>() * N,
-                ))
-            }
+        for axis in self.shape() {
+            serializer.write_usize(*axis)?;
         }
-    }
-}
-
-impl<T: Deserialize + Packed, const N: usize> Deserialize for [T; N] {
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        if N == 0 {
-            return Ok([(); N].map(|_| unreachable!()));
+        if D::NDIM.is_none() {
+            serializer.write_usize(self.ndim())?;
         }
-
-        if unsafe { T::repr_c_optimization_safe(deserializer.file_version) }.is_false() {
-            let mut data: [MaybeUninit<T>; N] = unsafe {
-                MaybeUninit::uninit().assume_init() //This seems strange, but is correct according to rust docs: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html, see chapter 'Initializing an array element-by-element'
-            };
-            for idx in 0..N {
-                data[idx] = MaybeUninit::new(T::deserialize(deserializer)?); //This leaks on panic, but we shouldn't panic and at least it isn't UB!
+        // The elements are always written in standard order
+        for stride in self.raw_dim().default_strides().slice() {
+            serializer.write_isize(*stride as isize)?;
+        }
+        match self.as_slice() {
+            Some(elements) if unsafe { T::repr_c_optimization_safe(serializer.file_version) }.is_yes() => {
+                serializer.write_usize(elements.len())?;
+                serializer.write_buf(unsafe {
+                    std::slice::from_raw_parts(elements.as_ptr() as *const u8, std::mem::size_of_val(elements))
+                })
             }
-            let ptr = &mut data as *mut _ as *mut [T; N];
-            let res = unsafe { ptr.read() };
-            Ok(res)
-        } else {
-            let mut data: [MaybeUninit<T>; N] = unsafe {
-                MaybeUninit::uninit().assume_init() //This seems strange, but is correct according to rust docs: https://doc.rust-lang.org/std/mem/union.MaybeUninit.html, see chapter 'Initializing an array element-by-element'
-            };
-
-            {
-                let ptr = data.as_mut_ptr();
-                let num_bytes: usize = std::mem::size_of::<T>() * N;
-                let slice: &mut [MaybeUninit<u8>] =
-                    unsafe { std::slice::from_raw_parts_mut(ptr as *mut MaybeUninit<u8>, num_bytes) };
-                deserializer
-                    .reader
-                    .read_exact(unsafe { std::mem::transmute::<&mut [MaybeUninit<u8>], &mut [u8]>(slice) })?;
+            _ => {
+                serializer.write_usize(self.len())?;
+                for item in self.iter() {
+                    item.serialize(serializer)?;
+                }
+                Ok(())
             }
-            let ptr = &mut data as *mut _ as *mut [T; N];
-            let res = unsafe { ptr.read() };
-            Ok(res)
         }
     }
 }
-
-impl<T1> Packed for Range<T1> {}
-impl<T1: WithSchema> WithSchema for Range<T1> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::new_tuple2::<T1, T1>(version, context)
-    }
-}
-impl<T1: Serialize> Serialize for Range<T1> {
-    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.start.serialize(serializer)?;
-        self.end.serialize(serializer)?;
-        Ok(())
-    }
-}
-impl<T1: Deserialize> Deserialize for Range<T1> {
+#[cfg(feature = "ndarray")]
+impl<T: Deserialize + Packed, D: ndarray::Dimension> Deserialize for ndarray::ArrayBase<ndarray::OwnedRepr<T>, D> {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(T1::deserialize(deserializer)?..T1::deserialize(deserializer)?)
+        use ndarray::ShapeBuilder;
+        let ndim = match D::NDIM {
+            Some(ndim) => ndim,
+            None => deserializer.read_usize()?,
+        };
+        let shape: D = deserialize_ndarray_dim(deserializer, ndim)?;
+        let stride_ndim = match D::NDIM {
+            Some(ndim) => ndim,
+            None => deserializer.read_usize()?,
+        };
+        if stride_ndim != ndim {
+            return Err(SavefileError::GeneralError {
+                msg: format!(
+                    "Corrupt ndarray: shape has {} dimensions, but strides have {}",
+                    ndim, stride_ndim
+                ),
+            });
+        }
+        let mut strides = D::zeros(ndim);
+        for axis in strides.slice_mut() {
+            let stride = deserializer.read_isize()?;
+            if stride < 0 {
+                return Err(SavefileError::GeneralError {
+                    msg: format!("Corrupt ndarray: negative stride {}", stride),
+                });
+            }
+            *axis = stride as usize;
+        }
+        let elements = Vec::<T>::deserialize(deserializer)?;
+        ndarray::ArrayBase::from_shape_vec(shape.strides(strides), elements).map_err(|err| {
+            SavefileError::GeneralError {
+                msg: format!("Corrupt ndarray: {}", err),
+            }
+        })
     }
 }
-impl<T1: Introspect> Introspect for Range<T1> {
+#[cfg(feature = "ndarray")]
+impl<T: Introspect, D: ndarray::Dimension> Introspect for ndarray::ArrayBase<ndarray::OwnedRepr<T>, D> {
     fn introspect_value(&self) -> String {
-        return "Range".to_string();
+        format!("ndarray{:?}", self.shape())
+    }
+
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Struct
     }
 
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
-        if index == 0 {
-            return Some(introspect_item("start".to_string(), &self.start));
+        if index >= self.len() {
+            return None;
         }
-        if index == 1 {
-            return Some(introspect_item("end".to_string(), &self.end));
+        let mut pos = D::zeros(self.ndim());
+        let mut rest = index;
+        for (axis, len) in pos.slice_mut().iter_mut().zip(self.shape()).rev() {
+            *axis = rest % len;
+            rest /= len;
         }
-        return None;
+        let item = self.get(pos.clone())?;
+        Some(introspect_item(format!("{:?}", pos.slice()), item))
     }
-}
 
-impl<T1: Packed> Packed for (T1,) {
-    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
-        if offset_of_tuple!((T1,), 0) == 0 && std::mem::size_of::<T1>() == std::mem::size_of::<(T1,)>() {
-            T1::repr_c_optimization_safe(version)
-        } else {
-            IsPacked::no()
-        }
+    fn introspect_len(&self) -> usize {
+        self.len()
     }
 }
-impl<T1: Packed, T2: Packed> Packed for (T1, T2) {
-    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
-        if offset_of_tuple!((T1, T2), 0) == 0
-            && std::mem::size_of::<T1>() + std::mem::size_of::<T2>() == std::mem::size_of::<(T1, T2)>()
-        {
-            T1::repr_c_optimization_safe(version) & T2::repr_c_optimization_safe(version)
-        } else {
-            IsPacked::no()
-        }
+
+#[cfg(feature = "uuid")]
+impl Packed for uuid::Uuid {
+    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
+        // Uuid is a repr(transparent) wrapper around [u8; 16]
+        IsPacked::yes()
     }
 }
-impl<T1: Packed, T2: Packed, T3: Packed> Packed for (T1, T2, T3) {
-    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
-        if offset_of_tuple!((T1, T2, T3), 0) == 0
-            && offset_of_tuple!((T1, T2, T3), 1) == std::mem::size_of::<T1>()
-            && std::mem::size_of::<T1>() + std::mem::size_of::<T2>() + std::mem::size_of::<T3>()
-                == std::mem::size_of::<(T1, T2, T3)>()
-        {
-            T1::repr_c_optimization_safe(version)
-                & T2::repr_c_optimization_safe(version)
-                & T3::repr_c_optimization_safe(version)
-        } else {
-            IsPacked::no()
-        }
+#[cfg(feature = "uuid")]
+impl WithSchema for uuid::Uuid {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_uuid)
     }
 }
-impl<T1: Packed, T2: Packed, T3: Packed, T4: Packed> Packed for (T1, T2, T3, T4) {
-    unsafe fn repr_c_optimization_safe(version: u32) -> IsPacked {
-        if offset_of_tuple!((T1, T2, T3, T4), 0) == 0
-            && offset_of_tuple!((T1, T2, T3, T4), 1) == std::mem::size_of::<T1>()
-            && offset_of_tuple!((T1, T2, T3, T4), 2) == std::mem::size_of::<T1>() + std::mem::size_of::<T2>()
-            && std::mem::size_of::<T1>()
-                + std::mem::size_of::<T2>()
-                + std::mem::size_of::<T3>()
-                + std::mem::size_of::<T4>()
-                == std::mem::size_of::<(T1, T2, T3, T4)>()
-        {
-            T1::repr_c_optimization_safe(version)
-                & T2::repr_c_optimization_safe(version)
-                & T3::repr_c_optimization_safe(version)
-                & T4::repr_c_optimization_safe(version)
-        } else {
-            IsPacked::no()
-        }
+#[cfg(feature = "uuid")]
+impl Serialize for uuid::Uuid {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_bytes(self.as_bytes())
     }
 }
-
-impl<T1: WithSchema, T2: WithSchema, T3: WithSchema> WithSchema for (T1, T2, T3) {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::new_tuple3::<T1, T2, T3>(version, context)
+#[cfg(feature = "uuid")]
+impl Deserialize for uuid::Uuid {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        let mut bytes = [0u8; 16];
+        deserializer.read_bytes_to_buf(&mut bytes)?;
+        Ok(uuid::Uuid::from_bytes(bytes))
     }
 }
-impl<T1: Serialize, T2: Serialize, T3: Serialize> Serialize for (T1, T2, T3) {
-    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.0.serialize(serializer)?;
-        self.1.serialize(serializer)?;
-        self.2.serialize(serializer)
+#[cfg(feature = "uuid")]
+impl Introspect for uuid::Uuid {
+    fn introspect_value(&self) -> String {
+        self.to_string()
     }
-}
-impl<T1: Deserialize, T2: Deserialize, T3: Deserialize> Deserialize for (T1, T2, T3) {
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok((
-            T1::deserialize(deserializer)?,
-            T2::deserialize(deserializer)?,
-            T3::deserialize(deserializer)?,
-        ))
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-impl<T1: WithSchema, T2: WithSchema> WithSchema for (T1, T2) {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::new_tuple2::<T1, T2>(version, context)
+/// Read a string, and parse it using `FromStr`. Used for types which are saved
+/// in their canonical string form.
+#[cfg(any(feature = "semver", feature = "url"))]
+fn deserialize_parsed_string<T: std::str::FromStr>(
+    deserializer: &mut Deserializer<impl Read>,
+    type_name: &str,
+) -> Result<T, SavefileError>
+where
+    T::Err: std::fmt::Display,
+{
+    let s = deserializer.read_string()?;
+    s.parse().map_err(|err| SavefileError::GeneralError {
+        msg: format!("Invalid {} '{}': {}", type_name, s, err),
+    })
+}
+
+#[cfg(feature = "semver")]
+impl Packed for semver::Version {}
+#[cfg(feature = "semver")]
+impl WithSchema for semver::Version {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
     }
 }
-impl<T1: Serialize, T2: Serialize> Serialize for (T1, T2) {
+#[cfg(feature = "semver")]
+impl Serialize for semver::Version {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.0.serialize(serializer)?;
-        self.1.serialize(serializer)
+        serializer.write_string(&self.to_string())
     }
 }
-impl<T1: Deserialize, T2: Deserialize> Deserialize for (T1, T2) {
+#[cfg(feature = "semver")]
+impl Deserialize for semver::Version {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok((T1::deserialize(deserializer)?, T2::deserialize(deserializer)?))
+        deserialize_parsed_string(deserializer, "semver version")
     }
 }
+#[cfg(feature = "semver")]
+impl Introspect for semver::Version {
+    fn introspect_value(&self) -> String {
+        self.to_string()
+    }
 
-impl<T1: WithSchema> WithSchema for (T1,) {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::new_tuple1::<T1>(version, context)
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
-impl<T1: Serialize> Serialize for (T1,) {
+
+#[cfg(feature = "semver")]
+impl Packed for semver::VersionReq {}
+#[cfg(feature = "semver")]
+impl WithSchema for semver::VersionReq {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
+    }
+}
+#[cfg(feature = "semver")]
+impl Serialize for semver::VersionReq {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.0.serialize(serializer)
+        serializer.write_string(&self.to_string())
     }
 }
-impl<T1: Deserialize> Deserialize for (T1,) {
+#[cfg(feature = "semver")]
+impl Deserialize for semver::VersionReq {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok((T1::deserialize(deserializer)?,))
+        deserialize_parsed_string(deserializer, "semver version requirement")
     }
 }
-
-#[cfg(feature = "nalgebra")]
-impl<T: nalgebra::Scalar> Introspect for nalgebra::Point3<T> {
+#[cfg(feature = "semver")]
+impl Introspect for semver::VersionReq {
     fn introspect_value(&self) -> String {
-        format!("{:?}", self)
+        self.to_string()
     }
 
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: nalgebra::Scalar> Introspect for nalgebra::Vector3<T> {
-    fn introspect_value(&self) -> String {
-        format!("{:?}", self)
-    }
 
-    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
-        None
+#[cfg(feature = "url")]
+impl Packed for url::Url {}
+#[cfg(feature = "url")]
+impl WithSchema for url::Url {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        Schema::Primitive(SchemaPrimitive::schema_string(VecOrStringLayout::Unknown))
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: nalgebra::Scalar> Introspect for nalgebra::Isometry3<T> {
+#[cfg(feature = "url")]
+impl Serialize for url::Url {
+    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
+        serializer.write_string(self.as_str())
+    }
+}
+#[cfg(feature = "url")]
+impl Deserialize for url::Url {
+    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
+        deserialize_parsed_string(deserializer, "url")
+    }
+}
+#[cfg(feature = "url")]
+impl Introspect for url::Url {
     fn introspect_value(&self) -> String {
-        format!("{:?}", self)
+        self.as_str().to_string()
     }
 
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: Packed + nalgebra::Scalar + Default> Packed for nalgebra::Point3<T> {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        let d = nalgebra::Point3::<T>::new(T::default(), T::default(), T::default());
-        let p1 = &d.x as *const T;
-        let p2 = &d.y as *const T;
-        let p3 = &d.z as *const T;
-
-        if std::mem::size_of::<nalgebra::Point3<T>>() == 3 * std::mem::size_of::<T>()
-            && p1.offset(1) == p2
-            && p1.offset(2) == p3
-        {
-            IsPacked::yes()
-        } else {
-            IsPacked::no()
-        }
-    }
-}
 
-#[cfg(feature = "nalgebra")]
-impl<T: WithSchema + nalgebra::Scalar> WithSchema for nalgebra::Point3<T> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Array(SchemaArray {
-            item_type: Box::new(T::schema(version, context)),
-            count: 3,
-        })
+#[cfg(feature = "bytes")]
+impl Packed for bytes::Bytes {}
+#[cfg(feature = "bytes")]
+impl WithSchema for bytes::Bytes {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        byte_string_schema()
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: Serialize + Packed + WithSchema + nalgebra::Scalar> Serialize for nalgebra::Point3<T> {
+#[cfg(feature = "bytes")]
+impl Serialize for bytes::Bytes {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.coords.x.serialize(serializer)?;
-        self.coords.y.serialize(serializer)?;
-        self.coords.z.serialize(serializer)?;
-
-        Ok(())
+        serializer.write_usize(self.len())?;
+        serializer.write_bytes(self)
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: Deserialize + Packed + WithSchema + nalgebra::Scalar + nalgebra::SimdValue + nalgebra::RealField> Deserialize
-    for nalgebra::Point3<T>
-{
+#[cfg(feature = "bytes")]
+impl Deserialize for bytes::Bytes {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(nalgebra::Point3::new(
-            <T as Deserialize>::deserialize(deserializer)?,
-            <T as Deserialize>::deserialize(deserializer)?,
-            <T as Deserialize>::deserialize(deserializer)?,
-        ))
+        let l = deserializer.read_usize()?;
+        Ok(deserializer.read_bytes(l)?.into())
     }
 }
+#[cfg(feature = "bytes")]
+impl Introspect for bytes::Bytes {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
+    }
 
-#[cfg(feature = "nalgebra")]
-impl<T: Packed + nalgebra::Scalar + Default> Packed for nalgebra::Vector3<T> {
-    unsafe fn repr_c_optimization_safe(_version: u32) -> IsPacked {
-        let d = nalgebra::Vector3::<T>::new(T::default(), T::default(), T::default());
-        let p1 = &d.x as *const T;
-        let p2 = &d.y as *const T;
-        let p3 = &d.z as *const T;
-
-        if std::mem::size_of::<nalgebra::Point3<T>>() == 3 * std::mem::size_of::<T>()
-            && p1.offset(1) == p2
-            && p1.offset(2) == p3
-        {
-            IsPacked::yes()
-        } else {
-            IsPacked::no()
-        }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Bytes(self.to_vec())
+    }
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
-#[cfg(feature = "nalgebra")]
-impl<T: WithSchema + nalgebra::Scalar> WithSchema for nalgebra::Vector3<T> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Array(SchemaArray {
-            item_type: Box::new(T::schema(version, context)),
-            count: 3,
-        })
+#[cfg(feature = "bytes")]
+impl Packed for bytes::BytesMut {}
+#[cfg(feature = "bytes")]
+impl WithSchema for bytes::BytesMut {
+    fn schema(_version: u32, _context: &mut WithSchemaContext) -> Schema {
+        byte_string_schema()
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: Serialize + Packed + WithSchema + nalgebra::Scalar> Serialize for nalgebra::Vector3<T> {
+#[cfg(feature = "bytes")]
+impl Serialize for bytes::BytesMut {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.x.serialize(serializer)?;
-        self.y.serialize(serializer)?;
-        self.z.serialize(serializer)?;
-
-        Ok(())
+        serializer.write_usize(self.len())?;
+        serializer.write_bytes(self)
     }
 }
-#[cfg(feature = "nalgebra")]
-impl<T: Deserialize + Packed + WithSchema + nalgebra::Scalar + nalgebra::SimdValue + nalgebra::RealField> Deserialize
-    for nalgebra::Vector3<T>
-{
+#[cfg(feature = "bytes")]
+impl Deserialize for bytes::BytesMut {
     fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(nalgebra::Vector3::new(
-            <T as Deserialize>::deserialize(deserializer)?,
-            <T as Deserialize>::deserialize(deserializer)?,
-            <T as Deserialize>::deserialize(deserializer)?,
-        ))
+        let l = deserializer.read_usize()?;
+        Ok(deserializer.read_bytes(l)?[..].into())
     }
 }
-
-#[cfg(feature = "nalgebra")]
-impl<T: Packed> Packed for nalgebra::Isometry3<T> {}
-
-#[cfg(feature = "nalgebra")]
-impl<T: WithSchema> WithSchema for nalgebra::Isometry3<T> {
-    fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
-        Schema::Array(SchemaArray {
-            item_type: Box::new(T::schema(version, context)),
-            count: 7,
-        })
+#[cfg(feature = "bytes")]
+impl Introspect for bytes::BytesMut {
+    fn introspect_value(&self) -> String {
+        format!("{:?}", self)
     }
-}
-#[cfg(feature = "nalgebra")]
-impl<T: Serialize + Packed + WithSchema + nalgebra::Scalar> Serialize for nalgebra::Isometry3<T> {
-    fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
-        self.translation.vector.x.serialize(serializer)?;
-        self.translation.vector.y.serialize(serializer)?;
-        self.translation.vector.z.serialize(serializer)?;
-
-        self.rotation.coords.w.serialize(serializer)?;
-        self.rotation.coords.x.serialize(serializer)?;
-        self.rotation.coords.y.serialize(serializer)?;
-        self.rotation.coords.z.serialize(serializer)?;
 
-        Ok(())
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Bytes(self.to_vec())
     }
-}
-#[cfg(feature = "nalgebra")]
-impl<T: Deserialize + Packed + WithSchema + nalgebra::Scalar + nalgebra::SimdValue + nalgebra::RealField> Deserialize
-    for nalgebra::Isometry3<T>
-{
-    fn deserialize(deserializer: &mut Deserializer<impl Read>) -> Result<Self, SavefileError> {
-        Ok(nalgebra::Isometry3::from_parts(
-            nalgebra::Point3::new(
-                <T as Deserialize>::deserialize(deserializer)?,
-                <T as Deserialize>::deserialize(deserializer)?,
-                <T as Deserialize>::deserialize(deserializer)?,
-            )
-            .into(),
-            nalgebra::UnitQuaternion::new_unchecked(nalgebra::Quaternion::new(
-                <T as Deserialize>::deserialize(deserializer)?,
-                <T as Deserialize>::deserialize(deserializer)?,
-                <T as Deserialize>::deserialize(deserializer)?,
-                <T as Deserialize>::deserialize(deserializer)?,
-            )),
-        ))
+
+    fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
+        None
     }
 }
 
@@ -7619,6 +12353,10 @@ impl<V: Introspect, const C: usize> Introspect for arrayvec::ArrayVec<V, C> {
         return "arrayvec[]".to_string();
     }
 
+    fn introspect_kind(&self) -> IntrospectKind {
+        IntrospectKind::Sequence
+    }
+
     fn introspect_child<'s>(&'s self, index: usize) -> Option<Box<dyn IntrospectItem<'s> + 's>> {
         if index >= self.len() {
             return None;
@@ -7683,7 +12421,7 @@ impl<V: Deserialize + Packed, const C: usize> Deserialize for arrayvec::ArrayVec
     }
 }
 
-use std::ops::{Deref, Range};
+use std::ops::{Bound, Deref, DerefMut, Range, RangeInclusive};
 impl<T: WithSchema> WithSchema for Box<T> {
     fn schema(version: u32, context: &mut WithSchemaContext) -> Schema {
         context.possible_recursion::<T>(|context| T::schema(version, context))
@@ -7743,12 +12481,15 @@ use memoffset::offset_of_tuple;
 use std::any::Any;
 use std::cell::Cell;
 use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry;
 #[allow(unused_imports)]
 use std::convert::{TryFrom, TryInto};
+use std::ffi::{CString, OsString};
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{Saturating, Wrapping};
 use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
 use std::slice;
@@ -7809,6 +12550,9 @@ impl Introspect for () {
     fn introspect_value(&self) -> String {
         "()".to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7824,6 +12568,10 @@ impl<T: Introspect> Introspect for (T,) {
         return "1-tuple".to_string();
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             return Some(introspect_item("0".to_string(), &self.0));
@@ -7837,6 +12585,10 @@ impl<T1: Introspect, T2: Introspect> Introspect for (T1, T2) {
         return "2-tuple".to_string();
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             return Some(introspect_item("0".to_string(), &self.0));
@@ -7852,6 +12604,10 @@ impl<T1: Introspect, T2: Introspect, T3: Introspect> Introspect for (T1, T2, T3)
         return "3-tuple".to_string();
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             return Some(introspect_item("0".to_string(), &self.0));
@@ -7870,6 +12626,10 @@ impl<T1: Introspect, T2: Introspect, T3: Introspect, T4: Introspect> Introspect
         return "4-tuple".to_string();
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         if index == 0 {
             return Some(introspect_item("0".to_string(), &self.0));
@@ -7891,6 +12651,9 @@ impl Introspect for AtomicBool {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Bool(self.load(Ordering::SeqCst))
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7899,6 +12662,9 @@ impl Introspect for AtomicU8 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(self.load(Ordering::SeqCst) as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7907,6 +12673,9 @@ impl Introspect for AtomicI8 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(self.load(Ordering::SeqCst) as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7915,6 +12684,9 @@ impl Introspect for AtomicU16 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(self.load(Ordering::SeqCst) as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7923,6 +12695,9 @@ impl Introspect for AtomicI16 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(self.load(Ordering::SeqCst) as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7931,6 +12706,9 @@ impl Introspect for AtomicU32 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(self.load(Ordering::SeqCst) as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7939,6 +12717,9 @@ impl Introspect for AtomicI32 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(self.load(Ordering::SeqCst) as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7947,6 +12728,9 @@ impl Introspect for AtomicU64 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(self.load(Ordering::SeqCst) as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7955,6 +12739,9 @@ impl Introspect for AtomicI64 {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(self.load(Ordering::SeqCst) as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7963,6 +12750,9 @@ impl Introspect for AtomicUsize {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(self.load(Ordering::SeqCst) as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -7971,6 +12761,9 @@ impl Introspect for AtomicIsize {
     fn introspect_value(&self) -> String {
         self.load(Ordering::SeqCst).to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(self.load(Ordering::SeqCst) as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8133,6 +12926,9 @@ impl Introspect for bool {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Bool(*self)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8141,6 +12937,9 @@ impl Introspect for u8 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8149,6 +12948,9 @@ impl Introspect for u16 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8157,6 +12959,9 @@ impl Introspect for u32 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8165,6 +12970,9 @@ impl Introspect for u64 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8173,6 +12981,9 @@ impl Introspect for u128 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8181,6 +12992,9 @@ impl Introspect for i8 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8189,6 +13003,9 @@ impl Introspect for i16 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8197,6 +13014,9 @@ impl Introspect for i32 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8205,6 +13025,9 @@ impl Introspect for i64 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8213,6 +13036,9 @@ impl Introspect for char {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Char(*self)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8221,6 +13047,9 @@ impl Introspect for i128 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8229,6 +13058,9 @@ impl Introspect for f32 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Float(*self as f64)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8237,6 +13069,9 @@ impl Introspect for f64 {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Float(*self)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8245,6 +13080,9 @@ impl Introspect for usize {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::UInt(*self as u128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8253,11 +13091,36 @@ impl Introspect for isize {
     fn introspect_value(&self) -> String {
         self.to_string()
     }
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::Int(*self as i128)
+    }
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
 }
 
+macro_rules! impl_introspect_mut_parse {
+    ($($t:ty),*) => {
+        $(
+            impl IntrospectMut for $t {
+                fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+                    *self = value.trim().parse().map_err(|_| IntrospectionError::InvalidValue)?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+impl_introspect_mut_parse!(bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl IntrospectMut for char {
+    fn set_from_str(&mut self, value: &str) -> Result<(), IntrospectionError> {
+        // Don't trim, since the char may be a space
+        *self = value.parse().map_err(|_| IntrospectionError::InvalidValue)?;
+        Ok(())
+    }
+}
+
 impl Serialize for u8 {
     fn serialize(&self, serializer: &mut Serializer<impl Write>) -> Result<(), SavefileError> {
         serializer.write_u8(*self)
@@ -8574,6 +13437,10 @@ impl Introspect for Canary1 {
         "Canary1".to_string()
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        IntrospectValue::None
+    }
+
     fn introspect_child(&self, _index: usize) -> Option<Box<dyn IntrospectItem<'_> + '_>> {
         None
     }
@@ -8646,11 +13513,33 @@ impl Introspect for Duration {
         0
     }
 }
+fn system_time_introspect_value(time: &SystemTime) -> IntrospectValue {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(after) => IntrospectValue::Timestamp {
+            secs: after.as_secs() as i64,
+            nanos: after.subsec_nanos(),
+        },
+        Err(err) => {
+            let before = err.duration();
+            let mut secs = -(before.as_secs() as i64);
+            let mut nanos = before.subsec_nanos();
+            if nanos > 0 {
+                secs -= 1;
+                nanos = 1_000_000_000 - nanos;
+            }
+            IntrospectValue::Timestamp { secs, nanos }
+        }
+    }
+}
 impl Introspect for SystemTime {
     fn introspect_value(&self) -> String {
         format!("{:?}", self)
     }
 
+    fn introspect_typed_value(&self) -> IntrospectValue {
+        system_time_introspect_value(self)
+    }
+
     fn introspect_child<'a>(&'a self, _index: usize) -> Option<Box<dyn IntrospectItem<'a> + 'a>> {
         None
     }
@@ -8735,6 +13624,18 @@ struct PathElement {
     key: String,
     key_disambiguator: usize,
     max_children: usize,
+    // Index of the child, if it was found by the latest call to 'dive'
+    index: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct IntrospectorPage {
+    // Index of the first loaded child
+    offset: usize,
+    // Number of children before 'offset' having each of the keys on the page, and the
+    // number of children of the object when they were counted. Counting these requires
+    // visiting every child before the page, so it is only redone if the object changes size.
+    key_counts: Option<(HashMap<String, usize>, usize)>,
 }
 
 /// A helper which allows navigating an introspected object.
@@ -8743,6 +13644,8 @@ struct PathElement {
 pub struct Introspector {
     path: Vec<PathElement>,
     child_load_count: usize,
+    // The loaded page, for each depth
+    pages: Vec<IntrospectorPage>,
 }
 
 /// A command to navigate within an introspected object
@@ -8763,6 +13666,15 @@ pub enum IntrospectorNavCommand {
     Nothing,
     /// Navigate one level up
     Up,
+    /// Load children starting at the given index, at the given depth in the tree.
+    /// Use this together with [Introspector::new_with] to page through large collections.
+    /// Any element selected at this depth is deselected.
+    Page {
+        /// Depth of the level to page
+        depth: usize,
+        /// Index of the first child to load
+        offset: usize,
+    },
 }
 
 /// Identifies an introspected element somewhere in the introspection tree
@@ -8841,15 +13753,26 @@ pub enum IntrospectionError {
     IndexOutOfRange,
     /// An attempt was made to back up when already at the top.
     AlreadyAtTop,
+    /// An attempt was made to modify an element which can't be modified.
+    ReadOnly,
+    /// The given string could not be parsed as a value of the type of the element.
+    InvalidValue,
+    /// An attempt was made to modify the selected element, but no element is selected.
+    NothingSelected,
+    /// The query given to [Introspector::query] could not be parsed.
+    InvalidQuery,
 }
 
 /// All fields at a specific depth in the introspection tree
 #[derive(Debug, Clone)]
 pub struct IntrospectionFrame {
-    /// The index of the expanded child, if any
+    /// The index of the expanded child in keyvals, if any
     pub selected: Option<usize>,
     /// All fields at this level
     pub keyvals: Vec<IntrospectedElement>,
+    /// The index of the child in `keyvals[0]`. This is nonzero if
+    /// [IntrospectorNavCommand::Page] has been used.
+    pub offset: usize,
     /// True if there may have been more children, but expansion was stopped
     /// because the limit given to the Introspector was reached.
     pub limit_reached: bool,
@@ -8962,6 +13885,7 @@ impl Introspector {
         Introspector {
             path: vec![],
             child_load_count: usize::MAX,
+            pages: vec![],
         }
     }
     /// Returns a new Introspector which will not enumerate more than 'child_load_count'
@@ -8971,6 +13895,7 @@ impl Introspector {
         Introspector {
             path: vec![],
             child_load_count,
+            pages: vec![],
         }
     }
 
@@ -8987,15 +13912,18 @@ impl Introspector {
     ) -> Result<Vec<IntrospectionFrame>, IntrospectionError> {
         let mut result_vec = Vec::new();
         let mut navigation_command = Some(navigation_command);
+        if let Some(path_element) = self.path.get_mut(depth) {
+            path_element.index = None;
+        }
         let mut cur_path = self.path.get(depth).cloned();
-        let mut index = 0;
+        let offset = self.pages.get(depth).map(|page| page.offset).unwrap_or(0);
+        let mut index = offset;
         let mut row = IntrospectionFrame {
             selected: None,
             keyvals: vec![],
+            offset,
             limit_reached: false,
         };
-        let mut key_disambig_map = HashMap::new();
-
         let mut do_select_nth = None;
 
         let mut err_if_key_not_found = false;
@@ -9007,10 +13935,12 @@ impl Introspector {
                     }
                     if depth == elem.depth {
                         self.path.drain(depth..);
+                        self.pages.truncate(depth + 1);
                         self.path.push(PathElement {
                             key: elem.key.clone(),
                             key_disambiguator: elem.key_disambiguator,
                             max_children: self.child_load_count,
+                            index: None,
                         });
                         cur_path = self.path.get(depth).cloned();
                         err_if_key_not_found = true;
@@ -9026,67 +13956,69 @@ impl Introspector {
                 }
                 IntrospectorNavCommand::Nothing => {}
                 IntrospectorNavCommand::Up => {}
+                IntrospectorNavCommand::Page { .. } => {}
             }
         }
 
-        loop {
-            if let Some(child_item) = object.introspect_child(index) {
-                let key: String = child_item.key().into();
+        let max_children = cur_path
+            .as_ref()
+            .map(|x| x.max_children)
+            .unwrap_or(self.child_load_count);
+        let children = object.introspect_children_range(offset, max_children);
+        row.limit_reached = children.len() >= max_children;
+        let mut key_disambig_map = if offset > 0 {
+            self.page_key_counts(depth, object, &children)
+        } else {
+            HashMap::new()
+        };
 
-                let disambig_counter: &mut usize = key_disambig_map.entry(key.clone()).or_insert(0usize);
-                let has_children = child_item.val().introspect_child(0).is_some();
-                row.keyvals.push(IntrospectedElement {
-                    key: IntrospectedElementKey {
-                        depth,
-                        key: key.clone(),
-                        key_disambiguator: *disambig_counter,
-                    },
-                    value: child_item.val().introspect_value(),
-                    has_children,
-                    selected: false,
-                });
+        for child_item in children {
+            let key: String = child_item.key().into();
 
-                if Some(index) == do_select_nth {
-                    self.path.push(PathElement {
-                        key: key.clone(),
-                        key_disambiguator: *disambig_counter,
-                        max_children: self.child_load_count,
-                    });
-                    do_select_nth = None;
-                    cur_path = self.path.last().cloned();
-                }
+            let disambig_counter: &mut usize = key_disambig_map.entry(key.clone()).or_insert(0usize);
+            let has_children = child_item.val().introspect_child(0).is_some();
+            row.keyvals.push(IntrospectedElement {
+                key: IntrospectedElementKey {
+                    depth,
+                    key: key.clone(),
+                    key_disambiguator: *disambig_counter,
+                },
+                value: child_item.val().introspect_value(),
+                has_children,
+                selected: false,
+            });
 
-                if let Some(cur_path_obj) = &cur_path {
-                    if row.selected.is_none()
-                        && cur_path_obj.key == key
-                        && cur_path_obj.key_disambiguator == *disambig_counter
-                    {
-                        row.selected = Some(index);
-                        row.keyvals.last_mut().unwrap().selected = true;
-                        if has_children {
-                            let mut subresult =
-                                self.dive(depth + 1, child_item.val(), navigation_command.take().unwrap())?;
-                            debug_assert_eq!(result_vec.len(), 0);
-                            std::mem::swap(&mut result_vec, &mut subresult);
-                        }
+            if Some(index) == do_select_nth {
+                self.path.push(PathElement {
+                    key: key.clone(),
+                    key_disambiguator: *disambig_counter,
+                    max_children: self.child_load_count,
+                    index: None,
+                });
+                self.pages.truncate(depth + 1);
+                do_select_nth = None;
+                cur_path = self.path.last().cloned();
+            }
+
+            if let Some(cur_path_obj) = &cur_path {
+                if row.selected.is_none()
+                    && cur_path_obj.key == key
+                    && cur_path_obj.key_disambiguator == *disambig_counter
+                {
+                    row.selected = Some(index - offset);
+                    row.keyvals.last_mut().unwrap().selected = true;
+                    self.path[depth].index = Some(index);
+                    if has_children {
+                        let mut subresult =
+                            self.dive(depth + 1, child_item.val(), navigation_command.take().unwrap())?;
+                        debug_assert_eq!(result_vec.len(), 0);
+                        std::mem::swap(&mut result_vec, &mut subresult);
                     }
                 }
-
-                *disambig_counter += 1;
-            } else {
-                break;
             }
 
+            *disambig_counter += 1;
             index += 1;
-            if index
-                >= cur_path
-                    .as_ref()
-                    .map(|x| x.max_children)
-                    .unwrap_or(self.child_load_count)
-            {
-                row.limit_reached = true;
-                break;
-            }
         }
         if do_select_nth.is_some() {
             if index == 0 {
@@ -9102,6 +14034,43 @@ impl Introspector {
         Ok(result_vec)
     }
 
+    /// The number of children before the page at 'depth' with each of the keys on the page,
+    /// i.e, the key disambiguators of the first page child with each key.
+    /// Cached in the page, and only recounted if the page has a key not counted before, or
+    /// if the number of children of the object has changed since the last count.
+    fn page_key_counts(
+        &mut self,
+        depth: usize,
+        object: &dyn Introspect,
+        children: &[Box<dyn IntrospectItem<'_> + '_>],
+    ) -> HashMap<String, usize> {
+        let page = &mut self.pages[depth];
+        let len = object.introspect_len();
+        if let Some((key_counts, counted_len)) = &page.key_counts {
+            if *counted_len == len && children.iter().all(|child| key_counts.contains_key(child.key())) {
+                return key_counts.clone();
+            }
+        }
+        let mut key_counts: HashMap<String, usize> =
+            children.iter().map(|child| (child.key().to_string(), 0)).collect();
+        let mut index = 0;
+        while index < page.offset {
+            // Visit in chunks, so that all children before the page are not loaded at once
+            let chunk = object.introspect_children_range(index, (page.offset - index).min(1024));
+            if chunk.is_empty() {
+                break;
+            }
+            for child in &chunk {
+                if let Some(count) = key_counts.get_mut(child.key()) {
+                    *count += 1;
+                }
+            }
+            index += chunk.len();
+        }
+        page.key_counts = Some((key_counts.clone(), len));
+        key_counts
+    }
+
     /// Navigate the introspection tree using the given navigation_command, and also
     /// return the tree as an IntrospectionResult.
     pub fn do_introspect(
@@ -9118,6 +14087,18 @@ impl Introspector {
                     return Err(IntrospectionError::AlreadyAtTop);
                 }
                 self.path.pop();
+                self.pages.truncate(self.path.len() + 1);
+            }
+            IntrospectorNavCommand::Page { depth, offset } => {
+                if *depth > self.path.len() {
+                    return Err(IntrospectionError::BadDepth);
+                }
+                self.path.truncate(*depth);
+                self.pages.resize(*depth + 1, IntrospectorPage::default());
+                self.pages[*depth] = IntrospectorPage {
+                    offset: *offset,
+                    key_counts: None,
+                };
             }
         }
         let frames = self.dive(0, object, navigation_command)?;
@@ -9132,4 +14113,682 @@ impl Introspector {
         };
         Ok(accum)
     }
+
+    /// Set the value of the currently selected element (the most recently expanded or
+    /// selected element), by parsing the given string.
+    ///
+    /// The object must be the same object which was given to [Introspector::do_introspect].
+    pub fn set_selected_value(&self, object: &mut dyn IntrospectMut, value: &str) -> Result<(), IntrospectionError> {
+        if self.path.is_empty() {
+            return Err(IntrospectionError::NothingSelected);
+        }
+        Self::set_value_at(object, &self.path, value)
+    }
+
+    fn set_value_at(
+        object: &mut dyn IntrospectMut,
+        path: &[PathElement],
+        value: &str,
+    ) -> Result<(), IntrospectionError> {
+        let Some((first, rest)) = path.split_first() else {
+            return object.set_from_str(value);
+        };
+        // Use the index found while navigating, unless the object has changed since
+        if let Some(index) = first.index {
+            if object
+                .introspect_child(index)
+                .is_some_and(|child| child.key() == first.key)
+            {
+                let mut child = object.introspect_child_mut(index).ok_or(IntrospectionError::ReadOnly)?;
+                return Self::set_value_at(child.val_mut(), rest, value);
+            }
+        }
+        let mut disambig_counter = 0;
+        let mut found_index = None;
+        for index in 0..first.max_children {
+            let Some(child) = object.introspect_child(index) else {
+                break;
+            };
+            if child.key() == first.key {
+                if disambig_counter == first.key_disambiguator {
+                    found_index = Some(index);
+                    break;
+                }
+                disambig_counter += 1;
+            }
+        }
+        let index = found_index.ok_or(IntrospectionError::UnknownKey)?;
+        let mut child = object.introspect_child_mut(index).ok_or(IntrospectionError::ReadOnly)?;
+        Self::set_value_at(child.val_mut(), rest, value)
+    }
+
+    /// Find all elements matching the given path query.
+    ///
+    /// The query is a list of segments separated by '.', like `players[3].inventory.items[0].name`.
+    /// Each segment selects children of the elements matched so far:
+    ///
+    ///  * `name` selects children with the key `name`. If several children have the same key, all of them match.
+    ///  * `*` selects all children.
+    ///  * `[3]` selects the child with index 3 (the 4th child).
+    ///  * `[*]` selects all children.
+    ///  * `[name=Bob]` selects all children which have a child with the key `name` and the value `Bob`.
+    ///
+    /// Bracketed segments can follow a key directly, or each other, without a '.'.
+    /// The characters `.[]=*\` can be escaped using a backslash.
+    ///
+    /// Only the first [MAX_CHILDREN] children of each element are searched.
+    ///
+    /// The matches are returned in the order they appear in the object. Returns
+    /// [IntrospectionError::InvalidQuery] if the query can't be parsed.
+    pub fn query(object: &dyn Introspect, query: &str) -> Result<Vec<IntrospectQueryMatch>, IntrospectionError> {
+        let steps = parse_introspect_query(query)?;
+        let mut result = Vec::new();
+        query_impl(object, &steps, &mut Vec::new(), &mut result);
+        Ok(result)
+    }
+}
+
+/// An element found by [Introspector::query]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntrospectQueryMatch {
+    /// The keys of all elements from the top level down to and including the found element.
+    /// These can be used with [IntrospectorNavCommand::ExpandElement] to navigate to the element.
+    pub path: Vec<IntrospectedElementKey>,
+    /// The value of the found element
+    pub value: String,
+    /// True if the found element has children
+    pub has_children: bool,
+}
+
+impl IntrospectQueryMatch {
+    /// The path of the element, as a dot-separated list of keys.
+    pub fn path_string(&self) -> String {
+        let keys: Vec<&str> = self.path.iter().map(|x| x.key.as_str()).collect();
+        keys.join(".")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum IntrospectQueryStep {
+    Key(String),
+    Wildcard,
+    Index(usize),
+    Predicate { key: String, value: String },
+}
+
+fn parse_introspect_query(query: &str) -> Result<Vec<IntrospectQueryStep>, IntrospectionError> {
+    let mut steps = Vec::new();
+    let mut chars = query.chars().peekable();
+    loop {
+        // Parse a key, unless the segment starts with a bracket
+        if chars.peek() != Some(&'[') {
+            let mut key = String::new();
+            let mut escaped = false;
+            while let Some(&c) = chars.peek() {
+                match c {
+                    '.' | '[' => break,
+                    ']' | '=' => return Err(IntrospectionError::InvalidQuery),
+                    '\\' => {
+                        chars.next();
+                        key.push(chars.next().ok_or(IntrospectionError::InvalidQuery)?);
+                        escaped = true;
+                        continue;
+                    }
+                    c => key.push(c),
+                }
+                chars.next();
+            }
+            if key.is_empty() && !escaped {
+                return Err(IntrospectionError::InvalidQuery);
+            }
+            if key == "*" && !escaped {
+                steps.push(IntrospectQueryStep::Wildcard);
+            } else {
+                steps.push(IntrospectQueryStep::Key(key));
+            }
+        }
+        while chars.peek() == Some(&'[') {
+            chars.next();
+            let mut left = String::new();
+            let mut right: Option<String> = None;
+            let mut escaped = false;
+            loop {
+                let c = match chars.next().ok_or(IntrospectionError::InvalidQuery)? {
+                    ']' => break,
+                    '[' => return Err(IntrospectionError::InvalidQuery),
+                    '=' if right.is_none() => {
+                        right = Some(String::new());
+                        continue;
+                    }
+                    '\\' => {
+                        escaped = true;
+                        chars.next().ok_or(IntrospectionError::InvalidQuery)?
+                    }
+                    c => c,
+                };
+                right.as_mut().unwrap_or(&mut left).push(c);
+            }
+            steps.push(match right {
+                Some(value) => IntrospectQueryStep::Predicate { key: left, value },
+                None if left == "*" && !escaped => IntrospectQueryStep::Wildcard,
+                None => IntrospectQueryStep::Index(left.parse().map_err(|_| IntrospectionError::InvalidQuery)?),
+            });
+        }
+        match chars.next() {
+            None => return Ok(steps),
+            Some('.') => {}
+            Some(_) => return Err(IntrospectionError::InvalidQuery),
+        }
+    }
+}
+
+/// Call 'visit' with the index and item of each child of 'object', in order, until it returns
+/// false. At most 'limit' children are visited. The children are fetched in chunks using
+/// [Introspect::introspect_children_range], so that collections without efficient indexing
+/// are not iterated once per child.
+fn visit_children<'a>(
+    object: &'a dyn Introspect,
+    limit: usize,
+    mut visit: impl FnMut(usize, Box<dyn IntrospectItem<'a> + 'a>) -> bool,
+) {
+    let mut index = 0;
+    while index < limit {
+        let chunk = object.introspect_children_range(index, (limit - index).min(1024));
+        if chunk.is_empty() {
+            return;
+        }
+        for child in chunk {
+            if !visit(index, child) {
+                return;
+            }
+            index += 1;
+        }
+    }
+}
+
+fn query_impl(
+    object: &dyn Introspect,
+    steps: &[IntrospectQueryStep],
+    path: &mut Vec<IntrospectedElementKey>,
+    result: &mut Vec<IntrospectQueryMatch>,
+) {
+    let Some((step, rest)) = steps.split_first() else {
+        return;
+    };
+    let limit = match step {
+        IntrospectQueryStep::Index(wanted) => wanted.saturating_add(1).min(MAX_CHILDREN),
+        _ => MAX_CHILDREN,
+    };
+    let mut key_disambig_map: HashMap<String, usize> = HashMap::new();
+    visit_children(object, limit, |index, child| {
+        let key = child.key();
+        let disambig_counter = key_disambig_map.entry(key.to_string()).or_insert(0);
+        let is_match = match step {
+            IntrospectQueryStep::Key(wanted) => key == wanted,
+            IntrospectQueryStep::Wildcard => true,
+            IntrospectQueryStep::Index(wanted) => index == *wanted,
+            IntrospectQueryStep::Predicate {
+                key: wanted_key,
+                value: wanted_value,
+            } => {
+                let mut found = false;
+                visit_children(child.val(), MAX_CHILDREN, |_, x| {
+                    found = x.key() == wanted_key && x.val().introspect_value() == *wanted_value;
+                    !found
+                });
+                found
+            }
+        };
+        if is_match {
+            path.push(IntrospectedElementKey {
+                depth: path.len(),
+                key: key.to_string(),
+                key_disambiguator: *disambig_counter,
+            });
+            if rest.is_empty() {
+                result.push(IntrospectQueryMatch {
+                    path: path.clone(),
+                    value: child.val().introspect_value(),
+                    has_children: child.val().introspect_child(0).is_some(),
+                });
+            } else {
+                query_impl(child.val(), rest, path, result);
+            }
+            path.pop();
+        }
+        *disambig_counter += 1;
+        true
+    });
+}
+
+/// The kind of difference reported in an [IntrospectDiffEntry]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntrospectDiffKind {
+    /// The element exists in both objects, but with different values
+    Changed,
+    /// The element only exists in the new object
+    Added,
+    /// The element only exists in the old object
+    Removed,
+}
+
+/// A single difference found by [introspect_diff]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectDiffEntry {
+    /// The keys of all elements from the top level down to and including the differing element.
+    /// For map entries, the key is the introspected value of the map key.
+    pub path: Vec<String>,
+    /// The kind of difference
+    pub kind: IntrospectDiffKind,
+    /// The value in the old object, or None if the element was added
+    pub old_value: Option<String>,
+    /// The value in the new object, or None if the element was removed
+    pub new_value: Option<String>,
+}
+
+impl IntrospectDiffEntry {
+    /// The path of the element, as a dot-separated list of keys.
+    pub fn path_string(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// Limits for [introspect_diff_with_limits]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntrospectDiffLimits {
+    /// Elements deeper than this are not compared. Top level children have depth 1.
+    pub max_depth: usize,
+    /// The diff stops after this many differences have been found.
+    pub max_entries: usize,
+}
+
+impl Default for IntrospectDiffLimits {
+    fn default() -> Self {
+        IntrospectDiffLimits {
+            max_depth: 32,
+            max_entries: 1000,
+        }
+    }
+}
+
+/// Compare two introspectable objects, and return a list of all elements that
+/// differ between them.
+///
+/// Children are matched by key. Entries of [IntrospectKind::Map] objects (which introspect as
+/// alternating key and value children) are instead matched by the value of their key, so
+/// that the iteration order of the maps does not matter. Use `#[savefile_introspect_key]`
+/// to control the value of map keys of struct type.
+///
+/// Only the first [MAX_CHILDREN] children of each element are compared.
+///
+/// Uses [IntrospectDiffLimits::default]. See [introspect_diff_with_limits].
+pub fn introspect_diff(old: &dyn Introspect, new: &dyn Introspect) -> Vec<IntrospectDiffEntry> {
+    introspect_diff_with_limits(old, new, IntrospectDiffLimits::default())
+}
+
+/// Like [introspect_diff], but with custom limits on depth and the number of reported differences.
+///
+/// If `limits.max_entries` differences are returned, there may be more differences which were not reported.
+pub fn introspect_diff_with_limits(
+    old: &dyn Introspect,
+    new: &dyn Introspect,
+    limits: IntrospectDiffLimits,
+) -> Vec<IntrospectDiffEntry> {
+    let mut result = Vec::new();
+    let mut path = Vec::new();
+    diff_impl(old, new, &limits, &mut path, &mut result);
+    result
+}
+
+struct IntrospectEntry<'a> {
+    // For map entries, this is the value of the map key, and `item` is the map value
+    key: String,
+    item: Box<dyn IntrospectItem<'a> + 'a>,
+}
+
+/// Returns the children of the object, with the entries of [IntrospectKind::Map] objects (which
+/// introspect as alternating key and value children) combined into one entry keyed by the value
+/// of the map key.
+/// Returns at most `max_entries` entries, and true if there were more.
+fn introspect_entries(object: &dyn Introspect, max_entries: usize) -> (Vec<IntrospectEntry<'_>>, bool) {
+    let is_map = object.introspect_kind() == IntrospectKind::Map;
+    let children_per_entry = if is_map { 2 } else { 1 };
+    let mut children = object
+        .introspect_children_range(0, max_entries.saturating_add(1).saturating_mul(children_per_entry))
+        .into_iter();
+    let mut entries = Vec::new();
+    while let Some(child) = children.next() {
+        if is_map {
+            let Some(value) = children.next() else {
+                break;
+            };
+            entries.push(IntrospectEntry {
+                key: child.val().introspect_value(),
+                item: value,
+            });
+        } else {
+            entries.push(IntrospectEntry {
+                key: child.key().to_string(),
+                item: child,
+            });
+        }
+    }
+    let truncated = entries.len() > max_entries;
+    entries.truncate(max_entries);
+    (entries, truncated)
+}
+
+fn diff_impl(
+    old: &dyn Introspect,
+    new: &dyn Introspect,
+    limits: &IntrospectDiffLimits,
+    path: &mut Vec<String>,
+    result: &mut Vec<IntrospectDiffEntry>,
+) {
+    if result.len() >= limits.max_entries {
+        return;
+    }
+    let old_value = old.introspect_value();
+    let new_value = new.introspect_value();
+    if old_value != new_value {
+        result.push(IntrospectDiffEntry {
+            path: path.clone(),
+            kind: IntrospectDiffKind::Changed,
+            old_value: Some(old_value),
+            new_value: Some(new_value),
+        });
+    }
+    if path.len() >= limits.max_depth {
+        return;
+    }
+
+    let (old_children, _) = introspect_entries(old, old.introspect_len().min(MAX_CHILDREN));
+    let (new_children, _) = introspect_entries(new, new.introspect_len().min(MAX_CHILDREN));
+
+    // Children with the same key are matched in order of appearance
+    let mut new_by_key: HashMap<(&str, usize), usize> = HashMap::new();
+    let mut key_counts: HashMap<&str, usize> = HashMap::new();
+    for (i, child) in new_children.iter().enumerate() {
+        let count = key_counts.entry(&child.key).or_insert(0);
+        new_by_key.insert((&child.key, *count), i);
+        *count += 1;
+    }
+    let mut matched = vec![false; new_children.len()];
+    key_counts.clear();
+    for child in old_children.iter() {
+        if result.len() >= limits.max_entries {
+            return;
+        }
+        let count = key_counts.entry(&child.key).or_insert(0);
+        let counterpart = new_by_key.get(&(child.key.as_str(), *count)).copied();
+        *count += 1;
+        path.push(child.key.clone());
+        if let Some(new_index) = counterpart {
+            matched[new_index] = true;
+            diff_impl(
+                child.item.val(),
+                new_children[new_index].item.val(),
+                limits,
+                path,
+                result,
+            );
+        } else {
+            result.push(IntrospectDiffEntry {
+                path: path.clone(),
+                kind: IntrospectDiffKind::Removed,
+                old_value: Some(child.item.val().introspect_value()),
+                new_value: None,
+            });
+        }
+        path.pop();
+    }
+    for (child, matched) in new_children.iter().zip(matched) {
+        if result.len() >= limits.max_entries {
+            return;
+        }
+        if !matched {
+            path.push(child.key.clone());
+            result.push(IntrospectDiffEntry {
+                path: path.clone(),
+                kind: IntrospectDiffKind::Added,
+                old_value: None,
+                new_value: Some(child.item.val().introspect_value()),
+            });
+            path.pop();
+        }
+    }
+}
+
+/// Options for [introspect_to_json] and [introspect_to_text]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectDumpOptions {
+    /// Elements deeper than this are written as their introspected value, without children.
+    /// Top level children have depth 1.
+    pub max_depth: usize,
+    /// At most this many children are written for each element. Map entries count as one child.
+    pub max_children: usize,
+    /// The values of elements with any of these keys are replaced by `<redacted>`.
+    pub redacted_keys: Vec<String>,
+}
+
+impl Default for IntrospectDumpOptions {
+    fn default() -> Self {
+        IntrospectDumpOptions {
+            max_depth: 32,
+            max_children: 1000,
+            redacted_keys: vec![],
+        }
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// Write the introspection tree of the object as a JSON document.
+///
+/// Elements whose children are keyed 0, 1, 2, etc. (like `Vec`) become arrays, other elements with
+/// children become objects. Map entries use the value of the map key as key, see `#[savefile_introspect_key]`.
+/// If an element with children also has a value, like an enum variant, it is written
+/// as the member `"$value"`. Elements without children are written using
+/// [Introspect::introspect_typed_value], where [IntrospectValue::None] becomes `null`,
+/// except for empty collections and structs, which become `[]` or `{}` depending
+/// on [Introspect::introspect_kind].
+///
+/// If there are more than `options.max_children` children, the member `"..."` (or array
+/// element `"..."`) is added.
+pub fn introspect_to_json(object: &dyn Introspect, options: &IntrospectDumpOptions) -> String {
+    let mut out = String::new();
+    write_json_node(&mut out, object, options, 0);
+    out.push('\n');
+    out
+}
+
+/// Write the introspection tree of the object as indented text, with one `key: value` line per element.
+///
+/// Children are indented by two spaces relative to their parent. Elements cut off by
+/// `options.max_depth` or `options.max_children` are marked with `...`. See [introspect_to_json]
+/// for how map entries and redaction are handled.
+pub fn introspect_to_text(object: &dyn Introspect, options: &IntrospectDumpOptions) -> String {
+    let mut out = String::new();
+    let (entries, truncated) = introspect_entries(object, options.max_children);
+    if entries.is_empty() {
+        out.push_str(&text_value(object.introspect_value()));
+        out.push('\n');
+    }
+    write_text_entries(&mut out, &entries, truncated, options, 1);
+    out
+}
+
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_json_value(out: &mut String, object: &dyn Introspect) {
+    match object.introspect_typed_value() {
+        IntrospectValue::Int(x) => out.push_str(&x.to_string()),
+        IntrospectValue::UInt(x) => out.push_str(&x.to_string()),
+        IntrospectValue::Float(x) if x.is_finite() => out.push_str(&format!("{:?}", x)),
+        IntrospectValue::Bool(x) => out.push_str(if x { "true" } else { "false" }),
+        IntrospectValue::Str(x) => write_json_string(out, &x),
+        IntrospectValue::Char(x) => write_json_string(out, &x.to_string()),
+        IntrospectValue::None => out.push_str("null"),
+        IntrospectValue::Float(_) | IntrospectValue::Timestamp { .. } | IntrospectValue::Bytes(_) => {
+            write_json_string(out, &object.introspect_value())
+        }
+    }
+}
+
+fn is_sequence(entries: &[IntrospectEntry]) -> bool {
+    entries
+        .iter()
+        .enumerate()
+        .all(|(index, entry)| entry.key == index.to_string() || entry.key == format!("#{}", index))
+}
+
+fn write_json_indent(out: &mut String, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+fn write_json_node(out: &mut String, object: &dyn Introspect, options: &IntrospectDumpOptions, depth: usize) {
+    let (entries, truncated) = if depth < options.max_depth {
+        introspect_entries(object, options.max_children)
+    } else {
+        (vec![], false)
+    };
+    if entries.is_empty() {
+        if depth >= options.max_depth && object.introspect_child(0).is_some() {
+            write_json_string(out, &object.introspect_value());
+            return;
+        }
+        if object.introspect_typed_value() == IntrospectValue::None {
+            match object.introspect_kind() {
+                IntrospectKind::Value => {}
+                IntrospectKind::Sequence => {
+                    out.push_str("[]");
+                    return;
+                }
+                IntrospectKind::Map | IntrospectKind::Struct => {
+                    out.push_str("{}");
+                    return;
+                }
+            }
+        }
+        write_json_value(out, object);
+        return;
+    }
+    let typed_value = object.introspect_typed_value();
+    if is_sequence(&entries) && typed_value == IntrospectValue::None {
+        out.push('[');
+        for (index, entry) in entries.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            write_json_indent(out, depth + 1);
+            write_json_node(out, entry.item.val(), options, depth + 1);
+        }
+        if truncated {
+            out.push(',');
+            write_json_indent(out, depth + 1);
+            out.push_str("\"...\"");
+        }
+        write_json_indent(out, depth);
+        out.push(']');
+        return;
+    }
+
+    out.push('{');
+    let mut first = true;
+    let mut member = |out: &mut String, key: &str| {
+        if !first {
+            out.push(',');
+        }
+        first = false;
+        write_json_indent(out, depth + 1);
+        write_json_string(out, key);
+        out.push_str(": ");
+    };
+    if typed_value != IntrospectValue::None {
+        member(out, "$value");
+        write_json_value(out, object);
+    }
+    let mut key_counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries.iter() {
+        // Keys in JSON objects should be unique
+        let count = key_counts.entry(&entry.key).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            member(out, &format!("{} ({})", entry.key, count));
+        } else {
+            member(out, &entry.key);
+        }
+        if options.redacted_keys.contains(&entry.key) {
+            write_json_string(out, REDACTED);
+        } else {
+            write_json_node(out, entry.item.val(), options, depth + 1);
+        }
+    }
+    if truncated {
+        member(out, "...");
+        out.push_str("null");
+    }
+    write_json_indent(out, depth);
+    out.push('}');
+}
+
+fn text_value(value: String) -> String {
+    value.replace('\n', "\\n")
+}
+
+fn write_text_entries(
+    out: &mut String,
+    entries: &[IntrospectEntry],
+    truncated: bool,
+    options: &IntrospectDumpOptions,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth - 1);
+    for entry in entries {
+        out.push_str(&indent);
+        out.push_str(&entry.key);
+        out.push(':');
+        if options.redacted_keys.contains(&entry.key) {
+            out.push(' ');
+            out.push_str(REDACTED);
+            out.push('\n');
+            continue;
+        }
+        let object = entry.item.val();
+        let (children, children_truncated) = if depth < options.max_depth {
+            introspect_entries(object, options.max_children)
+        } else {
+            (vec![], false)
+        };
+        if children.is_empty() || object.introspect_typed_value() != IntrospectValue::None {
+            out.push(' ');
+            out.push_str(&text_value(object.introspect_value()));
+        }
+        if children.is_empty() && object.introspect_child(0).is_some() {
+            out.push_str(" ...");
+        }
+        out.push('\n');
+        write_text_entries(out, &children, children_truncated, options, depth + 1);
+    }
+    if truncated {
+        out.push_str(&indent);
+        out.push_str("...\n");
+    }
 }
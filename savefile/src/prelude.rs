@@ -1,12 +1,20 @@
 pub use {
-    super::deserialize_slice_as_vec, super::get_result_schema, super::get_schema, super::introspect_item, super::load,
-    super::load_file, super::load_file_noschema, super::load_from_mem, super::load_noschema, super::save,
-    super::save_file, super::save_file_noschema, super::save_noschema, super::save_to_mem, super::AbiRemoved,
-    super::Canary1, super::Deserialize, super::Deserializer, super::Field, super::Introspect, super::IntrospectItem,
-    super::IntrospectedElementKey, super::IntrospectionResult, super::Introspector, super::IntrospectorNavCommand,
-    super::IsPacked, super::Packed, super::Removed, super::SavefileError, super::Schema, super::SchemaEnum,
-    super::SchemaPrimitive, super::SchemaStruct, super::Serialize, super::Serializer, super::Variant,
-    super::WithSchema, super::WithSchemaContext,
+    super::deserialize_slice_as_vec, super::get_result_schema, super::get_schema, super::introspect_diff,
+    super::introspect_item, super::introspect_item_mut, super::introspect_to_json, super::introspect_to_text,
+    super::load, super::load_file, super::load_file_noschema, super::load_file_strict, super::load_from_mem,
+    super::load_noschema, super::load_strict, super::load_with_registry, super::read_file_info, super::read_info,
+    super::save, super::save_file, super::save_file_noschema, super::save_file_with_metadata, super::save_noschema,
+    super::save_to_mem, super::save_with_metadata, super::save_with_schema_ref, super::verify_named_schema_history,
+    super::verify_schema_history, super::AbiRemoved, super::Canary1, super::Deserialize, super::Deserializer,
+    super::Field, super::FileInfo, super::Introspect, super::IntrospectDiffEntry, super::IntrospectDiffKind,
+    super::IntrospectDumpOptions, super::IntrospectItem, super::IntrospectItemMut, super::IntrospectKind,
+    super::IntrospectMut, super::IntrospectMutField, super::IntrospectMutFieldEditable,
+    super::IntrospectMutFieldReadOnly, super::IntrospectQueryMatch, super::IntrospectValue,
+    super::IntrospectedElementKey, super::IntrospectionError, super::IntrospectionResult, super::Introspector,
+    super::IntrospectorNavCommand, super::IsPacked, super::Packed, super::Remote, super::RemoteMirror, super::Removed,
+    super::SavefileError, super::Schema, super::SchemaEnum, super::SchemaFingerprintCache, super::SchemaPrimitive,
+    super::SchemaRegistry, super::SchemaStruct, super::Serialize, super::Serializer, super::Variant, super::WithSchema,
+    super::WithSchemaContext,
 };
 
 pub use byteorder::{LittleEndian, ReadBytesExt};